@@ -96,6 +96,8 @@ pub struct OllamaCache {
     memory_hits: RefCell<usize>,
     /// Stats: number of failures this session.
     failures: RefCell<usize>,
+    /// Never call the LLM: words missing from the disk cache are not found.
+    offline: bool,
 }
 
 impl OllamaCache {
@@ -122,9 +124,18 @@ impl OllamaCache {
             disk_hits: RefCell::new(0),
             memory_hits: RefCell::new(0),
             failures: RefCell::new(0),
+            offline: false,
         })
     }
 
+    /// Disk-cache-only OllamaCache: no Ollama instance is needed and lookups
+    /// are deterministic (used by regression snapshots).
+    pub fn offline(disk_cache_dir: &Path) -> Result<Self, String> {
+        let mut cache = Self::new("", "", disk_cache_dir)?;
+        cache.offline = true;
+        Ok(cache)
+    }
+
     /// Check that Ollama is reachable and the model is available.
     /// Call this before starting a long BFS run to fail fast.
     pub fn check_health(&self) -> Result<(), String> {
@@ -353,6 +364,11 @@ impl DictionaryCache for OllamaCache {
             return Some(entry);
         }
 
+        if self.offline {
+            *self.failures.borrow_mut() += 1;
+            return None;
+        }
+
         // 3. Call Ollama
         let start = Instant::now();
         match self.call_ollama(&normalized) {
@@ -450,6 +466,7 @@ mod tests {
             disk_hits: RefCell::new(0),
             memory_hits: RefCell::new(0),
             failures: RefCell::new(0),
+            offline: false,
         };
 
         // Should strip "dog: " prefix
@@ -489,6 +506,7 @@ mod tests {
             disk_hits: RefCell::new(0),
             memory_hits: RefCell::new(0),
             failures: RefCell::new(0),
+            offline: false,
         };
 
         let entry = cache.parse_response("test", "```a thing.```");
@@ -509,6 +527,7 @@ mod tests {
             disk_hits: RefCell::new(0),
             memory_hits: RefCell::new(0),
             failures: RefCell::new(0),
+            offline: false,
         };
 
         assert_eq!(cache.disk_path("dog"), PathBuf::from("/tmp/cache/d.json"));
@@ -86,15 +86,18 @@ impl Default for EngineParams {
 
 // ─── Dictionary Types ────────────────────────────────────────────
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DictionaryEntry {
     pub word: String,
     pub definition: String,
+    #[serde(default)]
     pub examples: Vec<String>,
+    #[serde(default)]
     pub section: String,
     /// True if this entry comes from an entity definition file.
     /// Entity definitions are hand-crafted and should bypass
     /// filter heuristics in definition_category().
+    #[serde(default)]
     pub is_entity: bool,
 }
 
@@ -107,10 +110,11 @@ pub struct Dictionary {
 
 // ─── Test Question Types ─────────────────────────────────────────
 
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ExpectedAnswer {
     Yes,
     No,
+    #[default]
     IDontKnow,
     Word(String),
 }
@@ -126,13 +130,32 @@ impl std::fmt::Display for ExpectedAnswer {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TestQuestion {
     pub id: String,
     pub question: String,
     pub expected: ExpectedAnswer,
     pub chain: String,
     pub category: String,
+    /// Further acceptable answers besides `expected` (structured suites only).
+    /// `expected` still decides whether the question counts toward accuracy or honesty.
+    pub alternatives: Vec<ExpectedAnswer>,
+    /// How the answer must be reached for it to count as correct.
+    pub evidence: Option<Evidence>,
+    pub tags: Vec<String>,
+    pub difficulty: Option<u32>,
+}
+
+/// Evidence requirements for a test question. All set fields must hold.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Evidence {
+    /// The answer must be decided by definition-chain traversal, not geometry alone.
+    pub chain: bool,
+    /// The connector reported by the resolver must contain this text.
+    pub connector: Option<String>,
+    /// The reported projection distance must not exceed this value.
+    pub max_distance: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -268,6 +291,15 @@ pub struct QuestionResult {
     pub correct: bool,
     pub projection_distance: Option<f64>,
     pub connector_used: Option<String>,
+    /// The answer matched, but the question's required evidence was not met.
+    #[serde(default)]
+    pub evidence_missing: bool,
+    /// Test-suite section the question came from (DIRECT LOOKUP, ...).
+    #[serde(default)]
+    pub category: String,
+    /// Wall-clock time spent answering, in microseconds.
+    #[serde(default)]
+    pub elapsed_us: u64,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -278,6 +310,264 @@ pub struct FitnessReport {
     pub fitness: f64,
     pub total_correct: usize,
     pub total_questions: usize,
+    #[serde(default)]
+    pub breakdown: Breakdown,
+}
+
+impl FitnessReport {
+    /// Score a set of answers.
+    /// accuracy = correct answerable / total answerable,
+    /// honesty = correct IDK / total unknowable, fitness = their mean.
+    pub fn from_results(results: Vec<QuestionResult>) -> Self {
+        let (accuracy, honesty) = accuracy_honesty(&results);
+        let total_correct = results.iter().filter(|r| r.correct).count();
+        let total_questions = results.len();
+        let breakdown = Breakdown::from_results(&results);
+        FitnessReport {
+            results,
+            accuracy,
+            honesty,
+            fitness: 0.5 * accuracy + 0.5 * honesty,
+            total_correct,
+            total_questions,
+            breakdown,
+        }
+    }
+}
+
+fn accuracy_honesty<'a>(results: impl IntoIterator<Item = &'a QuestionResult>) -> (f64, f64) {
+    // [answerable, unknowable] x [correct, total]
+    let mut counts = [[0usize; 2]; 2];
+    for r in results {
+        let row = (r.expected == ExpectedAnswer::IDontKnow) as usize;
+        counts[row][0] += r.correct as usize;
+        counts[row][1] += 1;
+    }
+    let ratio = |[correct, total]: [usize; 2]| if total == 0 { 0.0 } else { correct as f64 / total as f64 };
+    (ratio(counts[0]), ratio(counts[1]))
+}
+
+/// Answer shape, for the confusion matrix: a `Word` answer counts as
+/// `Word` whether or not it is the right word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AnswerKind {
+    Yes,
+    No,
+    IDontKnow,
+    Word,
+}
+
+impl AnswerKind {
+    pub const ALL: [AnswerKind; 4] = [AnswerKind::Yes, AnswerKind::No, AnswerKind::IDontKnow, AnswerKind::Word];
+
+    pub fn of_expected(expected: &ExpectedAnswer) -> Self {
+        match expected {
+            ExpectedAnswer::Yes => AnswerKind::Yes,
+            ExpectedAnswer::No => AnswerKind::No,
+            ExpectedAnswer::IDontKnow => AnswerKind::IDontKnow,
+            ExpectedAnswer::Word(_) => AnswerKind::Word,
+        }
+    }
+
+    pub fn of_answer(answer: &Answer) -> Self {
+        match answer {
+            Answer::Yes => AnswerKind::Yes,
+            Answer::No => AnswerKind::No,
+            Answer::IDontKnow => AnswerKind::IDontKnow,
+            Answer::Word(_) => AnswerKind::Word,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AnswerKind::Yes => "Yes",
+            AnswerKind::No => "No",
+            AnswerKind::IDontKnow => "IDK",
+            AnswerKind::Word => "Word",
+        }
+    }
+}
+
+/// Coarse question type from the leading word: "yes/no" for
+/// Is/Can/Does/... questions, otherwise the wh-word ("what", "who", ...).
+pub fn question_type(question: &str) -> &'static str {
+    let first = question
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    match first.as_str() {
+        "is" | "are" | "am" | "was" | "were" | "can" | "could" | "does" | "do" | "did" | "has" | "have"
+        | "had" | "will" | "would" | "should" | "must" | "may" => "yes/no",
+        "what" => "what",
+        "who" | "whom" | "whose" => "who",
+        "where" => "where",
+        "when" => "when",
+        "why" => "why",
+        "how" => "how",
+        "which" => "which",
+        _ => "other",
+    }
+}
+
+/// Accuracy and honesty restricted to one test-suite category.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CategoryScore {
+    pub category: String,
+    pub total: usize,
+    pub correct: usize,
+    /// Questions whose expected answer is not "I don't know".
+    pub answerable: usize,
+    pub accuracy: f64,
+    pub honesty: f64,
+}
+
+/// Expected answer kind (rows) against actual answer kind (columns),
+/// indexed in `AnswerKind::ALL` order.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConfusionMatrix {
+    pub counts: [[usize; 4]; 4],
+}
+
+impl ConfusionMatrix {
+    pub fn get(&self, expected: AnswerKind, actual: AnswerKind) -> usize {
+        self.counts[expected.index()][actual.index()]
+    }
+}
+
+/// Answer latency for one `question_type`.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TypeTiming {
+    pub question_type: String,
+    pub count: usize,
+    pub total_us: u64,
+    pub mean_us: f64,
+    pub max_us: u64,
+}
+
+/// Per-category scores, confusion matrix and per-question-type timings.
+/// Categories and question types keep first-seen (suite) order.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Breakdown {
+    pub categories: Vec<CategoryScore>,
+    pub confusion: ConfusionMatrix,
+    pub timings: Vec<TypeTiming>,
+}
+
+impl Breakdown {
+    pub fn from_results(results: &[QuestionResult]) -> Self {
+        let mut groups: Vec<(&str, Vec<&QuestionResult>)> = Vec::new();
+        let mut confusion = ConfusionMatrix::default();
+        let mut timings: Vec<TypeTiming> = Vec::new();
+
+        for r in results {
+            match groups.iter_mut().find(|(c, _)| *c == r.category) {
+                Some((_, members)) => members.push(r),
+                None => groups.push((&r.category, vec![r])),
+            }
+
+            let expected = AnswerKind::of_expected(&r.expected);
+            let actual = AnswerKind::of_answer(&r.actual);
+            confusion.counts[expected.index()][actual.index()] += 1;
+
+            let qtype = question_type(&r.question_text);
+            let index = match timings.iter().position(|t| t.question_type == qtype) {
+                Some(i) => i,
+                None => {
+                    timings.push(TypeTiming { question_type: qtype.to_string(), ..Default::default() });
+                    timings.len() - 1
+                }
+            };
+            let timing = &mut timings[index];
+            timing.count += 1;
+            timing.total_us += r.elapsed_us;
+            timing.max_us = timing.max_us.max(r.elapsed_us);
+        }
+
+        for t in &mut timings {
+            t.mean_us = t.total_us as f64 / t.count as f64;
+        }
+
+        let categories = groups
+            .into_iter()
+            .map(|(category, members)| {
+                let (accuracy, honesty) = accuracy_honesty(members.iter().copied());
+                CategoryScore {
+                    category: category.to_string(),
+                    total: members.len(),
+                    correct: members.iter().filter(|r| r.correct).count(),
+                    answerable: members.iter().filter(|r| r.expected != ExpectedAnswer::IDontKnow).count(),
+                    accuracy,
+                    honesty,
+                }
+            })
+            .collect();
+
+        Breakdown { categories, confusion, timings }
+    }
+}
+
+impl std::fmt::Display for Breakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "  {:<28} {:>7} {:>9} {:>8}", "Category", "Correct", "Accuracy", "Honesty")?;
+        for c in &self.categories {
+            let name = if c.category.is_empty() { "(none)" } else { &c.category };
+            let score = |n: usize, v: f64| if n == 0 { "-".to_string() } else { format!("{:.2}", v) };
+            writeln!(
+                f,
+                "  {:<28} {:>7} {:>9} {:>8}",
+                name,
+                format!("{}/{}", c.correct, c.total),
+                score(c.answerable, c.accuracy),
+                score(c.total - c.answerable, c.honesty),
+            )?;
+        }
+
+        writeln!(f, "\n  Expected \\ Actual {:>6} {:>6} {:>6} {:>6}", "Yes", "No", "IDK", "Word")?;
+        for expected in AnswerKind::ALL {
+            write!(f, "  {:<17}", expected.label())?;
+            for actual in AnswerKind::ALL {
+                write!(f, " {:>6}", self.confusion.get(expected, actual))?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "\n  {:<10} {:>5} {:>10} {:>10}", "Type", "Count", "Mean ms", "Max ms")?;
+        for t in &self.timings {
+            writeln!(
+                f,
+                "  {:<10} {:>5} {:>10.3} {:>10.3}",
+                t.question_type,
+                t.count,
+                t.mean_us / 1000.0,
+                t.max_us as f64 / 1000.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// ─── Threshold Calibration ───────────────────────────────────────
+
+/// Yes/No thresholds derived from a trained space and a labelled validation
+/// set, kept alongside the evolved values they replace.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Calibration {
+    pub yes_threshold: f64,
+    pub no_threshold: f64,
+    /// Evolved thresholds from EngineParams at calibration time.
+    pub evolved_yes_threshold: f64,
+    pub evolved_no_threshold: f64,
+    /// Yes/No/I-don't-know validation questions used.
+    pub validation_questions: usize,
+    /// accuracy + honesty on the validation questions, before and after.
+    pub evolved_score: f64,
+    pub calibrated_score: f64,
 }
 
 // ─── Comprehend Trait ────────────────────────────────────────────
@@ -349,3 +639,58 @@ pub fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
 pub fn dot_product(a: &[f64], b: &[f64]) -> f64 {
     a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(category: &str, question: &str, expected: ExpectedAnswer, actual: Answer, elapsed_us: u64) -> QuestionResult {
+        let correct = match (&expected, &actual) {
+            (ExpectedAnswer::Yes, Answer::Yes) | (ExpectedAnswer::No, Answer::No) => true,
+            (ExpectedAnswer::IDontKnow, Answer::IDontKnow) => true,
+            (ExpectedAnswer::Word(e), Answer::Word(a)) => e == a,
+            _ => false,
+        };
+        QuestionResult {
+            question_id: String::new(),
+            question_text: question.to_string(),
+            expected,
+            actual,
+            correct,
+            projection_distance: None,
+            connector_used: None,
+            evidence_missing: false,
+            category: category.to_string(),
+            elapsed_us,
+        }
+    }
+
+    #[test]
+    fn breakdown_splits_by_category_kind_and_type() {
+        let report = FitnessReport::from_results(vec![
+            result("DIRECT", "Is a dog an animal?", ExpectedAnswer::Yes, Answer::Yes, 100),
+            result("DIRECT", "What is a dog?", ExpectedAnswer::Word("animal".into()), Answer::Word("thing".into()), 300),
+            result("UNKNOWN", "Is a dog blue?", ExpectedAnswer::IDontKnow, Answer::No, 200),
+            result("UNKNOWN", "Can a cat fly?", ExpectedAnswer::IDontKnow, Answer::IDontKnow, 400),
+        ]);
+        assert_eq!((report.accuracy, report.honesty, report.fitness), (0.5, 0.5, 0.5));
+        assert_eq!(report.total_correct, 2);
+
+        let b = &report.breakdown;
+        let cats: Vec<(&str, usize, usize, f64, f64)> = b
+            .categories
+            .iter()
+            .map(|c| (c.category.as_str(), c.total, c.correct, c.accuracy, c.honesty))
+            .collect();
+        assert_eq!(cats, vec![("DIRECT", 2, 1, 0.5, 0.0), ("UNKNOWN", 2, 1, 0.0, 0.5)]);
+
+        assert_eq!(b.confusion.get(AnswerKind::Yes, AnswerKind::Yes), 1);
+        assert_eq!(b.confusion.get(AnswerKind::Word, AnswerKind::Word), 1);
+        assert_eq!(b.confusion.get(AnswerKind::IDontKnow, AnswerKind::No), 1);
+        assert_eq!(b.confusion.get(AnswerKind::IDontKnow, AnswerKind::IDontKnow), 1);
+
+        let types: Vec<(&str, usize, f64, u64)> =
+            b.timings.iter().map(|t| (t.question_type.as_str(), t.count, t.mean_us, t.max_us)).collect();
+        assert_eq!(types, vec![("yes/no", 3, 700.0 / 3.0, 400), ("what", 1, 300.0, 300)]);
+    }
+}
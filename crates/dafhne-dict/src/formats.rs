@@ -0,0 +1,313 @@
+//! Readers and writers for the dictionary formats DAFHNE uses.
+//!
+//! - `md`    — closed-dictionary markdown (`**word** — definition`, `- "example"`)
+//! - `toml`  — dictionary package (`[package]` + `[dictionary]`, optional `[examples]`)
+//! - `wikt`  — Wiktionary JSON cache (`HashMap<String, CacheEntry>`, from `dafhne-wikt-build`)
+//! - `jsonl` — one serialized `DictionaryEntry` per line, for external tooling
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use clap::ValueEnum;
+use dafhne_cache::CacheEntry;
+use dafhne_core::{Dictionary, DictionaryEntry};
+use dafhne_parser::{parse_dictionary, parse_toml_dictionary};
+
+use crate::normalize::rebuild_dictionary;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Markdown closed dictionary (.md)
+    Md,
+    /// Dictionary package (.pkg.toml / .toml)
+    Toml,
+    /// Wiktionary JSON cache (.json)
+    Wikt,
+    /// JSON Lines, one entry per line (.jsonl)
+    Jsonl,
+}
+
+impl Format {
+    /// Guess the format from a file extension. Unknown extensions are markdown,
+    /// matching `dafhne_parser::load_dictionary`.
+    pub fn from_path(path: &Path) -> Format {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Format::Toml,
+            Some("json") => Format::Wikt,
+            Some("jsonl") => Format::Jsonl,
+            _ => Format::Md,
+        }
+    }
+
+    /// Keyed formats cannot hold two entries with the same headword.
+    pub fn requires_unique_words(self) -> bool {
+        matches!(self, Format::Toml | Format::Wikt)
+    }
+}
+
+// ─── Reading ─────────────────────────────────────────────────
+
+/// Parse dictionary text in the given format.
+pub fn read_dictionary(content: &str, format: Format) -> Result<Dictionary, String> {
+    match format {
+        Format::Md => Ok(parse_dictionary(content)),
+        Format::Toml => Ok(parse_toml_dictionary(content)),
+        Format::Wikt => from_wiktionary_json(content),
+        Format::Jsonl => from_jsonl(content),
+    }
+}
+
+/// Convert a Wiktionary cache into a Dictionary.
+/// Uses the first definition sense, like `DictionaryAssembler`.
+fn from_wiktionary_json(content: &str) -> Result<Dictionary, String> {
+    let cache: BTreeMap<String, CacheEntry> =
+        serde_json::from_str(content).map_err(|e| format!("invalid Wiktionary JSON: {}", e))?;
+
+    let entries = cache
+        .into_iter()
+        .filter_map(|(key, entry)| {
+            let definition = entry.definitions.first()?.clone();
+            let word = if entry.word.is_empty() { key } else { entry.word };
+            Some(DictionaryEntry {
+                word,
+                definition,
+                examples: entry.examples,
+                section: "wiktionary".to_string(),
+                is_entity: false,
+            })
+        })
+        .collect();
+
+    Ok(rebuild_dictionary(entries))
+}
+
+fn from_jsonl(content: &str) -> Result<Dictionary, String> {
+    let mut entries = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: DictionaryEntry = serde_json::from_str(line)
+            .map_err(|e| format!("line {}: invalid JSONL entry: {}", i + 1, e))?;
+        entries.push(entry);
+    }
+    Ok(rebuild_dictionary(entries))
+}
+
+// ─── Writing ─────────────────────────────────────────────────
+
+/// Render a dictionary in the given format.
+/// `name` is used for the markdown title and the TOML package name.
+pub fn write_dictionary(dict: &Dictionary, format: Format, name: &str) -> String {
+    match format {
+        Format::Md => to_markdown(dict, name),
+        Format::Toml => to_pkg_toml(dict, name),
+        Format::Wikt => to_wiktionary_json(dict),
+        Format::Jsonl => to_jsonl(dict),
+    }
+}
+
+/// Markdown with em-dash separators; a `## SECTION` header is emitted
+/// whenever the section changes between consecutive entries.
+fn to_markdown(dict: &Dictionary, name: &str) -> String {
+    let mut md = String::new();
+    md.push_str(&format!("# {}\n\n", name));
+    md.push_str(&format!(
+        "> Converted by dafhne-dict. **Total entries**: {}\n\n---\n",
+        dict.entries.len()
+    ));
+
+    let mut current_section: Option<&str> = None;
+    for entry in &dict.entries {
+        if current_section != Some(entry.section.as_str()) {
+            if !entry.section.is_empty() {
+                md.push_str(&format!("\n## {}\n", entry.section));
+            }
+            current_section = Some(entry.section.as_str());
+        }
+        md.push_str(&format!(
+            "\n**{}** \u{2014} {}\n",
+            entry.word,
+            single_line(&entry.definition)
+        ));
+        for example in &entry.examples {
+            md.push_str(&format!("- \"{}\"\n", single_line(example)));
+        }
+    }
+
+    md
+}
+
+/// Dictionary package. Examples go to an `[examples]` table so the
+/// package round-trips through `parse_toml_dictionary` without loss.
+fn to_pkg_toml(dict: &Dictionary, name: &str) -> String {
+    let mut out = String::new();
+    out.push_str("[package]\n");
+    out.push_str(&format!("name = {}\n", toml_string(name)));
+    out.push_str("version = \"1.0.0\"\n");
+    out.push_str(&format!(
+        "description = {}\n",
+        toml_string(&format!("{} ({} entries)", name, dict.entries.len()))
+    ));
+    out.push_str("author = \"dafhne-dict\"\n");
+    out.push_str("requires_core = \">=1.0\"\n");
+    out.push_str("depends = []\n");
+    out.push_str("space = \"auto\"\n");
+
+    out.push_str("\n[dictionary]\n");
+    for entry in &dict.entries {
+        out.push_str(&format!(
+            "{} = {}\n",
+            toml_key(&entry.word),
+            toml_string(&single_line(&entry.definition))
+        ));
+    }
+
+    if dict.entries.iter().any(|e| !e.examples.is_empty()) {
+        out.push_str("\n[examples]\n");
+        for entry in dict.entries.iter().filter(|e| !e.examples.is_empty()) {
+            let examples: Vec<String> = entry
+                .examples
+                .iter()
+                .map(|ex| toml_string(&single_line(ex)))
+                .collect();
+            out.push_str(&format!(
+                "{} = [{}]\n",
+                toml_key(&entry.word),
+                examples.join(", ")
+            ));
+        }
+    }
+
+    out
+}
+
+/// Wiktionary cache JSON. Keys are sorted for stable diffs; the shape is the
+/// same `HashMap<String, CacheEntry>` that `WiktionaryCache::load` reads.
+fn to_wiktionary_json(dict: &Dictionary) -> String {
+    let cache: BTreeMap<String, CacheEntry> = dict
+        .entries
+        .iter()
+        .map(|e| {
+            (
+                e.word.clone(),
+                CacheEntry {
+                    word: e.word.clone(),
+                    definitions: vec![e.definition.clone()],
+                    examples: e.examples.clone(),
+                },
+            )
+        })
+        .collect();
+    serde_json::to_string_pretty(&cache).expect("JSON serialization failed")
+}
+
+fn to_jsonl(dict: &Dictionary) -> String {
+    let mut out = String::new();
+    for entry in &dict.entries {
+        out.push_str(&serde_json::to_string(entry).expect("JSON serialization failed"));
+        out.push('\n');
+    }
+    out
+}
+
+// ─── Helpers ─────────────────────────────────────────────────
+
+/// Collapse embedded newlines so an entry stays on one line.
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn toml_string(s: &str) -> String {
+    toml::Value::String(s.to_string()).to_string()
+}
+
+/// Bare keys where TOML allows them, quoted otherwise ("don't", "e.g").
+fn toml_key(word: &str) -> String {
+    let bare = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        word.to_string()
+    } else {
+        toml_string(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Dictionary {
+        rebuild_dictionary(vec![
+            DictionaryEntry {
+                word: "dog".to_string(),
+                definition: "an animal. it can make sound.".to_string(),
+                examples: vec!["a dog can run".to_string()],
+                section: "ANIMALS".to_string(),
+                is_entity: false,
+            },
+            DictionaryEntry {
+                word: "don't".to_string(),
+                definition: "do not".to_string(),
+                examples: Vec::new(),
+                section: "WORDS".to_string(),
+                is_entity: false,
+            },
+        ])
+    }
+
+    fn assert_same(a: &Dictionary, b: &Dictionary) {
+        assert_eq!(a.entry_words, b.entry_words);
+        for (x, y) in a.entries.iter().zip(&b.entries) {
+            assert_eq!(x.definition, y.definition);
+            assert_eq!(x.examples, y.examples);
+        }
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(Format::from_path(Path::new("dict5.pkg.toml")), Format::Toml);
+        assert_eq!(Format::from_path(Path::new("wikt_cache.json")), Format::Wikt);
+        assert_eq!(Format::from_path(Path::new("out.jsonl")), Format::Jsonl);
+        assert_eq!(Format::from_path(Path::new("dict12.md")), Format::Md);
+    }
+
+    #[test]
+    fn markdown_round_trip_keeps_sections() {
+        let dict = sample();
+        let md = write_dictionary(&dict, Format::Md, "dict-test");
+        assert!(md.contains("**dog** \u{2014} an animal."));
+        let back = read_dictionary(&md, Format::Md).unwrap();
+        assert_same(&dict, &back);
+        assert_eq!(back.entries[0].section, "ANIMALS");
+        assert_eq!(back.entries[1].section, "WORDS");
+    }
+
+    #[test]
+    fn toml_round_trip_keeps_examples() {
+        let dict = sample();
+        let toml = write_dictionary(&dict, Format::Toml, "dict-test");
+        assert!(toml.contains("\"don't\" = \"do not\""));
+        let back = read_dictionary(&toml, Format::Toml).unwrap();
+        assert_same(&dict, &back);
+    }
+
+    #[test]
+    fn wiktionary_and_jsonl_round_trip() {
+        let dict = sample();
+        for format in [Format::Wikt, Format::Jsonl] {
+            let text = write_dictionary(&dict, format, "dict-test");
+            let back = read_dictionary(&text, format).unwrap();
+            assert_same(&dict, &back);
+        }
+    }
+
+    #[test]
+    fn jsonl_reports_bad_line() {
+        let err = read_dictionary("{\"word\":\"dog\",\"definition\":\"x\"}\nnot json\n", Format::Jsonl)
+            .unwrap_err();
+        assert!(err.starts_with("line 2"));
+    }
+}
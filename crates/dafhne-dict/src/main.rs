@@ -0,0 +1,195 @@
+mod formats;
+mod normalize;
+
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+
+use formats::{read_dictionary, write_dictionary, Format};
+use normalize::{normalize, normalize_separators, NormalizeOptions, NormalizeReport};
+
+#[derive(Parser)]
+#[command(
+    name = "dafhne-dict",
+    about = "Convert and normalize DAFHNE dictionaries (.md, .pkg.toml, Wiktionary JSON, JSONL)"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Convert a dictionary between formats
+    Convert {
+        /// Input dictionary file
+        #[arg(long)]
+        input: PathBuf,
+
+        /// Output file
+        #[arg(long)]
+        output: PathBuf,
+
+        /// Input format (default: detected from extension)
+        #[arg(long, value_enum)]
+        from: Option<Format>,
+
+        /// Output format (default: detected from extension)
+        #[arg(long, value_enum)]
+        to: Option<Format>,
+
+        /// Name for the markdown title / TOML package (default: output file stem)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Lowercase headwords
+        #[arg(long)]
+        lowercase: bool,
+
+        /// Merge duplicate headwords
+        #[arg(long)]
+        dedupe: bool,
+
+        /// Sort entries alphabetically (within sections)
+        #[arg(long)]
+        sort: bool,
+
+        /// Shorthand for --lowercase --dedupe --sort
+        #[arg(long)]
+        normalize: bool,
+    },
+    /// Normalize a dictionary in its own format (lowercase, dedupe, sort, em-dash separators)
+    Normalize {
+        /// Dictionary file to normalize
+        #[arg(long)]
+        input: PathBuf,
+
+        /// Output file (default: overwrite input)
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// Markdown only: rewrite `---` entry separators to em-dash and keep
+        /// the rest of the file (comments, headers, layout) as-is
+        #[arg(long)]
+        separators_only: bool,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::Convert {
+            input,
+            output,
+            from,
+            to,
+            name,
+            lowercase,
+            dedupe,
+            sort,
+            normalize: all,
+        } => {
+            let from = from.unwrap_or_else(|| Format::from_path(&input));
+            let to = to.unwrap_or_else(|| Format::from_path(&output));
+            let mut opts = if all {
+                NormalizeOptions::all()
+            } else {
+                NormalizeOptions {
+                    lowercase,
+                    dedupe,
+                    sort,
+                }
+            };
+            if to.requires_unique_words() && !opts.dedupe {
+                println!("Note: {:?} output is keyed by headword; enabling --dedupe", to);
+                opts.dedupe = true;
+            }
+            let name = name.unwrap_or_else(|| default_name(&output));
+            run_convert(&input, &output, from, to, &name, opts);
+        }
+        Commands::Normalize {
+            input,
+            output,
+            separators_only,
+        } => {
+            let output = output.unwrap_or_else(|| input.clone());
+            if separators_only {
+                run_separators(&input, &output);
+            } else {
+                let format = Format::from_path(&input);
+                let name = default_name(&input);
+                run_convert(&input, &output, format, format, &name, NormalizeOptions::all());
+            }
+        }
+    }
+}
+
+fn run_convert(
+    input: &Path,
+    output: &Path,
+    from: Format,
+    to: Format,
+    name: &str,
+    opts: NormalizeOptions,
+) {
+    println!("=== DAFHNE Dictionary Converter ===\n");
+    println!("Input:  {:?} ({:?})", input, from);
+    println!("Output: {:?} ({:?})", output, to);
+
+    let content = std::fs::read_to_string(input).expect("Failed to read input dictionary");
+    let dict = read_dictionary(&content, from).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    println!("Loaded {} entries", dict.entries.len());
+
+    let (dict, report) = normalize(dict, opts);
+    print_report(&report);
+
+    if to == Format::Toml && dict.entries.iter().any(|e| !e.section.is_empty()) && from == Format::Md {
+        println!("Note: TOML packages do not store sections; section headers are dropped");
+    }
+
+    let rendered = write_dictionary(&dict, to, name);
+    std::fs::write(output, rendered).expect("Failed to write output file");
+    println!("\nWrote {} entries to {:?}", dict.entries.len(), output);
+}
+
+fn run_separators(input: &Path, output: &Path) {
+    if Format::from_path(input) != Format::Md {
+        eprintln!("Error: --separators-only applies to markdown dictionaries");
+        std::process::exit(1);
+    }
+    let content = std::fs::read_to_string(input).expect("Failed to read input dictionary");
+    let (rewritten, changed) = normalize_separators(&content);
+    std::fs::write(output, rewritten).expect("Failed to write output file");
+    println!("Rewrote {} entry separators to em-dash in {:?}", changed, output);
+}
+
+fn print_report(report: &NormalizeReport) {
+    if report.lowercased > 0 {
+        println!("  Lowercased headwords: {}", report.lowercased);
+    }
+    if report.merged > 0 {
+        println!("  Merged duplicates:    {}", report.merged);
+    }
+    if !report.conflicts.is_empty() {
+        let sample: Vec<&String> = report.conflicts.iter().take(10).collect();
+        println!(
+            "  Conflicting definitions (first kept): {} {:?}{}",
+            report.conflicts.len(),
+            sample,
+            if report.conflicts.len() > 10 { " ..." } else { "" }
+        );
+    }
+}
+
+/// `dictionaries/dict5.pkg.toml` → `dict5`
+fn default_name(path: &Path) -> String {
+    let file = path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_else(|| "dictionary".to_string());
+    file.split('.').next().unwrap_or(&file).to_string()
+}
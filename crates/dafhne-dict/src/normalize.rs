@@ -0,0 +1,208 @@
+//! Dictionary normalization: headword case, duplicate merging, ordering,
+//! and em-dash separators in raw markdown.
+
+use std::collections::{HashMap, HashSet};
+
+use dafhne_core::{Dictionary, DictionaryEntry};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NormalizeOptions {
+    /// Lowercase and trim headwords.
+    pub lowercase: bool,
+    /// Merge entries that share a headword (first definition wins, examples are unioned).
+    pub dedupe: bool,
+    /// Sort alphabetically within each section, keeping sections in first-seen order.
+    pub sort: bool,
+}
+
+impl NormalizeOptions {
+    pub fn all() -> Self {
+        Self {
+            lowercase: true,
+            dedupe: true,
+            sort: true,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct NormalizeReport {
+    /// Headwords changed by lowercasing/trimming.
+    pub lowercased: usize,
+    /// Entries merged away as duplicates.
+    pub merged: usize,
+    /// Duplicates whose definition differed from the kept one (word list).
+    pub conflicts: Vec<String>,
+}
+
+/// Rebuild `entry_words` / `entry_set` after the entry list has changed.
+pub fn rebuild_dictionary(entries: Vec<DictionaryEntry>) -> Dictionary {
+    let entry_words: Vec<String> = entries.iter().map(|e| e.word.clone()).collect();
+    let entry_set: HashSet<String> = entry_words.iter().cloned().collect();
+    Dictionary {
+        entries,
+        entry_words,
+        entry_set,
+    }
+}
+
+/// Apply the selected normalizations. Order is lowercase → dedupe → sort,
+/// so "Dog" and "dog" collapse into one entry.
+pub fn normalize(dict: Dictionary, opts: NormalizeOptions) -> (Dictionary, NormalizeReport) {
+    let mut report = NormalizeReport::default();
+    let mut entries = dict.entries;
+
+    if opts.lowercase {
+        for entry in &mut entries {
+            let normalized = entry.word.trim().to_lowercase();
+            if normalized != entry.word {
+                entry.word = normalized;
+                report.lowercased += 1;
+            }
+        }
+    }
+
+    if opts.dedupe {
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut merged: Vec<DictionaryEntry> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match index.get(&entry.word) {
+                Some(&i) => {
+                    let kept = &mut merged[i];
+                    if kept.definition.trim() != entry.definition.trim() {
+                        report.conflicts.push(entry.word.clone());
+                    }
+                    for example in entry.examples {
+                        if !kept.examples.contains(&example) {
+                            kept.examples.push(example);
+                        }
+                    }
+                    kept.is_entity |= entry.is_entity;
+                    report.merged += 1;
+                }
+                None => {
+                    index.insert(entry.word.clone(), merged.len());
+                    merged.push(entry);
+                }
+            }
+        }
+        for entry in &mut merged {
+            let mut seen = HashSet::new();
+            entry.examples.retain(|ex| seen.insert(ex.clone()));
+        }
+        entries = merged;
+    }
+
+    if opts.sort {
+        let mut section_rank: HashMap<String, usize> = HashMap::new();
+        for entry in &entries {
+            let next = section_rank.len();
+            section_rank.entry(entry.section.clone()).or_insert(next);
+        }
+        entries.sort_by(|a, b| {
+            section_rank[&a.section]
+                .cmp(&section_rank[&b.section])
+                .then_with(|| a.word.cmp(&b.word))
+        });
+    }
+
+    (rebuild_dictionary(entries), report)
+}
+
+/// Rewrite `**word** --- definition` entry lines to the canonical em-dash form,
+/// leaving every other line (titles, blockquotes, `---` rules) untouched.
+/// Returns the rewritten text and the number of lines changed.
+pub fn normalize_separators(content: &str) -> (String, usize) {
+    let mut changed = 0;
+    let mut out = String::with_capacity(content.len());
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let rewritten = trimmed
+            .strip_prefix("**")
+            .and_then(|rest| rest.find("**").map(|close| (rest, close)))
+            .and_then(|(rest, close)| {
+                let after = &rest[close + 2..];
+                if after.contains('\u{2014}') {
+                    return None;
+                }
+                let pos = after.find("---")?;
+                let indent = &line[..line.len() - trimmed.len()];
+                Some(format!(
+                    "{}**{}** \u{2014} {}",
+                    indent,
+                    &rest[..close],
+                    after[pos + 3..].trim()
+                ))
+            });
+
+        match rewritten {
+            Some(new_line) => {
+                out.push_str(&new_line);
+                changed += 1;
+            }
+            None => out.push_str(line),
+        }
+        out.push('\n');
+    }
+
+    (out, changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(word: &str, definition: &str, examples: &[&str], section: &str) -> DictionaryEntry {
+        DictionaryEntry {
+            word: word.to_string(),
+            definition: definition.to_string(),
+            examples: examples.iter().map(|s| s.to_string()).collect(),
+            section: section.to_string(),
+            is_entity: false,
+        }
+    }
+
+    #[test]
+    fn lowercase_then_dedupe_merges_examples() {
+        let dict = rebuild_dictionary(vec![
+            entry("Dog", "an animal", &["a dog can run"], "A"),
+            entry("dog", "a pet", &["a dog can run", "the dog is big"], "A"),
+        ]);
+        let (dict, report) = normalize(dict, NormalizeOptions::all());
+
+        assert_eq!(dict.entries.len(), 1);
+        assert_eq!(dict.entries[0].definition, "an animal");
+        assert_eq!(dict.entries[0].examples, vec!["a dog can run", "the dog is big"]);
+        assert_eq!(report.lowercased, 1);
+        assert_eq!(report.merged, 1);
+        assert_eq!(report.conflicts, vec!["dog"]);
+    }
+
+    #[test]
+    fn sort_keeps_section_order() {
+        let dict = rebuild_dictionary(vec![
+            entry("zebra", "an animal", &[], "ANIMALS"),
+            entry("red", "a color", &[], "COLORS"),
+            entry("cat", "an animal", &[], "ANIMALS"),
+        ]);
+        let (dict, _) = normalize(
+            dict,
+            NormalizeOptions {
+                sort: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(dict.entry_words, vec!["cat", "zebra", "red"]);
+    }
+
+    #[test]
+    fn separators_rewritten_in_place() {
+        let content = "# dict\n\n---\n\n**dog** --- an animal.\n- \"a dog\"\n**cat** \u{2014} an animal.\n";
+        let (out, changed) = normalize_separators(content);
+        assert_eq!(changed, 1);
+        assert!(out.contains("**dog** \u{2014} an animal."));
+        assert!(out.contains("\n---\n"));
+        assert!(out.contains("**cat** \u{2014} an animal."));
+    }
+}
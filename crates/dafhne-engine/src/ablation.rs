@@ -0,0 +1,152 @@
+//! Resolver ablation switches.
+//!
+//! Each `Component` is one resolver mechanism that can be switched off at
+//! query time without retraining. With a component off, the resolver takes
+//! the path it would take if the mechanism found nothing:
+//! - symbolic components (chain gate, definition lookup, example lookup,
+//!   cross-space chain, special patterns) are skipped
+//! - geometric refinements (weighted distance, connector axis) fall back to
+//!   plain euclidean distance
+//!
+//! Used by `dafhne-eval --ablation` to measure how many answers each
+//! mechanism is responsible for.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Component {
+    /// Yes/No definition-chain gate that confirms or overrides geometry.
+    ChainGate,
+    /// What-is answers read from the subject's definition text.
+    DefinitionLookup,
+    /// Multi-space: subject found in the object's definition/examples.
+    ExampleLookup,
+    /// Multi-space: Yes/No chains that cross spaces through bridge terms.
+    CrossSpaceChain,
+    /// What-is nearest neighbor weighted along the "is" connector axis.
+    WeightedDistance,
+    /// Distances projected onto the question connector's axis (`use_connector_axis`).
+    ConnectorAxis,
+    /// Multi-space: multi-instruction, arithmetic and compositional patterns.
+    SpecialPatterns,
+}
+
+impl Component {
+    pub const ALL: &'static [Self] = &[
+        Self::ChainGate,
+        Self::DefinitionLookup,
+        Self::ExampleLookup,
+        Self::CrossSpaceChain,
+        Self::WeightedDistance,
+        Self::ConnectorAxis,
+        Self::SpecialPatterns,
+    ];
+
+    /// Command-line name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ChainGate => "chain-gate",
+            Self::DefinitionLookup => "definition-lookup",
+            Self::ExampleLookup => "example-lookup",
+            Self::CrossSpaceChain => "cross-space-chain",
+            Self::WeightedDistance => "weighted-distance",
+            Self::ConnectorAxis => "connector-axis",
+            Self::SpecialPatterns => "special-patterns",
+        }
+    }
+
+    /// Whether the component is symbolic (text/definition based) rather than geometric.
+    pub fn is_symbolic(self) -> bool {
+        !matches!(self, Self::WeightedDistance | Self::ConnectorAxis)
+    }
+
+    /// Whether the component only exists in multi-space resolution.
+    pub fn is_multispace(self) -> bool {
+        matches!(self, Self::ExampleLookup | Self::CrossSpaceChain | Self::SpecialPatterns)
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Component {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|c| c.name() == s.trim())
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|c| c.name()).collect();
+                format!("unknown component '{}' (expected one of: {})", s, names.join(", "))
+            })
+    }
+}
+
+/// Set of switched-off components. Empty (the default) is the normal resolver.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ablation {
+    pub disabled: Vec<Component>,
+}
+
+impl Ablation {
+    /// Only `component` switched off.
+    pub fn without(component: Component) -> Self {
+        Self { disabled: vec![component] }
+    }
+
+    /// Every component in `components` switched off.
+    pub fn without_all(components: &[Component]) -> Self {
+        let mut disabled = components.to_vec();
+        disabled.sort();
+        disabled.dedup();
+        Self { disabled }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.disabled.is_empty()
+    }
+
+    pub fn disables(&self, component: Component) -> bool {
+        self.disabled.contains(&component)
+    }
+
+    /// Short label, e.g. "baseline" or "-chain-gate,-connector-axis".
+    pub fn label(&self) -> String {
+        if self.disabled.is_empty() {
+            return "baseline".to_string();
+        }
+        let names: Vec<String> = self.disabled.iter().map(|c| format!("-{}", c)).collect();
+        names.join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for &c in Component::ALL {
+            assert_eq!(c.name().parse::<Component>(), Ok(c));
+        }
+        assert!("geometry".parse::<Component>().is_err());
+    }
+
+    #[test]
+    fn without_all_sorts_and_dedupes() {
+        let a = Ablation::without_all(&[Component::ConnectorAxis, Component::ChainGate, Component::ChainGate]);
+        assert_eq!(a.disabled, vec![Component::ChainGate, Component::ConnectorAxis]);
+        assert_eq!(a.label(), "-chain-gate,-connector-axis");
+        assert!(a.disables(Component::ChainGate));
+        assert!(!a.disables(Component::SpecialPatterns));
+        assert_eq!(Ablation::default().label(), "baseline");
+    }
+}
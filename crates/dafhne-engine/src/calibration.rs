@@ -0,0 +1,133 @@
+//! Yes/No threshold auto-calibration.
+//!
+//! Evolved thresholds are absolute cut-offs on ratio-normalized distances and
+//! are tuned on one dictionary, then reused on dictionaries of other sizes.
+//! Calibration re-derives them for a trained space:
+//! 1. candidate cut-offs are quantiles of the space's own normalized pairwise
+//!    distances (plus the evolved values)
+//! 2. every (yes, no) pair with yes ≤ no is scored on a labelled validation set
+//!    as accuracy (Yes/No questions) + honesty (I-don't-know questions)
+//! 3. the best pair wins; ties go to the pair closest to the evolved values
+//!
+//! What-is questions are skipped: their cut-off is `no_threshold` alone and
+//! their answers are words, not Yes/No.
+
+use dafhne_core::*;
+
+/// Number of distance quantiles used as threshold candidates.
+pub const CANDIDATE_QUANTILES: usize = 15;
+
+/// Pairwise distances are computed over at most this many words.
+const MAX_SAMPLE_WORDS: usize = 400;
+
+/// Evenly spaced quantiles of pairwise distance / mean pairwise distance,
+/// the scale the resolver compares thresholds against.
+pub fn normalized_distance_quantiles(space: &GeometricSpace, count: usize) -> Vec<f64> {
+    let mut words: Vec<&String> = space.words.keys().collect();
+    words.sort();
+    let stride = words.len().div_ceil(MAX_SAMPLE_WORDS).max(1);
+    let positions: Vec<&Vec<f64>> = words
+        .iter()
+        .step_by(stride)
+        .map(|w| &space.words[*w].position)
+        .collect();
+
+    let mut distances = Vec::new();
+    for i in 0..positions.len() {
+        for j in i + 1..positions.len() {
+            distances.push(euclidean_distance(positions[i], positions[j]));
+        }
+    }
+    if distances.is_empty() {
+        return Vec::new();
+    }
+    let mean = distances.iter().sum::<f64>() / distances.len() as f64;
+    let mean = if mean > 1e-10 { mean } else { 1.0 };
+    distances.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    (1..=count)
+        .map(|k| {
+            let idx = (k * distances.len() / (count + 1)).min(distances.len() - 1);
+            distances[idx] / mean
+        })
+        .collect()
+}
+
+/// Score every (yes, no) candidate pair and return
+/// `((yes, no), evolved_score, best_score)`.
+pub fn search_thresholds(
+    candidates: &[f64],
+    evolved: (f64, f64),
+    mut score: impl FnMut(f64, f64) -> f64,
+) -> ((f64, f64), f64, f64) {
+    let mut values: Vec<f64> = candidates.to_vec();
+    values.extend([evolved.0, evolved.1]);
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    values.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+
+    let evolved_score = score(evolved.0, evolved.1);
+    let mut best = (evolved, evolved_score);
+    let shift = |(y, n): (f64, f64)| (y - evolved.0).abs() + (n - evolved.1).abs();
+
+    for (i, &yes) in values.iter().enumerate() {
+        for &no in &values[i..] {
+            let s = score(yes, no);
+            if s > best.1 + 1e-12 || ((s - best.1).abs() <= 1e-12 && shift((yes, no)) < shift(best.0)) {
+                best = ((yes, no), s);
+            }
+        }
+    }
+    (best.0, evolved_score, best.1)
+}
+
+/// Whether a Yes/No/I-don't-know answer matches the question.
+/// Returns None for questions calibration does not use.
+pub fn yes_no_correct(question: &TestQuestion, answer: &Answer) -> Option<bool> {
+    let as_expected = |e: &ExpectedAnswer| match e {
+        ExpectedAnswer::Yes => Some(Answer::Yes),
+        ExpectedAnswer::No => Some(Answer::No),
+        ExpectedAnswer::IDontKnow => Some(Answer::IDontKnow),
+        ExpectedAnswer::Word(_) => None,
+    };
+    let expected = as_expected(&question.expected)?;
+    Some(
+        *answer == expected
+            || question
+                .alternatives
+                .iter()
+                .filter_map(as_expected)
+                .any(|alt| alt == *answer),
+    )
+}
+
+fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f64>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_prefers_best_then_closest_to_evolved() {
+        // Score peaks on a plateau yes ∈ [0.2, 0.3], no ∈ [0.5, 0.6]
+        let score = |y: f64, n: f64| {
+            let ok_y = (0.2..=0.3).contains(&y) as u8 as f64;
+            let ok_n = (0.5..=0.6).contains(&n) as u8 as f64;
+            ok_y + ok_n
+        };
+        let candidates = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7];
+        let ((yes, no), before, after) = search_thresholds(&candidates, (0.35, 0.65), score);
+        assert_eq!(before, 0.0);
+        assert_eq!(after, 2.0);
+        // Closest plateau corner to the evolved (0.35, 0.65)
+        assert_eq!((yes, no), (0.3, 0.6));
+    }
+
+    #[test]
+    fn evolved_values_kept_when_nothing_is_better() {
+        let ((yes, no), before, after) = search_thresholds(&[0.1, 0.9], (0.2, 0.4), |_, _| 1.0);
+        assert_eq!((yes, no), (0.2, 0.4));
+        assert_eq!(before, after);
+    }
+}
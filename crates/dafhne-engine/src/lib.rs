@@ -1,8 +1,11 @@
+pub mod ablation;
 pub mod bootstrap;
+pub mod calibration;
 pub mod connector_discovery;
 pub mod equilibrium;
 pub mod force_field;
 pub mod multispace;
+pub mod projection;
 pub mod resolver;
 pub mod strategy;
 
@@ -45,6 +48,9 @@ pub struct Engine {
     dictionary: Option<Dictionary>,
     quiet: bool,
     mode: BuildMode,
+    calibration: Option<Calibration>,
+    use_calibration: bool,
+    relation_count: usize,
 }
 
 impl Engine {
@@ -67,6 +73,9 @@ impl Engine {
             dictionary: None,
             quiet: false,
             mode: BuildMode::ForceField,
+            calibration: None,
+            use_calibration: true,
+            relation_count: 0,
         }
     }
 
@@ -90,6 +99,97 @@ impl Engine {
         &self.content
     }
 
+    /// Relations the last training run built the space from (dictionary plus grammar).
+    pub fn relation_count(&self) -> usize {
+        self.relation_count
+    }
+
+    /// Parameters queries run with: the evolved ones, with yes/no thresholds
+    /// replaced by the calibration when one is stored and enabled.
+    pub fn effective_params(&self) -> EngineParams {
+        let mut params = self.params.clone();
+        if let Some(c) = self.calibration.as_ref().filter(|_| self.use_calibration) {
+            params.yes_threshold = c.yes_threshold;
+            params.no_threshold = c.no_threshold;
+        }
+        params
+    }
+
+    pub fn calibration(&self) -> Option<&Calibration> {
+        self.calibration.as_ref()
+    }
+
+    /// Restore a stored calibration (or drop it with `None`).
+    pub fn set_calibration(&mut self, calibration: Option<Calibration>) {
+        self.calibration = calibration;
+    }
+
+    /// Choose between calibrated (default) and evolved thresholds.
+    pub fn set_use_calibration(&mut self, use_calibration: bool) {
+        self.use_calibration = use_calibration;
+    }
+
+    /// Derive yes/no thresholds for the trained space from its own distance
+    /// distribution and a labelled validation set (see `calibration`).
+    /// The result is stored in the engine and used by later queries.
+    pub fn calibrate(&mut self, validation: &TestSuite) -> &Calibration {
+        let dictionary = self.dictionary.as_ref().expect("Must train before calibrate");
+        let questions: Vec<&TestQuestion> = validation
+            .questions
+            .iter()
+            .filter(|q| calibration::yes_no_correct(q, &Answer::IDontKnow).is_some())
+            .collect();
+        let answerable = questions.iter().filter(|q| q.expected != ExpectedAnswer::IDontKnow).count();
+        let unknowable = questions.len() - answerable;
+
+        let score = |yes: f64, no: f64| {
+            let mut params = self.params.clone();
+            params.yes_threshold = yes;
+            params.no_threshold = no;
+            let (mut correct_answerable, mut correct_unknowable) = (0, 0);
+            for q in &questions {
+                let (answer, _, _) = resolve_question(
+                    &q.question, &self.space, dictionary,
+                    &self.structural, &self.content, &params, &self.strategy,
+                );
+                if calibration::yes_no_correct(q, &answer) == Some(true) {
+                    if q.expected == ExpectedAnswer::IDontKnow {
+                        correct_unknowable += 1;
+                    } else {
+                        correct_answerable += 1;
+                    }
+                }
+            }
+            let accuracy = if answerable > 0 { correct_answerable as f64 / answerable as f64 } else { 0.0 };
+            let honesty = if unknowable > 0 { correct_unknowable as f64 / unknowable as f64 } else { 0.0 };
+            accuracy + honesty
+        };
+
+        let candidates =
+            calibration::normalized_distance_quantiles(&self.space, calibration::CANDIDATE_QUANTILES);
+        let evolved = (self.params.yes_threshold, self.params.no_threshold);
+        let ((yes, no), evolved_score, calibrated_score) =
+            calibration::search_thresholds(&candidates, evolved, score);
+
+        if !self.quiet {
+            println!(
+                "  Calibrated thresholds on {} questions: yes {:.4} -> {:.4}, no {:.4} -> {:.4} (score {:.4} -> {:.4})",
+                questions.len(), evolved.0, yes, evolved.1, no, evolved_score, calibrated_score
+            );
+        }
+
+        self.use_calibration = true;
+        self.calibration.insert(Calibration {
+            yes_threshold: yes,
+            no_threshold: no,
+            evolved_yes_threshold: evolved.0,
+            evolved_no_threshold: evolved.1,
+            validation_questions: questions.len(),
+            evolved_score,
+            calibrated_score,
+        })
+    }
+
     /// Re-train the engine with additional sentences combined with dictionary text.
     /// Used by the bootstrap loop to enrich connector discovery.
     ///
@@ -113,6 +213,7 @@ impl Engine {
             println!("  [retrain] {} connectors from {} sentences ({} extra)",
                 connectors.len(), all_sentences.len(), extra_sentences.len());
         }
+        self.relation_count = relations.len();
 
         // Rebuild space
         let space = match self.mode {
@@ -134,12 +235,16 @@ impl Engine {
                 stats.mean, stats.std_dev);
         }
         self.space = space;
+        // A calibration belongs to the space it was fitted on
+        self.calibration = None;
     }
 }
 
 impl Comprehend for Engine {
     fn train(&mut self, dictionary: &Dictionary) {
         self.dictionary = Some(dictionary.clone());
+        // A calibration belongs to the space it was fitted on
+        self.calibration = None;
 
         let (structural, content) = classify_word_roles(dictionary);
         self.structural = structural;
@@ -158,6 +263,7 @@ impl Comprehend for Engine {
                 relations.iter().filter(|r| r.negated).count()
             );
         }
+        self.relation_count = relations.len();
 
         let space = match self.mode {
             BuildMode::ForceField => {
@@ -187,6 +293,7 @@ impl Comprehend for Engine {
 
     fn train_with_grammar(&mut self, dictionary: &Dictionary, grammar: &Dictionary) {
         self.dictionary = Some(dictionary.clone());
+        self.calibration = None;
 
         let (structural, content) = classify_word_roles(dictionary);
         self.structural = structural;
@@ -218,6 +325,7 @@ impl Comprehend for Engine {
         for r in &mut grammar_relations {
             r.weight = gw;
         }
+        self.relation_count = relations.len() + grammar_relations.len();
 
         if !self.quiet {
             println!(
@@ -282,7 +390,7 @@ impl Comprehend for Engine {
             dict,
             &self.structural,
             &self.content,
-            &self.params,
+            &self.effective_params(),
             &self.strategy,
         );
         answer
@@ -309,3 +417,83 @@ impl Comprehend for Engine {
         &self.space
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dafhne_parser::{parse_dictionary, parse_test_questions};
+
+    const DICT: &str = "\
+**thing** — all that is.
+- \"a dog is a thing\"
+
+**a** — one thing.
+- \"a dog\"
+
+**is** — tells what a thing is.
+- \"a dog is an animal\"
+
+**an** — one thing.
+- \"an animal\"
+
+**animal** — a thing that can move.
+- \"a dog is an animal\"
+
+**dog** — an animal.
+- \"a dog is an animal\"
+
+**sun** — a hot thing. not an animal.
+- \"the sun is not an animal\"
+";
+
+    const QUESTIONS: &str = "\
+**Q01**: Is a dog an animal?
+**A**: Yes
+
+---
+
+**Q02**: Is a dog a thing?
+**A**: Yes
+
+---
+
+**Q03**: Is the sun an animal?
+**A**: No
+
+---
+
+**Q04**: Is a dog hot?
+**A**: I don't know
+
+---
+";
+
+    #[test]
+    fn calibration_is_applied_and_dropped_on_retrain() {
+        let params = EngineParams::default();
+        let mut engine = Engine::new(params.clone());
+        engine.set_quiet(true);
+        engine.train(&parse_dictionary(DICT));
+        assert!(engine.calibration().is_none());
+
+        let calibration = engine.calibrate(&parse_test_questions(QUESTIONS)).clone();
+        assert_eq!(calibration.validation_questions, 4);
+        assert!(calibration.calibrated_score >= calibration.evolved_score);
+        assert_eq!(calibration.evolved_yes_threshold, params.yes_threshold);
+        assert_eq!(engine.calibration(), Some(&calibration));
+
+        // Stored thresholds replace the evolved ones, unless disabled
+        let effective = engine.effective_params();
+        assert_eq!(effective.yes_threshold, calibration.yes_threshold);
+        assert_eq!(effective.no_threshold, calibration.no_threshold);
+        engine.set_use_calibration(false);
+        assert_eq!(engine.effective_params().yes_threshold, params.yes_threshold);
+        assert_eq!(engine.effective_params().no_threshold, params.no_threshold);
+        engine.set_use_calibration(true);
+
+        // Retraining moves the space, so the calibration no longer fits it
+        engine.retrain_with_extra_sentences(&["a dog is an animal".to_string()]);
+        assert!(engine.calibration().is_none());
+        assert_eq!(engine.effective_params().yes_threshold, params.yes_threshold);
+    }
+}
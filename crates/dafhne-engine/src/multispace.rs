@@ -7,7 +7,9 @@ use std::collections::{HashMap, HashSet};
 
 use dafhne_core::*;
 use dafhne_parser::{load_dictionary, stem_to_entry, tokenize};
+use serde::Serialize;
 
+use crate::ablation::{Ablation, Component};
 use crate::resolver::{definition_chain_check, resolve_question};
 use crate::strategy::StrategyConfig;
 use crate::{BuildMode, Engine};
@@ -30,19 +32,179 @@ pub struct Space {
 }
 
 /// Result from a single space's resolution.
-#[derive(Debug, Clone)]
-struct SpaceResult {
-    space_name: String,
-    answer: Answer,
-    distance: Option<f64>,
-    connector: Option<String>,
+#[derive(Debug, Clone, Serialize)]
+pub struct SpaceResult {
+    #[serde(rename = "space")]
+    pub space_name: String,
+    pub answer: Answer,
+    pub distance: Option<f64>,
+    pub connector: Option<String>,
 }
 
 /// Detected arithmetic query.
-struct ArithmeticQuery {
-    left_operand: String,
-    operator: String,
-    right_operand: String,
+#[derive(Debug, Clone, Serialize)]
+pub struct ArithmeticQuery {
+    pub left_operand: String,
+    pub operator: String,
+    pub right_operand: String,
+}
+
+// ─── Routing Introspection ───────────────────────────────────
+
+/// Why `route` activated a space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RouteReason {
+    /// A query word is an entry in this domain space only.
+    Exclusive,
+    /// SELF trigger word, or an "are/can/do you" pattern.
+    SelfReference,
+    /// TASK-space proximity to "number", "word", "content" or "self".
+    Task,
+    /// No other signal: the domain space recognising the most query words.
+    MostHits,
+    /// No signal at all: every domain space.
+    AllSpaces,
+}
+
+/// One space chosen by `route`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivatedSpace {
+    pub space: String,
+    pub reason: RouteReason,
+}
+
+/// Query content words a space recognises (as an entry or via stemming).
+#[derive(Debug, Clone, Serialize)]
+pub struct SpaceHits {
+    pub space: String,
+    pub words: Vec<String>,
+}
+
+/// Query words shared by a pair of spaces.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryBridge {
+    pub spaces: (String, String),
+    pub terms: Vec<String>,
+}
+
+/// Routing decision for one query.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Route {
+    /// Activated spaces, in `space_order` (unknown TASK domains last).
+    pub activated: Vec<ActivatedSpace>,
+    /// Per-space hits, in `space_order`; spaces without hits are omitted.
+    pub hits: Vec<SpaceHits>,
+    /// Query words that are bridge terms, per space pair.
+    pub bridges: Vec<QueryBridge>,
+    /// TASK-space domain scores, best first. Empty unless the TASK space
+    /// was consulted.
+    pub task_scores: Vec<(String, f64)>,
+}
+
+/// Which stage of `resolve` produced the answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResolveStage {
+    MultiInstruction,
+    Arithmetic,
+    /// Compositional pattern; the handler name is the connector.
+    SpecialPattern,
+    /// Activated spaces answered and their results were composed.
+    Routed,
+    ExampleLookup,
+    CrossSpaceChain,
+}
+
+/// Bridge term that closed a cross-space Yes/No chain.
+#[derive(Debug, Clone, Serialize)]
+pub struct BridgeUse {
+    pub source: String,
+    pub target: String,
+    pub bridge: String,
+}
+
+/// How `resolve` answered a query.
+#[derive(Debug, Clone, Serialize)]
+pub struct Resolution {
+    pub query: String,
+    pub answer: Answer,
+    pub distance: Option<f64>,
+    pub connector: Option<String>,
+    pub stage: ResolveStage,
+    /// Operands when the arithmetic detector took over.
+    pub arithmetic: Option<ArithmeticQuery>,
+    /// Computed even when an earlier stage took over, showing where the
+    /// query would otherwise have gone.
+    pub route: Route,
+    /// Answers from the activated spaces (empty when an earlier stage took over).
+    pub space_results: Vec<SpaceResult>,
+    /// Set when `stage` is `CrossSpaceChain`.
+    pub bridge: Option<BridgeUse>,
+}
+
+impl std::fmt::Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stage = match self.stage {
+            ResolveStage::MultiInstruction => "multi-instruction",
+            ResolveStage::Arithmetic => "arithmetic",
+            ResolveStage::SpecialPattern => "special pattern",
+            ResolveStage::Routed => "routed spaces",
+            ResolveStage::ExampleLookup => "example lookup",
+            ResolveStage::CrossSpaceChain => "cross-space chain",
+        };
+        write!(f, "Handled by: {}", stage)?;
+        if let Some(conn) = &self.connector {
+            write!(f, " ({})", conn)?;
+        }
+        writeln!(f)?;
+        if let Some(a) = &self.arithmetic {
+            writeln!(f, "Arithmetic: {} {} {}", a.left_operand, a.operator, a.right_operand)?;
+        }
+        let activated: Vec<String> = self
+            .route
+            .activated
+            .iter()
+            .map(|a| format!("{} ({})", a.space, reason_label(a.reason)))
+            .collect();
+        writeln!(f, "Routed to: {}", activated.join(", "))?;
+        for hits in &self.route.hits {
+            writeln!(f, "  {} hits: {}", hits.space, hits.words.join(", "))?;
+        }
+        if !self.route.task_scores.is_empty() {
+            let scores: Vec<String> =
+                self.route.task_scores.iter().map(|(n, s)| format!("{}={:.3}", n, s)).collect();
+            writeln!(f, "Task scores: {}", scores.join(", "))?;
+        }
+        for b in &self.route.bridges {
+            writeln!(f, "Bridge terms {}↔{}: {}", b.spaces.0, b.spaces.1, b.terms.join(", "))?;
+        }
+        for r in &self.space_results {
+            writeln!(f, "  [{}] {}", r.space_name, r.answer)?;
+        }
+        if let Some(b) = &self.bridge {
+            writeln!(f, "Bridge used: {} ({}→{})", b.bridge, b.source, b.target)?;
+        }
+        write!(f, "Answer: {}", self.answer)
+    }
+}
+
+fn reason_label(reason: RouteReason) -> &'static str {
+    match reason {
+        RouteReason::Exclusive => "exclusive",
+        RouteReason::SelfReference => "self-reference",
+        RouteReason::Task => "task",
+        RouteReason::MostHits => "most hits",
+        RouteReason::AllSpaces => "all spaces",
+    }
+}
+
+impl Resolution {
+    fn answered(mut self, stage: ResolveStage, result: (Answer, Option<f64>, Option<String>)) -> Self {
+        self.stage = stage;
+        (self.answer, self.distance, self.connector) = result;
+        self
+    }
 }
 
 /// The multi-space orchestrator.
@@ -56,6 +218,9 @@ pub struct MultiSpace {
     /// Words unique to SELF space vocabulary — used as trigger words for
     /// SELF-space activation instead of the old hardcoded ["dafhne"] list.
     pub self_trigger_words: HashSet<String>,
+    /// Resolver components switched off (multi-space mechanisms; per-space
+    /// ones live in each space's strategy). See `set_ablation`.
+    pub ablation: Ablation,
 }
 
 // ─── Structural Words ────────────────────────────────────────
@@ -125,9 +290,7 @@ impl MultiSpace {
         strategy: &StrategyConfig,
         build_mode: BuildMode,
     ) -> Self {
-        let mut spaces = HashMap::new();
-        let mut space_order = Vec::new();
-
+        let mut spaces = Vec::new();
         for config in configs {
             let dictionary = load_dictionary(&config.dict_path)
                 .unwrap_or_else(|_| panic!("Failed to read dictionary: {}", config.dict_path));
@@ -139,33 +302,9 @@ impl MultiSpace {
                 dictionary.entries.len()
             );
 
-            let mut engine = Engine::with_strategy(params.clone(), strategy.clone());
-            engine.set_quiet(true);
-            engine.set_mode(build_mode);
-            engine.train(&dictionary);
-
-            let space = Space {
-                name: config.name.clone(),
-                engine,
-                dictionary,
-                params: params.clone(),
-                strategy: strategy.clone(),
-            };
-
-            space_order.push(config.name.clone());
-            spaces.insert(config.name, space);
+            spaces.push((config.name, dictionary, params.clone(), strategy.clone()));
         }
-
-        let mut ms = MultiSpace {
-            spaces,
-            bridges: HashMap::new(),
-            space_order,
-            structural_words_cache: HashSet::new(),
-            self_trigger_words: HashSet::new(),
-        };
-        ms.finish_construction();
-
-        ms
+        Self::from_dictionaries(spaces, build_mode)
     }
 
     /// Construct a MultiSpace with per-space parameters.
@@ -178,9 +317,7 @@ impl MultiSpace {
         default_strategy: &StrategyConfig,
         build_mode: BuildMode,
     ) -> Self {
-        let mut spaces = HashMap::new();
-        let mut space_order = Vec::new();
-
+        let mut spaces = Vec::new();
         for config in configs {
             let dictionary = load_dictionary(&config.dict_path)
                 .unwrap_or_else(|_| panic!("Failed to read dictionary: {}", config.dict_path));
@@ -198,21 +335,36 @@ impl MultiSpace {
                 params.dimensions
             );
 
+            spaces.push((config.name, dictionary, params, strategy));
+        }
+        Self::from_dictionaries(spaces, build_mode)
+    }
+
+    /// Construct a MultiSpace from dictionaries already in memory: one
+    /// (name, dictionary, params, strategy) per space, in order.
+    pub fn from_dictionaries(
+        dictionaries: Vec<(String, Dictionary, EngineParams, StrategyConfig)>,
+        build_mode: BuildMode,
+    ) -> Self {
+        let mut spaces = HashMap::new();
+        let mut space_order = Vec::new();
+
+        for (name, dictionary, params, strategy) in dictionaries {
             let mut engine = Engine::with_strategy(params.clone(), strategy.clone());
             engine.set_quiet(true);
             engine.set_mode(build_mode);
             engine.train(&dictionary);
 
             let space = Space {
-                name: config.name.clone(),
+                name: name.clone(),
                 engine,
                 dictionary,
                 params,
                 strategy,
             };
 
-            space_order.push(config.name.clone());
-            spaces.insert(config.name, space);
+            space_order.push(name.clone());
+            spaces.insert(name, space);
         }
 
         let mut ms = MultiSpace {
@@ -221,15 +373,32 @@ impl MultiSpace {
             space_order,
             structural_words_cache: HashSet::new(),
             self_trigger_words: HashSet::new(),
+            ablation: Ablation::default(),
         };
         ms.finish_construction();
 
         ms
     }
 
+    /// Switch resolver components off (or back on) for every space and for
+    /// the multi-space mechanisms. Training is unaffected.
+    pub fn set_ablation(&mut self, ablation: &Ablation) {
+        self.ablation = ablation.clone();
+        for space in self.spaces.values_mut() {
+            space.strategy.ablation = ablation.clone();
+        }
+    }
+
+    fn enabled(&self, component: Component) -> bool {
+        !self.ablation.disables(component)
+    }
+
     /// Shared post-construction setup: bridges, structural words, self trigger words.
     fn finish_construction(&mut self) {
         self.identify_bridges();
+        if let Some(space) = self.space_order.first().and_then(|n| self.spaces.get(n)) {
+            self.ablation = space.strategy.ablation.clone();
+        }
 
         let mut structural_cache = HashSet::new();
         for (_, space) in &self.spaces {
@@ -257,6 +426,14 @@ impl MultiSpace {
         }
     }
 
+    /// Bridge terms content words first, then alphabetically, so the bridge
+    /// reported for a chain is stable and meaningful.
+    fn bridge_order<'a>(&self, bridges: &'a HashSet<String>) -> Vec<&'a String> {
+        let mut sorted: Vec<&String> = bridges.iter().collect();
+        sorted.sort_by_key(|b| (self.is_structural_cached(b), b.as_str()));
+        sorted
+    }
+
     /// Check whether a word is structural (high doc-frequency across spaces).
     /// Uses the discovered structural word cache built from per-space classify_word_roles().
     fn is_structural_cached(&self, word: &str) -> bool {
@@ -306,44 +483,88 @@ impl MultiSpace {
 
     /// Resolve a query against the multi-space architecture.
     pub fn resolve(&self, query: &str) -> (Answer, Option<f64>, Option<String>) {
-        // Priority 1: Multi-instruction detection (period-separated)
-        if let Some(result) = self.detect_multi_instruction(query) {
-            return result;
-        }
+        let r = self.resolve_staged(query, false);
+        (r.answer, r.distance, r.connector)
+    }
+
+    /// Resolve a query and report which stage answered, the routing
+    /// decision and, for cross-space chains, the bridge term used.
+    pub fn explain(&self, query: &str) -> Resolution {
+        let mut resolution = self.resolve_staged(query, true);
+        if matches!(
+            resolution.stage,
+            ResolveStage::MultiInstruction | ResolveStage::Arithmetic | ResolveStage::SpecialPattern
+        ) {
+            // An earlier stage took over before routing; show where the query would have gone
+            resolution.route = self.route(query);
+        }
+        resolution
+    }
 
-        // Priority 2: Arithmetic detection (X plus/minus Y)
-        if let Some(arith) = self.detect_arithmetic(query) {
-            if let Some(answer) = self.resolve_arithmetic(&arith) {
-                return (answer, Some(0.0), Some("arithmetic".to_string()));
+    /// Shared by `resolve` and `explain`. The route is only computed once
+    /// the special patterns have passed, and the per-space results are only
+    /// kept when `keep_results` is set, so `resolve` does no extra work.
+    fn resolve_staged(&self, query: &str, keep_results: bool) -> Resolution {
+        let resolution = Resolution {
+            query: query.to_string(),
+            answer: Answer::IDontKnow,
+            distance: None,
+            connector: None,
+            stage: ResolveStage::Routed,
+            arithmetic: None,
+            route: Route::default(),
+            space_results: Vec::new(),
+            bridge: None,
+        };
+
+        if self.enabled(Component::SpecialPatterns) {
+            // Priority 1: Multi-instruction detection (period-separated)
+            if let Some(result) = self.detect_multi_instruction(query) {
+                return resolution.answered(ResolveStage::MultiInstruction, result);
             }
-        }
 
-        // Priority 3: Special pattern detection
-        if let Some(result) = self.detect_special_patterns(query) {
-            return result;
+            // Priority 2: Arithmetic detection (X plus/minus Y)
+            if let Some(arith) = self.detect_arithmetic(query) {
+                if let Some(answer) = self.resolve_arithmetic(&arith) {
+                    let resolution = Resolution { arithmetic: Some(arith), ..resolution };
+                    return resolution.answered(
+                        ResolveStage::Arithmetic,
+                        (answer, Some(0.0), Some("arithmetic".to_string())),
+                    );
+                }
+            }
+
+            // Priority 3: Special pattern detection
+            if let Some(result) = self.detect_special_patterns(query) {
+                return resolution.answered(ResolveStage::SpecialPattern, result);
+            }
         }
 
         // Priority 4: Route to space(s) and resolve
-        let activated = self.route_query(query);
-
-        if activated.is_empty() {
-            return (Answer::IDontKnow, None, None);
+        let resolution = Resolution { route: self.route(query), ..resolution };
+        if resolution.route.activated.is_empty() {
+            return resolution;
         }
 
         // Resolve in each activated space
         let mut results: Vec<SpaceResult> = Vec::new();
-        for space_name in &activated {
-            if let Some(result) = self.resolve_in_space(space_name, query) {
+        for activated in &resolution.route.activated {
+            if let Some(result) = self.resolve_in_space(&activated.space, query) {
                 results.push(result);
             }
         }
+        let resolution = if keep_results {
+            Resolution { space_results: results.clone(), ..resolution }
+        } else {
+            resolution
+        };
 
         // If all results are IDK, try example-based lookup
         // (for queries like "Is dog a noun?" where "dog" isn't an entry
         // but appears in the "noun" definition)
-        if results.iter().all(|r| r.answer == Answer::IDontKnow) {
+        if self.enabled(Component::ExampleLookup) && results.iter().all(|r| r.answer == Answer::IDontKnow) {
             if let Some(result) = self.try_example_based_lookup(query) {
-                return result;
+                return resolution.answered(ResolveStage::ExampleLookup, result);
             }
         }
 
@@ -357,25 +578,31 @@ impl MultiSpace {
             .all(|r| r.answer == Answer::IDontKnow);
         let has_idk = results.iter().any(|r| r.answer == Answer::IDontKnow);
 
-        if has_idk || all_non_word_idk || results.is_empty() {
-            if let Some(result) = self.try_cross_space_yes_no(query, &activated) {
-                return result;
+        if self.enabled(Component::CrossSpaceChain) && (has_idk || all_non_word_idk || results.is_empty()) {
+            if let Some((connector, bridge)) = self.try_cross_space_yes_no(query) {
+                let resolution = Resolution { bridge: Some(bridge), ..resolution };
+                return resolution.answered(
+                    ResolveStage::CrossSpaceChain,
+                    (Answer::Yes, Some(0.0), Some(connector)),
+                );
             }
         }
 
         // Compose results
-        self.compose_results(results, query)
+        let composed = self.compose_results(results, query);
+        resolution.answered(ResolveStage::Routed, composed)
     }
 
     // ─── Routing ─────────────────────────────────────────────
 
-    /// Route a query to the appropriate space(s).
-    fn route_query(&self, query: &str) -> Vec<String> {
+    /// Route a query to the appropriate space(s), without resolving it.
+    pub fn route(&self, query: &str) -> Route {
         let tokens = tokenize(query);
+        let mut route = Route::default();
 
         // Count how many content words each space recognizes
         let mut space_hits: HashMap<String, Vec<String>> = HashMap::new();
-        let mut exclusive: HashSet<String> = HashSet::new();
+        let mut exclusive: HashMap<String, RouteReason> = HashMap::new();
 
         for token in &tokens {
             if self.is_structural_cached(token) {
@@ -401,7 +628,7 @@ impl MultiSpace {
                 .filter(|s| s.as_str() != "task")
                 .collect();
             if domain_spaces.len() == 1 {
-                exclusive.insert(domain_spaces[0].clone());
+                exclusive.insert(domain_spaces[0].clone(), RouteReason::Exclusive);
             }
         }
 
@@ -426,7 +653,7 @@ impl MultiSpace {
 
         if has_self_trigger || has_self_pattern {
             if self.spaces.contains_key("self") {
-                exclusive.insert("self".to_string());
+                exclusive.entry("self".to_string()).or_insert(RouteReason::SelfReference);
             }
         }
 
@@ -492,6 +719,7 @@ impl MultiSpace {
                     ("self", self_score),
                 ];
                 scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+                route.task_scores = scores.iter().map(|(n, s)| (n.to_string(), *s)).collect();
 
                 let (best_name, best_score) = scores[0];
                 let (_second_name, second_score) = scores[1];
@@ -499,11 +727,11 @@ impl MultiSpace {
                 if best_score > 0.0 {
                     if best_score > second_score * 1.2 {
                         // Clear winner
-                        exclusive.insert(best_name.to_string());
+                        exclusive.insert(best_name.to_string(), RouteReason::Task);
                     } else {
                         // Top two are close — activate both
-                        exclusive.insert(scores[0].0.to_string());
-                        exclusive.insert(scores[1].0.to_string());
+                        exclusive.insert(scores[0].0.to_string(), RouteReason::Task);
+                        exclusive.insert(scores[1].0.to_string(), RouteReason::Task);
                     }
                 }
             }
@@ -516,7 +744,7 @@ impl MultiSpace {
                 .filter(|(name, _)| name.as_str() != "task")
                 .max_by_key(|(_, words)| words.len())
             {
-                exclusive.insert(best.clone());
+                exclusive.insert(best.clone(), RouteReason::MostHits);
             }
         }
 
@@ -524,12 +752,42 @@ impl MultiSpace {
         if exclusive.is_empty() {
             for name in &self.space_order {
                 if name != "task" {
-                    exclusive.insert(name.clone());
+                    exclusive.insert(name.clone(), RouteReason::AllSpaces);
                 }
             }
         }
 
-        exclusive.into_iter().collect()
+        // Report in space order; TASK domains without a loaded space go last
+        let position = |name: &str| self.space_order.iter().position(|n| n == name).unwrap_or(usize::MAX);
+        route.activated = exclusive
+            .into_iter()
+            .map(|(space, reason)| ActivatedSpace { space, reason })
+            .collect();
+        route.activated.sort_by(|a, b| position(&a.space).cmp(&position(&b.space)).then_with(|| a.space.cmp(&b.space)));
+        route.hits = self
+            .space_order
+            .iter()
+            .filter_map(|name| space_hits.remove(name).map(|words| SpaceHits { space: name.clone(), words }))
+            .collect();
+
+        let mut pairs: Vec<&(String, String)> = self.bridges.keys().collect();
+        pairs.sort();
+        for pair in pairs {
+            let terms: Vec<String> = tokens
+                .iter()
+                .filter(|t| !self.is_structural_cached(t))
+                .filter(|t| {
+                    let bridge = &self.bridges[pair];
+                    bridge.contains(t.as_str()) || stem_to_entry(t, bridge).is_some()
+                })
+                .cloned()
+                .collect();
+            if !terms.is_empty() {
+                route.bridges.push(QueryBridge { spaces: pair.clone(), terms });
+            }
+        }
+
+        route
     }
 
     // ─── Per-Space Resolution ────────────────────────────────
@@ -543,7 +801,7 @@ impl MultiSpace {
             &space.dictionary,
             space.engine.structural(),
             space.engine.content(),
-            &space.params,
+            &space.engine.effective_params(),
             &space.strategy,
         );
         Some(SpaceResult {
@@ -1190,12 +1448,9 @@ impl MultiSpace {
 
     // ─── Cross-Space Chain Resolution ────────────────────────
 
-    /// Attempt cross-space Yes/No resolution using bridge terms.
-    fn try_cross_space_yes_no(
-        &self,
-        query: &str,
-        _activated: &[String],
-    ) -> Option<(Answer, Option<f64>, Option<String>)> {
+    /// Attempt cross-space Yes/No resolution using bridge terms. A hit is
+    /// always Yes; returns the connector name and the bridge that closed it.
+    fn try_cross_space_yes_no(&self, query: &str) -> Option<(String, BridgeUse)> {
         let tokens = tokenize(query);
 
         // Parse as Yes/No: find subject and object
@@ -1247,13 +1502,12 @@ impl MultiSpace {
                 if src_name == tgt_name {
                     continue;
                 }
-                if let Some(true) = self.definition_bridge_chain(
+                if let Some(bridge) = self.definition_bridge_chain(
                     subject, object, src_name, tgt_name,
                 ) {
                     return Some((
-                        Answer::Yes,
-                        Some(0.0),
-                        Some(format!("cross-space:{}→{}", src_name, tgt_name)),
+                        format!("cross-space:{}→{}", src_name, tgt_name),
+                        BridgeUse { source: src_name.to_string(), target: tgt_name.to_string(), bridge },
                     ));
                 }
             }
@@ -1265,19 +1519,15 @@ impl MultiSpace {
                 if src == tgt {
                     continue;
                 }
-                if let Some(true) = self.cross_space_chain(
+                if let Some(bridge) = self.cross_space_chain(
                     subject,
                     object,
                     src,
                     tgt,
                 ) {
                     return Some((
-                        Answer::Yes,
-                        Some(0.0),
-                        Some(format!(
-                            "cross-space-chain:{}→{}",
-                            src, tgt
-                        )),
+                        format!("cross-space-chain:{}→{}", src, tgt),
+                        BridgeUse { source: src.to_string(), target: tgt.to_string(), bridge },
                     ));
                 }
             }
@@ -1295,13 +1545,15 @@ impl MultiSpace {
     ///    a. Check if that bridge word appears in object's definition (target space)
     ///    b. Check if bridge word's definition in target contains object
     ///    c. If so, the chain holds: subject→category→bridge→object
+    ///
+    /// Returns the bridge term when the chain holds.
     fn definition_bridge_chain(
         &self,
         subject: &str,
         object: &str,
         source_space: &str,
         target_space: &str,
-    ) -> Option<bool> {
+    ) -> Option<String> {
         let key = if source_space < target_space {
             (source_space.to_string(), target_space.to_string())
         } else {
@@ -1334,7 +1586,7 @@ impl MultiSpace {
         }
 
         // Check if any reachable word is a bridge term
-        for bridge in bridges {
+        for bridge in self.bridge_order(bridges) {
             if !reachable.contains(bridge.as_str()) {
                 continue;
             }
@@ -1351,7 +1603,7 @@ impl MultiSpace {
                     .collect();
 
                 if obj_set.contains(bridge.as_str()) {
-                    return Some(true);
+                    return Some(bridge.clone());
                 }
 
                 // Also check one hop from object's definition words
@@ -1364,7 +1616,7 @@ impl MultiSpace {
                             let ns = stem_to_entry(nw, &tgt.dictionary.entry_set)
                                 .unwrap_or_else(|| nw.clone());
                             if ns == *bridge {
-                                return Some(true);
+                                return Some(bridge.clone());
                             }
                         }
                     }
@@ -1377,13 +1629,14 @@ impl MultiSpace {
 
     /// Cross-space chain: subject→bridge in source, bridge→object in target.
     /// Checks both forward (bridge→object) and reverse (object→bridge) in target.
+    /// Returns the bridge term when the chain holds.
     fn cross_space_chain(
         &self,
         subject: &str,
         object: &str,
         source_space: &str,
         target_space: &str,
-    ) -> Option<bool> {
+    ) -> Option<String> {
         // Find bridge terms between the two spaces
         let key = if source_space < target_space {
             (source_space.to_string(), target_space.to_string())
@@ -1395,7 +1648,7 @@ impl MultiSpace {
         let src = self.spaces.get(source_space)?;
         let tgt = self.spaces.get(target_space)?;
 
-        for bridge in bridges {
+        for bridge in self.bridge_order(bridges) {
             if bridge == subject || bridge == object {
                 // Direct bridge: subject or object IS a bridge term
                 if bridge == subject {
@@ -1412,7 +1665,7 @@ impl MultiSpace {
                         tgt.params.max_follow_per_hop,
                     ) == Some(true)
                     {
-                        return Some(true);
+                        return Some(bridge.clone());
                     }
                     // Also check reverse: does object's definition mention subject?
                     let mut visited2 = HashSet::new();
@@ -1427,7 +1680,7 @@ impl MultiSpace {
                         tgt.params.max_follow_per_hop,
                     ) == Some(true)
                     {
-                        return Some(true);
+                        return Some(bridge.clone());
                     }
                 }
                 if bridge == object {
@@ -1444,7 +1697,7 @@ impl MultiSpace {
                         src.params.max_follow_per_hop,
                     ) == Some(true)
                     {
-                        return Some(true);
+                        return Some(bridge.clone());
                     }
                 }
                 continue;
@@ -1483,7 +1736,7 @@ impl MultiSpace {
             );
 
             if rev == Some(true) {
-                return Some(true);
+                return Some(bridge.clone());
             }
 
             // Reverse: does object's definition chain contain bridge?
@@ -1501,7 +1754,7 @@ impl MultiSpace {
             );
 
             if rev2 == Some(true) {
-                return Some(true);
+                return Some(bridge.clone());
             }
         }
 
@@ -2426,3 +2679,44 @@ fn avg_distance(results: &[SpaceResult]) -> Option<f64> {
         Some(dists.iter().sum::<f64>() / dists.len() as f64)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn math_grammar() -> MultiSpace {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../dictionaries");
+        let configs = vec![
+            SpaceConfig { name: "math".to_string(), dict_path: format!("{}/dict_math5.md", dir) },
+            SpaceConfig { name: "grammar".to_string(), dict_path: format!("{}/dict_grammar5.md", dir) },
+        ];
+        MultiSpace::new(configs, &EngineParams::default(), &StrategyConfig::default(), BuildMode::ForceField)
+    }
+
+    #[test]
+    fn explain_reports_route_and_matches_resolve() {
+        let multi = math_grammar();
+
+        let res = multi.explain("Is three a noun?");
+        let activated: Vec<(&str, RouteReason)> =
+            res.route.activated.iter().map(|a| (a.space.as_str(), a.reason)).collect();
+        assert_eq!(activated, vec![("math", RouteReason::Exclusive), ("grammar", RouteReason::Exclusive)]);
+        assert!(res.route.hits.iter().any(|h| h.space == "math" && h.words == ["three"]));
+        assert!(res.route.hits.iter().any(|h| h.space == "grammar" && h.words.contains(&"noun".to_string())));
+        assert_eq!(multi.resolve("Is three a noun?"), (res.answer.clone(), res.distance, res.connector.clone()));
+        // "three" is only in math and "noun" only in grammar: a bridge term closes the chain
+        assert_eq!(res.stage, ResolveStage::CrossSpaceChain);
+        assert_eq!(res.answer, Answer::Yes);
+        let bridge = res.bridge.as_ref().expect("cross-space chain without a bridge");
+        assert_eq!((bridge.source.as_str(), bridge.target.as_str()), ("math", "grammar"));
+        assert!(!res.space_results.is_empty());
+
+        let res = multi.explain("What is two plus three?");
+        assert_eq!(res.stage, ResolveStage::Arithmetic);
+        assert_eq!(res.arithmetic.as_ref().map(|a| a.operator.as_str()), Some("plus"));
+        assert!(res.space_results.is_empty());
+        // The route is still reported when arithmetic took over
+        assert!(res.route.activated.iter().any(|a| a.space == "math"));
+        assert_eq!(multi.resolve("What is two plus three?"), (res.answer, res.distance, res.connector));
+    }
+}
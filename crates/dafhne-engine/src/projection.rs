@@ -0,0 +1,616 @@
+//! 2-D / 3-D projections of a trained space for plotting.
+//!
+//! - `Pca`: top principal components of the centred word positions (power
+//!   iteration with deflation). Connector axes are their force directions
+//!   projected onto the same components.
+//! - `Tsne`: exact t-SNE (O(n²) per step, fine up to a few thousand words),
+//!   started from the PCA layout so runs are deterministic. There is no linear
+//!   map back to the space, so a connector axis is drawn along the correlation
+//!   of each word's projection onto the force direction with the layout.
+//!
+//! A `Projection` serializes to JSON as-is (the format the server returns);
+//! `to_svg` and `to_html` render it with no external scripts or fonts.
+
+use std::fmt;
+use std::str::FromStr;
+
+use dafhne_core::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectionMethod {
+    #[default]
+    Pca,
+    Tsne,
+}
+
+impl ProjectionMethod {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Pca => "pca",
+            Self::Tsne => "tsne",
+        }
+    }
+}
+
+impl fmt::Display for ProjectionMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ProjectionMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pca" => Ok(Self::Pca),
+            "tsne" | "t-sne" => Ok(Self::Tsne),
+            _ => Err(format!("unknown projection method '{}' (expected pca or tsne)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionOptions {
+    pub method: ProjectionMethod,
+    /// Output dimensions: 2 or 3.
+    pub dims: usize,
+    /// t-SNE: effective number of neighbours per word.
+    pub perplexity: f64,
+    /// t-SNE: gradient steps.
+    pub iterations: usize,
+}
+
+impl Default for ProjectionOptions {
+    fn default() -> Self {
+        Self {
+            method: ProjectionMethod::Pca,
+            dims: 2,
+            perplexity: 10.0,
+            iterations: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectedPoint {
+    pub word: String,
+    pub coords: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectedAxis {
+    /// Connector pattern, space-joined.
+    pub connector: String,
+    /// Axis direction in the projection, length in [0, 1]: the share of the
+    /// unit force direction PCA keeps, or the t-SNE correlation.
+    pub direction: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Projection {
+    pub method: ProjectionMethod,
+    pub dims: usize,
+    /// Dimensionality of the projected space.
+    pub source_dimensions: usize,
+    /// PCA only: share of total variance per component.
+    pub variance_explained: Vec<f64>,
+    /// Centred on the origin, sorted by word.
+    pub points: Vec<ProjectedPoint>,
+    pub axes: Vec<ProjectedAxis>,
+}
+
+/// Project every word of `space` to `options.dims` (clamped to 2..=3) dimensions.
+pub fn project(space: &GeometricSpace, options: &ProjectionOptions) -> Projection {
+    let dims = options.dims.clamp(2, 3);
+    let mut words: Vec<&String> = space.words.keys().collect();
+    words.sort();
+
+    let d = space.dimensions;
+    let n = words.len().max(1) as f64;
+    let mut mean = vec![0.0; d];
+    for w in &words {
+        for (m, x) in mean.iter_mut().zip(&space.words[*w].position) {
+            *m += x / n;
+        }
+    }
+    let centred: Vec<Vec<f64>> = words
+        .iter()
+        .map(|w| space.words[*w].position.iter().zip(&mean).map(|(x, m)| x - m).collect())
+        .collect();
+
+    let (components, variance_explained) = principal_components(&centred, d, dims);
+    let pca: Vec<Vec<f64>> = centred
+        .iter()
+        .map(|x| components.iter().map(|c| dot(x, c)).collect())
+        .collect();
+
+    let unit_directions: Vec<(String, Vec<f64>)> = space
+        .connectors
+        .iter()
+        .map(|c| {
+            let norm = dot(&c.force_direction, &c.force_direction).sqrt();
+            let unit = if norm > 1e-10 {
+                c.force_direction.iter().map(|x| x / norm).collect()
+            } else {
+                vec![0.0; d]
+            };
+            (c.pattern.join(" "), unit)
+        })
+        .collect();
+
+    let (coords, axes, variance_explained) = match options.method {
+        ProjectionMethod::Pca => {
+            let axes = unit_directions
+                .into_iter()
+                .map(|(connector, unit)| ProjectedAxis {
+                    connector,
+                    direction: components.iter().map(|c| dot(&unit, c)).collect(),
+                })
+                .collect();
+            (pca, axes, variance_explained)
+        }
+        ProjectionMethod::Tsne => {
+            let layout = tsne(&centred, pca, options.perplexity, options.iterations);
+            let axes = unit_directions
+                .into_iter()
+                .map(|(connector, unit)| {
+                    let along: Vec<f64> = centred.iter().map(|x| dot(x, &unit)).collect();
+                    ProjectedAxis {
+                        connector,
+                        direction: (0..dims)
+                            .map(|k| correlation(&along, &layout.iter().map(|y| y[k]).collect::<Vec<_>>()))
+                            .collect(),
+                    }
+                })
+                .collect();
+            (layout, axes, Vec::new())
+        }
+    };
+
+    Projection {
+        method: options.method,
+        dims,
+        source_dimensions: d,
+        variance_explained,
+        points: words
+            .into_iter()
+            .zip(coords)
+            .map(|(word, coords)| ProjectedPoint { word: word.clone(), coords })
+            .collect(),
+        axes,
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len() as f64;
+    if a.len() < 2 {
+        return 0.0;
+    }
+    let (ma, mb) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+    let cov: f64 = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum();
+    let va: f64 = a.iter().map(|x| (x - ma) * (x - ma)).sum();
+    let vb: f64 = b.iter().map(|y| (y - mb) * (y - mb)).sum();
+    if va < 1e-20 || vb < 1e-20 {
+        0.0
+    } else {
+        cov / (va * vb).sqrt()
+    }
+}
+
+/// Top `k` unit principal axes of already-centred rows, and the share of
+/// total variance each explains. Missing axes (rank < k) are zero vectors.
+fn principal_components(rows: &[Vec<f64>], d: usize, k: usize) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let total: f64 = rows.iter().map(|x| dot(x, x)).sum();
+    let mut rng = SimpleRng::new(42);
+    let mut components: Vec<Vec<f64>> = Vec::new();
+    let mut explained = Vec::new();
+
+    for _ in 0..k {
+        let mut v = random_unit_vector(d, &mut rng);
+        let mut eigen = 0.0;
+        for _ in 0..500 {
+            // v ← Xᵀ X v, kept orthogonal to the axes already found
+            let xv: Vec<f64> = rows.iter().map(|x| dot(x, &v)).collect();
+            let mut next = vec![0.0; d];
+            for (x, s) in rows.iter().zip(&xv) {
+                for (n, xi) in next.iter_mut().zip(x) {
+                    *n += xi * s;
+                }
+            }
+            for c in &components {
+                let p = dot(&next, c);
+                for (n, ci) in next.iter_mut().zip(c) {
+                    *n -= p * ci;
+                }
+            }
+            let norm = dot(&next, &next).sqrt();
+            if norm < 1e-12 {
+                v = vec![0.0; d];
+                eigen = 0.0;
+                break;
+            }
+            next.iter_mut().for_each(|x| *x /= norm);
+            let delta: f64 = next.iter().zip(&v).map(|(a, b)| (a - b).abs()).sum();
+            v = next;
+            eigen = norm;
+            if delta < 1e-10 {
+                break;
+            }
+        }
+        // Sign convention: largest coefficient positive, so layouts don't flip
+        let largest = v.iter().copied().fold(0.0f64, |m, x| if x.abs() > m.abs() { x } else { m });
+        if largest < 0.0 {
+            v.iter_mut().for_each(|x| *x = -*x);
+        }
+        explained.push(if total > 1e-20 { eigen / total } else { 0.0 });
+        components.push(v);
+    }
+    (components, explained)
+}
+
+/// Exact t-SNE with early exaggeration, momentum and per-coordinate gains.
+fn tsne(rows: &[Vec<f64>], init: Vec<Vec<f64>>, perplexity: f64, iterations: usize) -> Vec<Vec<f64>> {
+    let n = rows.len();
+    if n < 3 {
+        return init;
+    }
+    let dims = init[0].len();
+    let perplexity = perplexity.min((n - 1) as f64 / 3.0).max(1.0);
+
+    let d2: Vec<Vec<f64>> = rows
+        .iter()
+        .map(|a| rows.iter().map(|b| a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()).collect())
+        .collect();
+
+    // Conditional affinities: binary search each point's precision for the target entropy
+    let target = perplexity.ln();
+    let mut p = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        let (mut beta, mut lo, mut hi) = (1.0, 0.0, f64::INFINITY);
+        let scale = d2[i].iter().copied().filter(|&x| x > 0.0).fold(f64::INFINITY, f64::min);
+        let scale = if scale.is_finite() { scale } else { 1.0 };
+        for _ in 0..64 {
+            let mut sum = 0.0;
+            let mut weighted = 0.0;
+            for j in 0..n {
+                if j != i {
+                    let e = (-beta * (d2[i][j] - scale).max(0.0)).exp();
+                    p[i][j] = e;
+                    sum += e;
+                    weighted += e * (d2[i][j] - scale).max(0.0);
+                }
+            }
+            let entropy = sum.ln() + beta * weighted / sum;
+            p[i].iter_mut().for_each(|x| *x /= sum);
+            if (entropy - target).abs() < 1e-5 {
+                break;
+            }
+            if entropy > target {
+                lo = beta;
+                beta = if hi.is_finite() { (beta + hi) / 2.0 } else { beta * 2.0 };
+            } else {
+                hi = beta;
+                beta = (beta + lo) / 2.0;
+            }
+        }
+    }
+    let joint: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| ((p[i][j] + p[j][i]) / (2.0 * n as f64)).max(1e-12)).collect())
+        .collect();
+
+    // Start from PCA, shrunk to a tiny spread
+    let spread = (init.iter().map(|y| y[0] * y[0]).sum::<f64>() / n as f64).sqrt();
+    let shrink = if spread > 1e-12 { 1e-4 / spread } else { 1.0 };
+    let mut y: Vec<Vec<f64>> = init.into_iter().map(|r| r.into_iter().map(|x| x * shrink).collect()).collect();
+    let mut update = vec![vec![0.0; dims]; n];
+    let mut gains = vec![vec![1.0f64; dims]; n];
+    let learning_rate = (n as f64 / 12.0).max(50.0);
+    let exaggeration_steps = (iterations / 4).min(250);
+
+    for step in 0..iterations {
+        let exaggeration = if step < exaggeration_steps { 12.0 } else { 1.0 };
+        let momentum = if step < exaggeration_steps { 0.5 } else { 0.8 };
+
+        let mut num = vec![vec![0.0; n]; n];
+        let mut z = 0.0;
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let dist: f64 = y[i].iter().zip(&y[j]).map(|(a, b)| (a - b) * (a - b)).sum();
+                let q = 1.0 / (1.0 + dist);
+                num[i][j] = q;
+                num[j][i] = q;
+                z += 2.0 * q;
+            }
+        }
+
+        for i in 0..n {
+            let mut grad = vec![0.0; dims];
+            for j in 0..n {
+                if i != j {
+                    let f = 4.0 * (exaggeration * joint[i][j] - num[i][j] / z) * num[i][j];
+                    for (g, (a, b)) in grad.iter_mut().zip(y[i].iter().zip(&y[j])) {
+                        *g += f * (a - b);
+                    }
+                }
+            }
+            for k in 0..dims {
+                gains[i][k] = if (grad[k] > 0.0) != (update[i][k] > 0.0) {
+                    gains[i][k] + 0.2
+                } else {
+                    (gains[i][k] * 0.8).max(0.01)
+                };
+                update[i][k] = momentum * update[i][k] - learning_rate * gains[i][k] * grad[k];
+            }
+        }
+        for (row, u) in y.iter_mut().zip(&update) {
+            row.iter_mut().zip(u).for_each(|(a, b)| *a += b);
+        }
+        for k in 0..dims {
+            let m = y.iter().map(|r| r[k]).sum::<f64>() / n as f64;
+            y.iter_mut().for_each(|r| r[k] -= m);
+        }
+    }
+    y
+}
+
+// ─── Rendering ──────────────────────────────────────────────────
+
+const SIZE: f64 = 800.0;
+const MARGIN: f64 = 60.0;
+/// Fixed 3-D view (radians); the HTML page lets you drag it around.
+const VIEW_YAW: f64 = 0.6;
+const VIEW_PITCH: f64 = 0.35;
+const AXIS_COLORS: [&str; 6] = ["#d62728", "#1f77b4", "#2ca02c", "#9467bd", "#ff7f0e", "#8c564b"];
+
+/// Rotate a 2-D/3-D point into screen space: (x, y, depth).
+fn view(c: &[f64], yaw: f64, pitch: f64) -> (f64, f64, f64) {
+    let (x, y, z) = (c[0], c.get(1).copied().unwrap_or(0.0), c.get(2).copied().unwrap_or(0.0));
+    let x1 = x * yaw.cos() + z * yaw.sin();
+    let z1 = -x * yaw.sin() + z * yaw.cos();
+    (x1, y * pitch.cos() - z1 * pitch.sin(), y * pitch.sin() + z1 * pitch.cos())
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn title(projection: &Projection) -> String {
+    let mut title = format!(
+        "{} {}-D of {}-D space, {} words",
+        projection.method.name().to_uppercase(),
+        projection.dims,
+        projection.source_dimensions,
+        projection.points.len()
+    );
+    if !projection.variance_explained.is_empty() {
+        let shares: Vec<String> =
+            projection.variance_explained.iter().map(|v| format!("{:.0}%", v * 100.0)).collect();
+        title.push_str(&format!(", variance explained {}", shares.join(" + ")));
+    }
+    title
+}
+
+/// Standalone SVG: words as labelled points, connector axes as arrows from
+/// the centre. `highlight` words are drawn larger, in red.
+pub fn to_svg(projection: &Projection, highlight: &[String]) -> String {
+    let (yaw, pitch) = if projection.dims == 3 { (VIEW_YAW, VIEW_PITCH) } else { (0.0, 0.0) };
+    let radius = projection
+        .points
+        .iter()
+        .map(|p| dot(&p.coords, &p.coords).sqrt())
+        .fold(1e-12, f64::max);
+    let scale = (SIZE / 2.0 - MARGIN) / radius;
+    let screen = |c: &[f64]| {
+        let (x, y, depth) = view(c, yaw, pitch);
+        (SIZE / 2.0 + scale * x, SIZE / 2.0 - scale * y, depth)
+    };
+    let coords = |c: &[f64]| c.iter().map(|x| format!("{:.6}", x)).collect::<Vec<_>>().join(",");
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{s}\" height=\"{s}\" viewBox=\"0 0 {s} {s}\" \
+         data-dims=\"{}\" data-scale=\"{:.6}\" font-family=\"sans-serif\">\n",
+        projection.dims,
+        scale,
+        s = SIZE
+    );
+    svg.push_str(
+        "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"9\" refY=\"5\" markerWidth=\"6\" \
+         markerHeight=\"6\" orient=\"auto-start-reverse\"><path d=\"M0,0 L10,5 L0,10 z\" fill=\"context-stroke\"/></marker></defs>\n",
+    );
+    svg.push_str(&format!("<rect width=\"{s}\" height=\"{s}\" fill=\"white\"/>\n", s = SIZE));
+    svg.push_str(&format!(
+        "<text x=\"12\" y=\"22\" font-size=\"14\" fill=\"#333\">{}</text>\n",
+        xml_escape(&title(projection))
+    ));
+
+    for (i, axis) in projection.axes.iter().enumerate() {
+        let color = AXIS_COLORS[i % AXIS_COLORS.len()];
+        let tip: Vec<f64> = axis.direction.iter().map(|x| x * radius).collect();
+        let (x, y, _) = screen(&tip);
+        svg.push_str(&format!(
+            "<g class=\"axis\" data-c=\"{}\"><line x1=\"{c}\" y1=\"{c}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" \
+             stroke-width=\"2\" marker-end=\"url(#arrow)\"/><text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\" \
+             fill=\"{}\">{}</text></g>\n",
+            coords(&tip),
+            x,
+            y,
+            color,
+            x + 4.0,
+            y - 4.0,
+            color,
+            xml_escape(&axis.connector),
+            c = SIZE / 2.0
+        ));
+    }
+
+    // Far points first so near ones are drawn on top
+    let mut order: Vec<(usize, (f64, f64, f64))> =
+        projection.points.iter().enumerate().map(|(i, p)| (i, screen(&p.coords))).collect();
+    order.sort_by(|a, b| a.1 .2.partial_cmp(&b.1 .2).unwrap_or(std::cmp::Ordering::Equal));
+    for (i, (x, y, _)) in order {
+        let point = &projection.points[i];
+        let hit = highlight.iter().any(|h| h.eq_ignore_ascii_case(&point.word));
+        let (r, fill, weight) = if hit { (5.5, "#d62728", "bold") } else { (3.0, "#444", "normal") };
+        svg.push_str(&format!(
+            "<g class=\"pt{}\" data-word=\"{w}\" data-c=\"{}\" transform=\"translate({:.1},{:.1})\">\
+             <title>{w}</title><circle r=\"{}\" fill=\"{}\"/><text x=\"6\" y=\"4\" font-size=\"11\" \
+             font-weight=\"{}\" fill=\"#222\">{w}</text></g>\n",
+            if hit { " hit" } else { "" },
+            coords(&point.coords),
+            x,
+            y,
+            r,
+            fill,
+            weight,
+            w = xml_escape(&point.word)
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Self-contained HTML page around `to_svg`: a box to highlight words and,
+/// for 3-D projections, drag-to-rotate.
+pub fn to_html(projection: &Projection, highlight: &[String]) -> String {
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", xml_escape(&title(projection))));
+    html.push_str(
+        "<style>\nbody{font-family:sans-serif;margin:16px}\n\
+         #q{width:420px;padding:4px}\n\
+         svg{border:1px solid #ccc;cursor:grab}\n\
+         g.pt.hit circle{fill:#d62728}\n\
+         g.pt.hit text{font-weight:bold}\n\
+         </style>\n</head>\n<body>\n",
+    );
+    html.push_str(&format!(
+        "<p><input id=\"q\" placeholder=\"Highlight words (comma-separated)\" value=\"{}\"> {}</p>\n",
+        xml_escape(&highlight.join(", ")),
+        if projection.dims == 3 { "Drag to rotate." } else { "" }
+    ));
+    html.push_str(&to_svg(projection, highlight));
+    html.push_str(&format!(
+        "<script>\n\
+const svg = document.querySelector('svg');\n\
+const dims = {dims}, scale = +svg.dataset.scale, half = {half};\n\
+let yaw = {yaw}, pitch = {pitch};\n\
+function view(c) {{\n\
+  const [x, y, z] = [c[0], c[1], c[2] || 0];\n\
+  const x1 = x * Math.cos(yaw) + z * Math.sin(yaw), z1 = -x * Math.sin(yaw) + z * Math.cos(yaw);\n\
+  return [half + scale * x1, half - scale * (y * Math.cos(pitch) - z1 * Math.sin(pitch)),\n\
+          y * Math.sin(pitch) + z1 * Math.cos(pitch)];\n\
+}}\n\
+const coords = el => el.dataset.c.split(',').map(Number);\n\
+function redraw() {{\n\
+  svg.querySelectorAll('g.axis').forEach(g => {{\n\
+    const [x, y] = view(coords(g));\n\
+    const line = g.querySelector('line'), text = g.querySelector('text');\n\
+    line.setAttribute('x2', x); line.setAttribute('y2', y);\n\
+    text.setAttribute('x', x + 4); text.setAttribute('y', y - 4);\n\
+  }});\n\
+  const pts = [...svg.querySelectorAll('g.pt')].map(g => [g, view(coords(g))]);\n\
+  pts.sort((a, b) => a[1][2] - b[1][2]);\n\
+  pts.forEach(([g, [x, y]]) => {{ g.setAttribute('transform', `translate(${{x}},${{y}})`); svg.appendChild(g); }});\n\
+}}\n\
+document.getElementById('q').addEventListener('input', e => {{\n\
+  const words = e.target.value.toLowerCase().split(',').map(w => w.trim()).filter(w => w);\n\
+  svg.querySelectorAll('g.pt').forEach(g => g.classList.toggle('hit', words.includes(g.dataset.word.toLowerCase())));\n\
+}});\n\
+if (dims === 3) {{\n\
+  let drag = null;\n\
+  svg.addEventListener('mousedown', e => drag = [e.clientX, e.clientY]);\n\
+  window.addEventListener('mouseup', () => drag = null);\n\
+  window.addEventListener('mousemove', e => {{\n\
+    if (!drag) return;\n\
+    yaw += (e.clientX - drag[0]) * 0.01; pitch += (e.clientY - drag[1]) * 0.01;\n\
+    drag = [e.clientX, e.clientY];\n\
+    redraw();\n\
+  }});\n\
+}}\n\
+</script>\n</body>\n</html>\n",
+        dims = projection.dims,
+        half = SIZE / 2.0,
+        yaw = if projection.dims == 3 { VIEW_YAW } else { 0.0 },
+        pitch = if projection.dims == 3 { VIEW_PITCH } else { 0.0 },
+    ));
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn space(points: &[(&str, [f64; 3])]) -> GeometricSpace {
+        let words: HashMap<String, WordPoint> = points
+            .iter()
+            .map(|(w, p)| (w.to_string(), WordPoint { word: w.to_string(), position: p.to_vec() }))
+            .collect();
+        GeometricSpace {
+            dimensions: 3,
+            words,
+            connectors: vec![Connector {
+                pattern: vec!["is".to_string()],
+                force_direction: vec![0.0, 0.0, 2.0],
+                magnitude: 1.0,
+                frequency: 1,
+                uniformity: 1.0,
+            }],
+            distance_stats: None,
+        }
+    }
+
+    #[test]
+    fn pca_finds_the_spread_axis() {
+        let s = space(&[("a", [0.0, 0.0, -2.0]), ("b", [0.0, 0.1, -1.0]), ("c", [0.0, 0.0, 1.0]), ("d", [0.0, -0.1, 2.0])]);
+        let p = project(&s, &ProjectionOptions::default());
+        assert_eq!(p.points.iter().map(|p| p.word.as_str()).collect::<Vec<_>>(), vec!["a", "b", "c", "d"]);
+        assert!(p.variance_explained[0] > 0.99);
+        // The "is" axis lies along the first component
+        assert!((p.axes[0].direction[0] - 1.0).abs() < 1e-2);
+        assert!(p.points[0].coords[0] < p.points[3].coords[0]);
+    }
+
+    #[test]
+    fn tsne_keeps_clusters_apart() {
+        let s = space(&[
+            ("dog", [0.0, 0.0, 0.0]),
+            ("cat", [0.1, 0.0, 0.0]),
+            ("animal", [0.0, 0.1, 0.0]),
+            ("sun", [5.0, 5.0, 5.0]),
+            ("hot", [5.1, 5.0, 5.0]),
+            ("light", [5.0, 5.1, 5.0]),
+        ]);
+        let options = ProjectionOptions { method: ProjectionMethod::Tsne, iterations: 300, ..Default::default() };
+        let p = project(&s, &options);
+        let at = |w: &str| p.points.iter().find(|p| p.word == w).unwrap().coords.clone();
+        let dist = |a: &str, b: &str| {
+            let (a, b) = (at(a), at(b));
+            dot(&a, &a) + dot(&b, &b) - 2.0 * dot(&a, &b)
+        };
+        assert!(dist("dog", "cat") < dist("dog", "sun"));
+        assert!(dist("hot", "light") < dist("cat", "hot"));
+    }
+
+    #[test]
+    fn renders_points_and_axes() {
+        let s = space(&[("dog", [0.0, 0.0, 1.0]), ("cat", [1.0, 0.0, 0.0]), ("a<b", [0.0, 1.0, 0.0])]);
+        let p = project(&s, &ProjectionOptions { dims: 3, ..Default::default() });
+        let svg = to_svg(&p, &["dog".to_string()]);
+        assert_eq!(svg.matches("<g class=\"pt").count(), 3);
+        assert_eq!(svg.matches("class=\"pt hit\"").count(), 1);
+        assert!(svg.contains("a&lt;b"));
+        assert!(svg.contains("class=\"axis\""));
+        let html = to_html(&p, &[]);
+        assert!(html.contains("Drag to rotate") && html.trim_end().ends_with("</html>"));
+    }
+}
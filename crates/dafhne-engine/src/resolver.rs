@@ -2,6 +2,7 @@ use std::collections::HashSet;
 use dafhne_core::*;
 use dafhne_parser::{stem_to_entry, tokenize};
 
+use crate::ablation::Component;
 use crate::strategy::{NegationModel, StrategyConfig};
 
 // ─── Boolean Operators ────────────────────────────────────────
@@ -234,6 +235,51 @@ fn definition_category(
     None
 }
 
+// ─── Text-Only Helpers (no trained space) ────────────────────
+
+/// An untrained space: no words, no connectors. The definition-only helpers
+/// below only consult the space for connector patterns, so they behave as
+/// plain text analysis when given this.
+fn empty_space() -> GeometricSpace {
+    GeometricSpace {
+        dimensions: 0,
+        words: Default::default(),
+        connectors: Vec::new(),
+        distance_stats: None,
+    }
+}
+
+/// Category noun from a word's first definition sentence, without a trained space.
+/// "dog — a domestic mammal..." → "mammal". Same extraction as "What is X?".
+pub fn first_sentence_category(
+    subject: &str,
+    dictionary: &Dictionary,
+    structural: &HashSet<String>,
+) -> Option<String> {
+    definition_category(subject, dictionary, &empty_space(), structural)
+}
+
+/// Whether either word's definition chain reaches the other, without a trained space.
+/// Mentions under "not" count as links too: this answers "are they related at all",
+/// which is what sibling-negative generation needs.
+pub fn definitions_linked(
+    a: &str,
+    b: &str,
+    dictionary: &Dictionary,
+    structural: &HashSet<String>,
+    params: &EngineParams,
+) -> bool {
+    let space = empty_space();
+    let reaches = |from: &str, to: &str| {
+        definition_chain_check(
+            from, to, dictionary, structural, params.max_chain_hops,
+            &mut HashSet::new(), &space, params.max_follow_per_hop,
+        )
+        .is_some()
+    };
+    reaches(a, b) || reaches(b, a)
+}
+
 /// Check if a word is a property/adjective/verb word rather than a category noun.
 ///
 /// This is a DEFINITION-SHAPE heuristic, not a hardcoded word list (A09).
@@ -248,7 +294,7 @@ fn definition_category(
 /// The heuristic patterns ("to", "-ing" suffix, "not X") are ELI5 definition
 /// conventions, not English grammar rules. They would need adaptation for
 /// non-ELI5 definition styles but work across any language using ELI5 format.
-fn is_property_word(word: &str, dictionary: &Dictionary) -> bool {
+pub fn is_property_word(word: &str, dictionary: &Dictionary) -> bool {
     dictionary.entries.iter()
         .find(|e| e.word == word)
         .map_or(false, |e| {
@@ -620,6 +666,50 @@ pub fn resolve_question(
     }
 }
 
+/// Report what the definition chain alone says about a question, ignoring geometry.
+///
+/// Returns:
+/// - `Some(true)`  — chain links subject and object (Yes/No in either direction, or a Why path)
+/// - `Some(false)` — chain explicitly negates the object ("not {object}")
+/// - `None`        — the chain cannot decide; any answer came from geometry or fallbacks
+///
+/// Used by evaluation to check "answer must come from chain traversal" evidence.
+pub fn chain_evidence(
+    question: &str,
+    space: &GeometricSpace,
+    dictionary: &Dictionary,
+    structural: &HashSet<String>,
+    content: &HashSet<String>,
+    params: &EngineParams,
+) -> Option<bool> {
+    let tokens = tokenize(question);
+    match detect_question_type(&tokens, dictionary, content, structural)? {
+        QuestionType::YesNo { subject, object, negated: false, .. } => {
+            let mut visited = HashSet::new();
+            definition_chain_check(
+                &subject, &object, dictionary, structural, params.max_chain_hops,
+                &mut visited, space, params.max_follow_per_hop,
+            )
+            .or_else(|| {
+                let mut visited_rev = HashSet::new();
+                definition_chain_check(
+                    &object, &subject, dictionary, structural, params.max_chain_hops,
+                    &mut visited_rev, space, params.max_follow_per_hop,
+                )
+            })
+        }
+        QuestionType::WhyIs { subject, object, .. } => {
+            let mut path = vec![subject.clone()];
+            trace_chain_path(
+                &subject, &object, dictionary, structural, params.max_chain_hops,
+                &mut HashSet::new(), &mut path, space, params.max_follow_per_hop,
+            )
+            .then_some(true)
+        }
+        _ => None,
+    }
+}
+
 // ─── Connector Matching ────────────────────────────────────────
 
 /// Find a connector in the space that best matches the given pattern.
@@ -1257,7 +1347,7 @@ fn resolve_yes_no(
     // - None + both in dict → No (category objects) or IDK (property objects)
     // - None + not both in dict → trust geometry
     // For negated questions, the geometric pipeline handles via threshold inversion.
-    if !negated {
+    if !negated && strategy.enabled(Component::ChainGate) {
         let max_hops = params.max_chain_hops;
 
         // Forward check: subject → object
@@ -1343,7 +1433,7 @@ fn compute_geometric_yes_no(
     // Connector-axis path takes priority: project onto the connector's trained
     // direction axis to measure relationship-specific distance. This overrides
     // negation-model-specific paths when enabled.
-    if strategy.use_connector_axis {
+    if strategy.use_connector_axis && strategy.enabled(Component::ConnectorAxis) {
         if let Some(conn) = find_matching_connector(space, connector_pattern) {
             let proj_dist = projected_distance(subject_pos, object_pos, &conn.force_direction);
             let (axis_mean, _) = compute_axis_distance_stats(space, &conn.force_direction);
@@ -1495,7 +1585,7 @@ fn resolve_what_is(
     // a geometrically close but semantically wrong word (e.g., "bad" for "person"
     // because they co-occur in examples). Definition extraction directly reads
     // "person — an animal that can..." → "animal".
-    if extra_content_words == 0 {
+    if extra_content_words == 0 && strategy.enabled(Component::DefinitionLookup) {
         // Definitions come in two shapes:
         //   Category: "dog — an animal that can..." → starts with article + noun → answer = "an animal"
         //   Descriptive: "sentence — words in order that tell a thing" → starts with content word → answer = full text
@@ -1541,15 +1631,19 @@ fn resolve_what_is(
     };
 
     // Try axis-weighted nearest neighbor using the "is"/"is a" connector direction
-    let is_connector = find_matching_connector(space, connector_pattern)
-        .or_else(|| space.connectors.iter().find(|c| {
-            c.pattern == vec!["is".to_string()] || c.pattern == vec!["is".to_string(), "a".to_string()]
-        }));
+    let is_connector = if strategy.enabled(Component::WeightedDistance) {
+        find_matching_connector(space, connector_pattern)
+            .or_else(|| space.connectors.iter().find(|c| {
+                c.pattern == vec!["is".to_string()] || c.pattern == vec!["is".to_string(), "a".to_string()]
+            }))
+    } else {
+        None
+    };
 
     let alpha = params.weighted_distance_alpha;
 
     // Connector-axis mode takes priority (existing)
-    let connector_axis = if strategy.use_connector_axis {
+    let connector_axis = if strategy.use_connector_axis && strategy.enabled(Component::ConnectorAxis) {
         find_matching_connector(space, connector_pattern)
     } else {
         None
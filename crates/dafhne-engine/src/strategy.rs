@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use dafhne_core::SimpleRng;
 
+use crate::ablation::{Ablation, Component};
+
 // ─── Force Function ────────────────────────────────────────────
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -186,6 +188,9 @@ pub struct StrategyConfig {
     pub negation_model: NegationModel,
     #[serde(default)]
     pub use_connector_axis: bool,
+    /// Resolver components switched off for ablation runs (query time only).
+    #[serde(default, skip_serializing_if = "Ablation::is_empty")]
+    pub ablation: Ablation,
 }
 
 impl Default for StrategyConfig {
@@ -197,6 +202,14 @@ impl Default for StrategyConfig {
             multi_connector: MultiConnectorHandling::Sequential,
             negation_model: NegationModel::Inversion,
             use_connector_axis: false,
+            ablation: Ablation::default(),
         }
     }
 }
+
+impl StrategyConfig {
+    /// Whether a resolver component is active (not ablated).
+    pub fn enabled(&self, component: Component) -> bool {
+        !self.ablation.disables(component)
+    }
+}
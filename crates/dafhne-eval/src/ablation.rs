@@ -0,0 +1,218 @@
+//! Ablation runs: re-evaluate a suite with resolver components switched off.
+//!
+//! Rows:
+//! - baseline (the configured resolver)
+//! - one row per component, with only that component off
+//! - one row with every symbolic component off (the geometric floor)
+//!
+//! A baseline-correct answer that survives the geometric floor came from
+//! geometry (or, for I-don't-know questions, from geometric absence); every
+//! other correct answer depends on at least one symbolic mechanism. The split
+//! tracks the geometry/symbol balance across releases.
+
+use dafhne_core::*;
+use dafhne_engine::ablation::{Ablation, Component};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AblationRow {
+    pub label: String,
+    pub disabled: Vec<Component>,
+    pub accuracy: f64,
+    pub honesty: f64,
+    pub fitness: f64,
+    pub total_correct: usize,
+    /// Question ids correct in the baseline but not here.
+    pub lost: Vec<String>,
+    /// Question ids wrong in the baseline but correct here.
+    pub gained: Vec<String>,
+}
+
+/// Where the baseline's correct answers come from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnswerBalance {
+    pub geometric: usize,
+    pub symbolic: usize,
+    pub absence: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AblationReport {
+    pub total_questions: usize,
+    pub rows: Vec<AblationRow>,
+    pub balance: AnswerBalance,
+}
+
+/// Evaluate `base` and every single-component and all-symbolic ablation on
+/// top of it. `evaluate` runs the suite under one configuration.
+pub fn run_ablation(
+    base: &Ablation,
+    components: &[Component],
+    mut evaluate: impl FnMut(&Ablation) -> FitnessReport,
+) -> AblationReport {
+    let with = |extra: &[Component]| {
+        let mut all = base.disabled.clone();
+        all.extend_from_slice(extra);
+        Ablation::without_all(&all)
+    };
+    let active: Vec<Component> = components.iter().copied().filter(|c| !base.disables(*c)).collect();
+    let symbolic: Vec<Component> = active.iter().copied().filter(|c| c.is_symbolic()).collect();
+
+    let baseline = evaluate(base);
+    let mut rows = vec![row(base.label(), base, &baseline, &baseline)];
+    let mut floor = None;
+    for &component in &active {
+        let ablation = with(&[component]);
+        let report = evaluate(&ablation);
+        rows.push(row(ablation.label(), &ablation, &report, &baseline));
+        if symbolic == [component] {
+            floor = Some(report);
+        }
+    }
+    if symbolic.len() > 1 {
+        let ablation = with(&symbolic);
+        let report = evaluate(&ablation);
+        rows.push(row("geometry only (all symbolic off)".to_string(), &ablation, &report, &baseline));
+        floor = Some(report);
+    }
+    let floor = floor.unwrap_or_else(|| baseline.clone());
+
+    let mut balance = AnswerBalance::default();
+    for (b, f) in baseline.results.iter().zip(&floor.results) {
+        if !b.correct {
+            continue;
+        }
+        if !f.correct {
+            balance.symbolic += 1;
+        } else if b.expected == ExpectedAnswer::IDontKnow {
+            balance.absence += 1;
+        } else {
+            balance.geometric += 1;
+        }
+    }
+
+    AblationReport {
+        total_questions: baseline.total_questions,
+        rows,
+        balance,
+    }
+}
+
+fn row(label: String, ablation: &Ablation, report: &FitnessReport, baseline: &FitnessReport) -> AblationRow {
+    let mut lost = Vec::new();
+    let mut gained = Vec::new();
+    for (b, r) in baseline.results.iter().zip(&report.results) {
+        match (b.correct, r.correct) {
+            (true, false) => lost.push(r.question_id.clone()),
+            (false, true) => gained.push(r.question_id.clone()),
+            _ => {}
+        }
+    }
+    AblationRow {
+        label,
+        disabled: ablation.disabled.clone(),
+        accuracy: report.accuracy,
+        honesty: report.honesty,
+        fitness: report.fitness,
+        total_correct: report.total_correct,
+        lost,
+        gained,
+    }
+}
+
+/// Print the contribution table and the answer balance.
+pub fn print_ablation_report(report: &AblationReport) {
+    println!(
+        "  {:<44} {:>8} {:>8} {:>8} {:>9} {:>6} {:>6}",
+        "configuration", "accuracy", "honesty", "fitness", "\u{0394}fitness", "lost", "gained"
+    );
+    let base = report.rows.first().map_or(0.0, |r| r.fitness);
+    for r in &report.rows {
+        println!(
+            "  {:<44} {:>8.2} {:>8.2} {:>8.4} {:>+9.4} {:>6} {:>6}",
+            r.label,
+            r.accuracy,
+            r.honesty,
+            r.fitness,
+            r.fitness - base,
+            r.lost.len(),
+            r.gained.len(),
+        );
+    }
+    for r in report.rows.iter().skip(1).filter(|r| !r.lost.is_empty()) {
+        println!("  {} lost: {}", r.label, r.lost.join(", "));
+    }
+
+    let b = &report.balance;
+    let correct = b.geometric + b.symbolic + b.absence;
+    let pct = |n: usize| if correct == 0 { 0.0 } else { 100.0 * n as f64 / correct as f64 };
+    println!(
+        "\n  Answer balance ({} correct of {}): geometric {} ({:.0}%), symbolic {} ({:.0}%), absence {} ({:.0}%)",
+        correct,
+        report.total_questions,
+        b.geometric,
+        pct(b.geometric),
+        b.symbolic,
+        pct(b.symbolic),
+        b.absence,
+        pct(b.absence),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(correct: &[bool]) -> FitnessReport {
+        let results: Vec<QuestionResult> = correct
+            .iter()
+            .enumerate()
+            .map(|(i, &ok)| QuestionResult {
+                question_id: format!("Q{:02}", i + 1),
+                question_text: String::new(),
+                expected: if i == 2 { ExpectedAnswer::IDontKnow } else { ExpectedAnswer::Yes },
+                actual: Answer::Yes,
+                correct: ok,
+                projection_distance: None,
+                connector_used: None,
+                evidence_missing: false,
+                category: String::new(),
+                elapsed_us: 0,
+            })
+            .collect();
+        let total_correct = correct.iter().filter(|c| **c).count();
+        FitnessReport {
+            results,
+            accuracy: 0.0,
+            honesty: 0.0,
+            fitness: total_correct as f64,
+            total_correct,
+            total_questions: correct.len(),
+            breakdown: Breakdown::default(),
+        }
+    }
+
+    #[test]
+    fn rows_and_balance() {
+        // Q01 needs the chain gate, Q02 is geometric, Q03 is absence,
+        // Q04 is only fixed by dropping the connector axis.
+        let components = [Component::ChainGate, Component::ConnectorAxis];
+        let result = run_ablation(&Ablation::default(), &components, |a| {
+            if a.disables(Component::ChainGate) {
+                report(&[false, true, true, false])
+            } else if a.disables(Component::ConnectorAxis) {
+                report(&[true, true, true, true])
+            } else {
+                report(&[true, true, true, false])
+            }
+        });
+
+        let labels: Vec<&str> = result.rows.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["baseline", "-chain-gate", "-connector-axis"]);
+        assert_eq!(result.rows[1].lost, vec!["Q01"]);
+        assert_eq!(result.rows[2].gained, vec!["Q04"]);
+        assert_eq!(result.balance.symbolic, 1);
+        assert_eq!(result.balance.geometric, 1);
+        assert_eq!(result.balance.absence, 1);
+    }
+}
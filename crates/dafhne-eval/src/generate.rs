@@ -0,0 +1,578 @@
+//! Test-suite generation from a closed dictionary.
+//!
+//! Every question is read off the dictionary text, so a generated suite is
+//! held out from hand-written ones but needs no trained space:
+//! - DIRECT LOOKUP: "dog — a domestic mammal" → "Is a dog a mammal?" Yes
+//! - TRANSITIVE REASONING: category of the category → "Is a dog an animal?" Yes
+//! - DEFINITION STATEMENTS: "it can make sound" → "Can a dog make sound?" Yes
+//! - NEGATION: "it is not a plant" → "Is an animal a plant?" No
+//! - SIBLING NEGATION: two words sharing a category whose definitions never
+//!   reach each other → "Is a dog a cat?" No
+//! - PROPERTY QUERY: "What is a dog?" → "a mammal"
+//! - UNKNOWN: probes built from words absent from the dictionary → I don't know
+
+use std::collections::{HashMap, HashSet};
+
+use dafhne_core::*;
+use dafhne_engine::connector_discovery::classify_word_roles;
+use dafhne_engine::resolver::{definitions_linked, first_sentence_category, is_property_word};
+use dafhne_parser::{stem_to_entry, tokenize};
+
+/// Ordinary words unlikely to be in an ELI5 closed dictionary.
+/// Filtered against the dictionary before use.
+pub const DEFAULT_ABSENT_WORDS: &[&str] = &[
+    "zebra", "volcano", "piano", "telescope", "dragon", "galaxy", "umbrella",
+    "penguin", "violin", "glacier", "cactus", "submarine", "tornado", "kangaroo",
+    "saxophone", "pyramid", "octopus", "lantern", "meteor", "walrus",
+];
+
+/// Never valid as a category noun, whatever the role classifier decides.
+const FUNCTION_WORDS: &[&str] = &[
+    "not", "is", "am", "are", "was", "were", "be", "been", "can", "has", "have",
+    "do", "does", "it", "they", "that", "this", "the", "a", "an", "of", "to",
+];
+
+/// Words that end the noun phrase opening a definition ("a wild animal *related* to ...").
+const CLAUSE_WORDS: &[&str] = &[
+    "that", "which", "who", "where", "when", "of", "to", "on", "in", "at", "with", "for", "from",
+    "by", "and", "or", "but", "as", "such", "like",
+];
+
+/// Section order in the generated markdown.
+const CATEGORIES: &[&str] = &[
+    "DIRECT LOOKUP",
+    "TRANSITIVE REASONING",
+    "DEFINITION STATEMENTS",
+    "NEGATION",
+    "SIBLING NEGATION",
+    "UNKNOWN",
+    "PROPERTY QUERY",
+];
+
+pub struct GeneratorConfig {
+    /// Maximum questions per category (0 = no limit). Sampled, not truncated.
+    pub max_per_category: usize,
+    /// Seed for sampling.
+    pub seed: u64,
+    /// Candidate words for I-don't-know probes.
+    pub absent_words: Vec<String>,
+    /// Maximum sibling negatives per subject word.
+    pub siblings_per_word: usize,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            max_per_category: 50,
+            seed: 42,
+            absent_words: DEFAULT_ABSENT_WORDS.iter().map(|w| w.to_string()).collect(),
+            siblings_per_word: 2,
+        }
+    }
+}
+
+/// Convert a descriptive sentence into a Yes/No question for verification.
+///
+/// "a dog is an animal." → "Is a dog an animal?"
+/// "a dog can make sound." → "Can a dog make sound?"
+/// "a dog is not a cat." → "Is a dog not a cat?"
+/// "a dog can live with a person." → "Can a dog live with a person?"
+pub fn sentence_to_question(sentence: &str) -> Option<String> {
+    let s = sentence.trim().trim_end_matches('.');
+    let words: Vec<&str> = s.split_whitespace().collect();
+    if words.len() < 3 {
+        return None;
+    }
+
+    // Find the verb: "is", "can", "has", "does"
+    // Pattern: [subject...] [verb] [rest...]
+    // "a dog is an animal" → verb at position 2
+    // "the sun is big" → verb at position 2
+    // "a dog can make sound" → verb at position 2
+    // "montmorency is a dog" → verb at position 1
+    let verbs = ["is", "can", "has", "does"];
+    let verb_pos = words.iter().position(|w| verbs.contains(w))?;
+
+    if verb_pos == 0 {
+        return None; // sentence starts with verb — can't restructure
+    }
+
+    let subject_part = words[..verb_pos].join(" ");
+    let verb = words[verb_pos];
+    let rest = words[verb_pos + 1..].join(" ");
+
+    // Construct question: "Is/Can [subject] [rest]?"
+    let q_verb = match verb {
+        "is" => "Is",
+        "can" => "Can",
+        "has" => "Has",
+        "does" => "Does",
+        _ => return None,
+    };
+
+    Some(format!("{} {} {}?", q_verb, subject_part, rest))
+}
+
+/// Generate a test suite from a closed dictionary.
+/// `params` supplies the chain depth used to decide whether siblings are related.
+pub fn generate_suite(
+    dictionary: &Dictionary,
+    params: &EngineParams,
+    config: &GeneratorConfig,
+) -> TestSuite {
+    let (structural, _content) = classify_word_roles(dictionary);
+
+    // Category nouns: words whose definition starts "a/an ..." and yields a category.
+    let mut categories: Vec<(String, String)> = Vec::new();
+    for entry in &dictionary.entries {
+        if !starts_with_indefinite_article(&entry.definition)
+            || FUNCTION_WORDS.contains(&entry.word.as_str())
+        {
+            continue;
+        }
+        if let Some(cat) = first_sentence_category(&entry.word, dictionary, &structural) {
+            // "a proof — not ...", "heat — was ..." pick up function words
+            if structural.contains(&cat) || FUNCTION_WORDS.contains(&cat.as_str()) {
+                continue;
+            }
+            if !is_category_noun(&entry.definition, &cat, dictionary) {
+                continue;
+            }
+            categories.push((entry.word.clone(), cat));
+        }
+    }
+    let category_of: HashMap<&str, &str> = categories
+        .iter()
+        .map(|(w, c)| (w.as_str(), c.as_str()))
+        .collect();
+
+    let mut candidates: HashMap<&str, Vec<TestQuestion>> = HashMap::new();
+    let mut push = |cat: &'static str, question: String, expected: ExpectedAnswer, chain: String| {
+        candidates.entry(cat).or_default().push(TestQuestion {
+            question,
+            expected,
+            chain,
+            category: cat.to_string(),
+            ..Default::default()
+        });
+    };
+
+    for (word, cat) in &categories {
+        let entry = dictionary.entries.iter().find(|e| &e.word == word);
+        let first_sentence = entry
+            .map(|e| e.definition.split('.').next().unwrap_or("").trim().to_string())
+            .unwrap_or_default();
+
+        // Direct is-a and What-Is
+        push(
+            "DIRECT LOOKUP",
+            format!("Is {} {}?", with_article(word), with_article(cat)),
+            ExpectedAnswer::Yes,
+            format!("{} definition \u{2192} \"{}\" \u{2192} {}", word, first_sentence, cat),
+        );
+        push(
+            "PROPERTY QUERY",
+            format!("What is {}?", with_article(word)),
+            ExpectedAnswer::Word(with_article(cat)),
+            format!("{} definition \u{2192} \"{}\" \u{2192} category = {}", word, first_sentence, cat),
+        );
+
+        // Transitive: category of the category
+        if let Some(&grand) = category_of.get(cat.as_str()) {
+            if grand != word && grand != cat {
+                push(
+                    "TRANSITIVE REASONING",
+                    format!("Is {} {}?", with_article(word), with_article(grand)),
+                    ExpectedAnswer::Yes,
+                    format!("{} \u{2192} {} \u{2192} {} (2 hops)", word, cat, grand),
+                );
+            }
+        }
+    }
+
+    // Definition statements and explicit negations, from category-noun subjects
+    for (word, _) in &categories {
+        let Some(entry) = dictionary.entries.iter().find(|e| &e.word == word) else {
+            continue;
+        };
+        for sentence in entry.definition.split('.').skip(1) {
+            let tokens = tokenize(sentence);
+            let Some((verb, negated, rest)) = definition_statement(word, &tokens, dictionary) else {
+                continue;
+            };
+            let question = format!("{} {} {}?", capitalize(verb), with_article(word), rest.join(" "));
+            if negated {
+                push(
+                    "NEGATION",
+                    question,
+                    ExpectedAnswer::No,
+                    format!("{} definition \u{2192} \"{}\" (explicit negation)", word, sentence.trim()),
+                );
+            } else {
+                push(
+                    "DEFINITION STATEMENTS",
+                    question,
+                    ExpectedAnswer::Yes,
+                    format!("{} definition \u{2192} \"{}\"", word, sentence.trim()),
+                );
+            }
+        }
+    }
+
+    // Sibling negatives: same category, definitions unrelated in both directions
+    let mut by_category: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (word, cat) in &categories {
+        by_category.entry(cat.as_str()).or_default().push(word.as_str());
+    }
+    for (word, cat) in &categories {
+        let mut added = 0;
+        for sibling in &by_category[cat.as_str()] {
+            if added >= config.siblings_per_word {
+                break;
+            }
+            if *sibling == word.as_str()
+                || definitions_linked(word, sibling, dictionary, &structural, params)
+            {
+                continue;
+            }
+            push(
+                "SIBLING NEGATION",
+                format!("Is {} {}?", with_article(word), with_article(sibling)),
+                ExpectedAnswer::No,
+                format!(
+                    "{} and {} are both {}; neither definition reaches the other",
+                    word, sibling, with_article(cat)
+                ),
+            );
+            added += 1;
+        }
+    }
+
+    // I-don't-know probes from absent words
+    let absent: Vec<&String> = config
+        .absent_words
+        .iter()
+        .filter(|w| stem_to_entry(w, &dictionary.entry_set).is_none())
+        .collect();
+    for (i, probe) in absent.iter().enumerate() {
+        push(
+            "UNKNOWN",
+            format!("What is {}?", with_article(probe)),
+            ExpectedAnswer::IDontKnow,
+            format!("{} is not in the dictionary", probe),
+        );
+        if !categories.is_empty() {
+            let (word, _) = &categories[i * 7 % categories.len()];
+            push(
+                "UNKNOWN",
+                format!("Is {} {}?", with_article(word), with_article(probe)),
+                ExpectedAnswer::IDontKnow,
+                format!("{} is not in the dictionary; no chain can reach it", probe),
+            );
+        }
+    }
+
+    // Sample each category, dedupe question text, number sequentially
+    let mut rng = SimpleRng::new(config.seed);
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut questions: Vec<TestQuestion> = Vec::new();
+    for cat in CATEGORIES {
+        let Some(pool) = candidates.remove(cat) else {
+            continue;
+        };
+        let pool: Vec<TestQuestion> = pool
+            .into_iter()
+            .filter(|q| seen.insert(q.question.to_lowercase()))
+            .collect();
+        questions.extend(sample(pool, config.max_per_category, &mut rng));
+    }
+
+    let width = questions.len().to_string().len().max(2);
+    for (i, q) in questions.iter_mut().enumerate() {
+        q.id = format!("Q{:0width$}", i + 1, width = width);
+    }
+
+    TestSuite { questions }
+}
+
+/// Render a suite in the markdown test format read by `parse_test_questions`.
+pub fn suite_to_markdown(suite: &TestSuite, title: &str) -> String {
+    let mut md = String::new();
+    md.push_str(&format!(
+        "# {} \u{2014} Generated Test Questions ({})\n\n",
+        title,
+        suite.questions.len()
+    ));
+    md.push_str("> Generated from the dictionary text by `dafhne-eval --generate-tests`.\n");
+    md.push_str("> Answer types: YES, NO, I DON'T KNOW, or a word/phrase from the dictionary.\n");
+
+    let mut current: Option<&str> = None;
+    for q in &suite.questions {
+        if current != Some(q.category.as_str()) {
+            md.push_str(&format!("\n---\n\n## {}\n", q.category));
+            current = Some(q.category.as_str());
+        }
+        md.push_str(&format!("\n**{}**: {}\n", q.id, q.question));
+        md.push_str(&format!("**A**: {}\n", q.expected));
+        if !q.chain.is_empty() {
+            md.push_str(&format!("**Chain**: {}\n", q.chain));
+        }
+    }
+
+    md
+}
+
+// ─── Helpers ─────────────────────────────────────────────────
+
+/// Read a later definition sentence as a statement about `word`.
+/// Returns (question verb, negated, object tokens).
+///
+/// Accepted shapes (ELI5 definitions):
+///   "it can make sound" / "they can be trained" / "dogs have fur" → (can|has|is, false, ...)
+///   "it is not a plant" / "it cannot fly"                          → (..., true, ...)
+///   "not a plant" (bare fragment after the category sentence)      → (is, true, ...)
+///
+/// The object must end in a dictionary word so the question stays answerable
+/// inside the closed dictionary.
+fn definition_statement(
+    word: &str,
+    tokens: &[String],
+    dictionary: &Dictionary,
+) -> Option<(&'static str, bool, Vec<String>)> {
+    let first = tokens.first()?;
+    let (verb, mut negated, mut rest): (&'static str, bool, &[String]) = if first == "not" {
+        ("is", true, &tokens[1..])
+    } else {
+        let about_word = matches!(first.as_str(), "it" | "they")
+            || stem_to_entry(first, &dictionary.entry_set).as_deref() == Some(word);
+        if !about_word || tokens.len() < 3 {
+            return None;
+        }
+        match tokens[1].as_str() {
+            "is" | "are" => ("is", false, &tokens[2..]),
+            "can" => ("can", false, &tokens[2..]),
+            "cannot" => ("can", true, &tokens[2..]),
+            "has" | "have" => ("has", false, &tokens[2..]),
+            _ => return None,
+        }
+    };
+
+    if rest.first().map(|t| t.as_str()) == Some("not") {
+        negated = true;
+        rest = &rest[1..];
+    }
+    // "have been companions", "is also ..." — not simple statements
+    if rest.is_empty() || rest.len() > 6 || matches!(rest[0].as_str(), "been" | "also" | "not") {
+        return None;
+    }
+    // "but a portion", "solid not gas" — compound objects are not single claims
+    if rest.iter().any(|t| matches!(t.as_str(), "but" | "and" | "or" | "not" | "as")) {
+        return None;
+    }
+    // Bare fragments: "not a plant" / "not flat", never "not in pain"
+    if first == "not" && rest.len() > 1 && !matches!(rest[0].as_str(), "a" | "an") {
+        return None;
+    }
+    stem_to_entry(rest.last()?, &dictionary.entry_set)?;
+
+    Some((verb, negated, rest.to_vec()))
+}
+
+/// Random subset of at most `max` items (0 = all), kept in original order.
+fn sample<T>(items: Vec<T>, max: usize, rng: &mut SimpleRng) -> Vec<T> {
+    if max == 0 || items.len() <= max {
+        return items;
+    }
+    let mut indices: Vec<usize> = (0..items.len()).collect();
+    for i in (1..indices.len()).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        indices.swap(i, j);
+    }
+    let keep: HashSet<usize> = indices.into_iter().take(max).collect();
+    items
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| keep.contains(i))
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// Whether `cat` is a sound is-a category for a word defined as `definition`:
+/// the head of the noun phrase the definition opens with, itself a noun
+/// entry (defined "a/an ...") and not a property word. Rejects "a strong,
+/// warm color" → warm, "an action done on purpose" → purpose, and partitive
+/// heads: "a piece of land" says nothing about pieces.
+fn is_category_noun(definition: &str, cat: &str, dictionary: &Dictionary) -> bool {
+    let first_sentence = definition.split('.').next().unwrap_or("");
+    let tokens = tokenize(first_sentence);
+    let phrase: Vec<&String> = tokens
+        .iter()
+        .skip(1) // the article
+        .take_while(|t| !CLAUSE_WORDS.contains(&t.as_str()))
+        .collect();
+    let Some(head) = phrase
+        .iter()
+        .rposition(|t| stem_to_entry(t, &dictionary.entry_set).is_some())
+    else {
+        return false;
+    };
+    if stem_to_entry(phrase[head], &dictionary.entry_set).as_deref() != Some(cat) {
+        return false;
+    }
+    // tokens[0] is the article, so the word after the head is tokens[head + 2]
+    if tokens.get(head + 2).map(String::as_str) == Some("of") {
+        return false;
+    }
+    let is_noun_entry = dictionary
+        .entries
+        .iter()
+        .any(|e| e.word == cat && starts_with_indefinite_article(&e.definition));
+    is_noun_entry && !is_property_word(cat, dictionary)
+}
+
+fn starts_with_indefinite_article(definition: &str) -> bool {
+    matches!(
+        tokenize(definition).first().map(|s| s.as_str()),
+        Some("a") | Some("an")
+    )
+}
+
+/// `word` with "a" or "an" in front, chosen by how the word sounds.
+pub fn with_article(word: &str) -> String {
+    // Spelling is a proxy for sound: "a unit", "a one", "an hour"
+    const CONSONANT_SOUND: &[&str] = &["uni", "use", "usu", "uti", "ure", "eu", "ewe", "one", "once"];
+    const VOWEL_SOUND: &[&str] = &["hour", "honest", "honor", "heir"];
+    let lower = word.to_lowercase();
+    let vowel = if CONSONANT_SOUND.iter().any(|p| lower.starts_with(p)) {
+        false
+    } else if VOWEL_SOUND.iter().any(|p| lower.starts_with(p)) {
+        true
+    } else {
+        lower.starts_with(|c: char| "aeiou".contains(c))
+    };
+    format!("{} {}", if vowel { "an" } else { "a" }, word)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dafhne_parser::{parse_dictionary, parse_test_questions};
+
+    const DICT: &str = "\
+**thing** \u{2014} all that is.
+**creature** \u{2014} a thing that lives.
+**animal** \u{2014} a creature. it is not a plant.
+**plant** \u{2014} a thing that lives in the ground.
+**dog** \u{2014} an animal. it can make sound.
+**cat** \u{2014} an animal. it can see.
+**warm** \u{2014} a little hot.
+**hot** \u{2014} having heat.
+**heat** \u{2014} a thing you feel.
+**red** \u{2014} a warm heat.
+**piece** \u{2014} a part.
+**part** \u{2014} a thing in a thing.
+**garden** \u{2014} a piece of ground.
+**task** \u{2014} a piece of work.
+**work** \u{2014} a thing you do.
+**sound** \u{2014} what you hear.
+**ground** \u{2014} where you live.
+**see** \u{2014} to know with the eyes.
+**make** \u{2014} to cause.
+**lives** \u{2014} is alive.
+";
+
+    fn generated() -> TestSuite {
+        let dict = parse_dictionary(DICT);
+        let config = GeneratorConfig {
+            max_per_category: 0,
+            absent_words: vec!["zebra".to_string(), "dog".to_string()],
+            ..Default::default()
+        };
+        generate_suite(&dict, &EngineParams::default(), &config)
+    }
+
+    fn find<'a>(suite: &'a TestSuite, question: &str) -> &'a TestQuestion {
+        suite
+            .questions
+            .iter()
+            .find(|q| q.question == question)
+            .unwrap_or_else(|| panic!("missing generated question: {}", question))
+    }
+
+    #[test]
+    fn test_sentence_to_question() {
+        assert_eq!(sentence_to_question("a dog is an animal."), Some("Is a dog an animal?".to_string()));
+        assert_eq!(sentence_to_question("a dog can make sound."), Some("Can a dog make sound?".to_string()));
+        assert_eq!(sentence_to_question("is it"), None);
+    }
+
+    #[test]
+    fn test_generated_question_kinds() {
+        let suite = generated();
+        assert_eq!(find(&suite, "Is a dog an animal?").expected, ExpectedAnswer::Yes);
+        assert_eq!(find(&suite, "Is a dog a creature?").category, "TRANSITIVE REASONING");
+        assert_eq!(find(&suite, "Can a dog make sound?").expected, ExpectedAnswer::Yes);
+        assert_eq!(find(&suite, "Is an animal a plant?").expected, ExpectedAnswer::No);
+        assert_eq!(find(&suite, "Is a dog a cat?").category, "SIBLING NEGATION");
+        assert_eq!(
+            find(&suite, "What is a cat?").expected,
+            ExpectedAnswer::Word("an animal".to_string())
+        );
+        assert_eq!(find(&suite, "What is a zebra?").expected, ExpectedAnswer::IDontKnow);
+        // Only head nouns that are themselves noun entries become categories
+        assert!(suite.questions.iter().all(|q| q.question != "Is a red a warm?"));
+        assert!(suite.questions.iter().all(|q| q.question != "Is an animal a thing?"));
+        // "a piece of ..." makes no category, so no "Is a garden a task?"
+        assert!(suite.questions.iter().all(|q| !q.question.contains("garden") && !q.question.contains("task")));
+        // Words present in the dictionary are never used as IDK probes
+        assert!(suite.questions.iter().all(|q| q.question != "What is a dog?" || q.expected != ExpectedAnswer::IDontKnow));
+    }
+
+    #[test]
+    fn test_articles_follow_sound() {
+        assert_eq!(with_article("unit"), "a unit");
+        assert_eq!(with_article("uncle"), "an uncle");
+        assert_eq!(with_article("hour"), "an hour");
+        assert_eq!(with_article("one"), "a one");
+        assert_eq!(with_article("animal"), "an animal");
+        assert_eq!(with_article("dog"), "a dog");
+        assert_eq!(with_article("Hour"), "an Hour");
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let suite = generated();
+        let md = suite_to_markdown(&suite, "tiny");
+        let parsed = parse_test_questions(&md);
+        assert_eq!(parsed.questions.len(), suite.questions.len());
+        for (a, b) in parsed.questions.iter().zip(&suite.questions) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.question, b.question);
+            assert_eq!(a.expected, b.expected);
+            assert_eq!(a.category, b.category);
+        }
+    }
+
+    #[test]
+    fn test_sampling_caps_each_category() {
+        let dict = parse_dictionary(DICT);
+        let config = GeneratorConfig {
+            max_per_category: 1,
+            ..Default::default()
+        };
+        let suite = generate_suite(&dict, &EngineParams::default(), &config);
+        let mut per_cat: HashMap<&str, usize> = HashMap::new();
+        for q in &suite.questions {
+            *per_cat.entry(q.category.as_str()).or_default() += 1;
+        }
+        assert!(per_cat.values().all(|&n| n == 1));
+    }
+}
@@ -1,3 +1,10 @@
+pub mod ablation;
+pub mod generate;
+pub mod output;
+pub mod snapshot;
+
+use std::time::Instant;
+
 use dafhne_core::*;
 use dafhne_engine::multispace::MultiSpace;
 use dafhne_engine::strategy::StrategyConfig;
@@ -13,6 +20,7 @@ pub fn evaluate(
     let mut results = Vec::new();
 
     for question in &test_suite.questions {
+        let start = Instant::now();
         let (answer, distance, connector_used) = dafhne_engine::resolver::resolve_question(
             &question.question,
             engine.space(),
@@ -23,63 +31,20 @@ pub fn evaluate(
             strategy,
         );
 
-        let correct = match (&question.expected, &answer) {
-            (ExpectedAnswer::Yes, Answer::Yes) => true,
-            (ExpectedAnswer::No, Answer::No) => true,
-            (ExpectedAnswer::IDontKnow, Answer::IDontKnow) => true,
-            (ExpectedAnswer::Word(expected), Answer::Word(actual)) => {
-                fuzzy_word_match(expected, actual)
-            }
-            _ => false,
-        };
-
-        results.push(QuestionResult {
-            question_id: question.id.clone(),
-            question_text: question.question.clone(),
-            expected: question.expected.clone(),
-            actual: answer,
-            correct,
-            projection_distance: distance,
-            connector_used,
-        });
+        let elapsed_us = start.elapsed().as_micros() as u64;
+        results.push(score_answer(question, answer, distance, connector_used, elapsed_us, || {
+            dafhne_engine::resolver::chain_evidence(
+                &question.question,
+                engine.space(),
+                dictionary,
+                engine.structural(),
+                engine.content(),
+                params,
+            )
+        }));
     }
 
-    // accuracy = correct answerable / total answerable
-    let answerable: Vec<&QuestionResult> = results
-        .iter()
-        .filter(|r| r.expected != ExpectedAnswer::IDontKnow)
-        .collect();
-    let correct_answerable = answerable.iter().filter(|r| r.correct).count();
-    let accuracy = if answerable.is_empty() {
-        0.0
-    } else {
-        correct_answerable as f64 / answerable.len() as f64
-    };
-
-    // honesty = correct IDK / total unknowable
-    let unknowable: Vec<&QuestionResult> = results
-        .iter()
-        .filter(|r| r.expected == ExpectedAnswer::IDontKnow)
-        .collect();
-    let correct_idk = unknowable.iter().filter(|r| r.correct).count();
-    let honesty = if unknowable.is_empty() {
-        0.0
-    } else {
-        correct_idk as f64 / unknowable.len() as f64
-    };
-
-    let fitness = 0.5 * accuracy + 0.5 * honesty;
-    let total_correct = results.iter().filter(|r| r.correct).count();
-    let total_questions = results.len();
-
-    FitnessReport {
-        results,
-        accuracy,
-        honesty,
-        fitness,
-        total_correct,
-        total_questions,
-    }
+    FitnessReport::from_results(results)
 }
 
 /// Multi-space evaluation: query the MultiSpace orchestrator.
@@ -90,65 +55,16 @@ pub fn evaluate_multispace(
     let mut results = Vec::new();
 
     for question in &test_suite.questions {
+        let start = Instant::now();
         let (answer, distance, connector_used) = multi.resolve(&question.question);
 
-        let correct = match (&question.expected, &answer) {
-            (ExpectedAnswer::Yes, Answer::Yes) => true,
-            (ExpectedAnswer::No, Answer::No) => true,
-            (ExpectedAnswer::IDontKnow, Answer::IDontKnow) => true,
-            (ExpectedAnswer::Word(expected), Answer::Word(actual)) => {
-                fuzzy_word_match(expected, actual)
-            }
-            _ => false,
-        };
-
-        results.push(QuestionResult {
-            question_id: question.id.clone(),
-            question_text: question.question.clone(),
-            expected: question.expected.clone(),
-            actual: answer,
-            correct,
-            projection_distance: distance,
-            connector_used,
-        });
+        let elapsed_us = start.elapsed().as_micros() as u64;
+        results.push(score_answer(question, answer, distance, connector_used, elapsed_us, || {
+            multispace_chain_evidence(multi, &question.question)
+        }));
     }
 
-    // accuracy = correct answerable / total answerable
-    let answerable: Vec<&QuestionResult> = results
-        .iter()
-        .filter(|r| r.expected != ExpectedAnswer::IDontKnow)
-        .collect();
-    let correct_answerable = answerable.iter().filter(|r| r.correct).count();
-    let accuracy = if answerable.is_empty() {
-        0.0
-    } else {
-        correct_answerable as f64 / answerable.len() as f64
-    };
-
-    // honesty = correct IDK / total unknowable
-    let unknowable: Vec<&QuestionResult> = results
-        .iter()
-        .filter(|r| r.expected == ExpectedAnswer::IDontKnow)
-        .collect();
-    let correct_idk = unknowable.iter().filter(|r| r.correct).count();
-    let honesty = if unknowable.is_empty() {
-        0.0
-    } else {
-        correct_idk as f64 / unknowable.len() as f64
-    };
-
-    let fitness = 0.5 * accuracy + 0.5 * honesty;
-    let total_correct = results.iter().filter(|r| r.correct).count();
-    let total_questions = results.len();
-
-    FitnessReport {
-        results,
-        accuracy,
-        honesty,
-        fitness,
-        total_correct,
-        total_questions,
-    }
+    FitnessReport::from_results(results)
 }
 
 /// Dual-space evaluation: query two engines and combine answers.
@@ -168,6 +84,7 @@ pub fn evaluate_dual(
     let mut results = Vec::new();
 
     for question in &test_suite.questions {
+        let start = Instant::now();
         let (answer_a, dist_a, conn_a) = dafhne_engine::resolver::resolve_question(
             &question.question,
             engine_a.space(),
@@ -208,62 +125,20 @@ pub fn evaluate_dual(
             }
         };
 
-        let correct = match (&question.expected, &answer) {
-            (ExpectedAnswer::Yes, Answer::Yes) => true,
-            (ExpectedAnswer::No, Answer::No) => true,
-            (ExpectedAnswer::IDontKnow, Answer::IDontKnow) => true,
-            (ExpectedAnswer::Word(expected), Answer::Word(actual)) => {
-                fuzzy_word_match(expected, actual)
-            }
-            _ => false,
-        };
-
-        results.push(QuestionResult {
-            question_id: question.id.clone(),
-            question_text: question.question.clone(),
-            expected: question.expected.clone(),
-            actual: answer,
-            correct,
-            projection_distance: distance,
-            connector_used,
-        });
+        let elapsed_us = start.elapsed().as_micros() as u64;
+        results.push(score_answer(question, answer, distance, connector_used, elapsed_us, || {
+            dafhne_engine::resolver::chain_evidence(
+                &question.question,
+                engine_a.space(),
+                dictionary,
+                engine_a.structural(),
+                engine_a.content(),
+                params_a,
+            )
+        }));
     }
 
-    // Same fitness computation as single-space
-    let answerable: Vec<&QuestionResult> = results
-        .iter()
-        .filter(|r| r.expected != ExpectedAnswer::IDontKnow)
-        .collect();
-    let correct_answerable = answerable.iter().filter(|r| r.correct).count();
-    let accuracy = if answerable.is_empty() {
-        0.0
-    } else {
-        correct_answerable as f64 / answerable.len() as f64
-    };
-
-    let unknowable: Vec<&QuestionResult> = results
-        .iter()
-        .filter(|r| r.expected == ExpectedAnswer::IDontKnow)
-        .collect();
-    let correct_idk = unknowable.iter().filter(|r| r.correct).count();
-    let honesty = if unknowable.is_empty() {
-        0.0
-    } else {
-        correct_idk as f64 / unknowable.len() as f64
-    };
-
-    let fitness = 0.5 * accuracy + 0.5 * honesty;
-    let total_correct = results.iter().filter(|r| r.correct).count();
-    let total_questions = results.len();
-
-    FitnessReport {
-        results,
-        accuracy,
-        honesty,
-        fitness,
-        total_correct,
-        total_questions,
-    }
+    FitnessReport::from_results(results)
 }
 
 /// Compute how "confident" an answer is based on distance from threshold boundaries.
@@ -299,6 +174,100 @@ fn answer_confidence(answer: &Answer, distance: Option<f64>, params: &EnginePara
     }
 }
 
+/// Score one answer: it is correct when it matches the expected answer (or an
+/// alternative) and meets the question's evidence requirements. `chain` is only
+/// called for matched questions that require chain evidence.
+pub fn score_answer(
+    question: &TestQuestion,
+    answer: Answer,
+    distance: Option<f64>,
+    connector_used: Option<String>,
+    elapsed_us: u64,
+    chain: impl FnOnce() -> Option<bool>,
+) -> QuestionResult {
+    let matched = answer_matches(question, &answer);
+    let evidence_ok = !matched || evidence_satisfied(question, &answer, distance, connector_used.as_deref(), chain);
+    QuestionResult {
+        question_id: question.id.clone(),
+        question_text: question.question.clone(),
+        expected: question.expected.clone(),
+        actual: answer,
+        correct: matched && evidence_ok,
+        projection_distance: distance,
+        connector_used,
+        evidence_missing: !evidence_ok,
+        category: question.category.clone(),
+        elapsed_us,
+    }
+}
+
+/// Whether `answer` matches the expected answer or any listed alternative.
+pub fn answer_matches(question: &TestQuestion, answer: &Answer) -> bool {
+    std::iter::once(&question.expected)
+        .chain(&question.alternatives)
+        .any(|expected| match (expected, answer) {
+            (ExpectedAnswer::Yes, Answer::Yes) => true,
+            (ExpectedAnswer::No, Answer::No) => true,
+            (ExpectedAnswer::IDontKnow, Answer::IDontKnow) => true,
+            (ExpectedAnswer::Word(expected), Answer::Word(actual)) => {
+                fuzzy_word_match(expected, actual)
+            }
+            _ => false,
+        })
+}
+
+/// Check the question's evidence requirements (if any) against what the resolver
+/// reported. `chain` is only called for questions that require chain evidence.
+pub fn evidence_satisfied(
+    question: &TestQuestion,
+    answer: &Answer,
+    distance: Option<f64>,
+    connector: Option<&str>,
+    chain: impl FnOnce() -> Option<bool>,
+) -> bool {
+    let evidence = match &question.evidence {
+        Some(e) => e,
+        None => return true,
+    };
+    if let Some(pattern) = &evidence.connector {
+        if !connector.is_some_and(|c| c.contains(pattern.as_str())) {
+            return false;
+        }
+    }
+    if let Some(max) = evidence.max_distance {
+        if !distance.is_some_and(|d| d <= max) {
+            return false;
+        }
+    }
+    if evidence.chain {
+        // The chain verdict must agree with the answer given.
+        return matches!(
+            (chain(), answer),
+            (Some(true), Answer::Yes | Answer::Word(_)) | (Some(false), Answer::No)
+        );
+    }
+    true
+}
+
+/// Chain evidence across spaces: the first space (in load order) whose
+/// definitions decide the question.
+pub fn multispace_chain_evidence(multi: &MultiSpace, question: &str) -> Option<bool> {
+    multi
+        .space_order
+        .iter()
+        .filter_map(|name| multi.spaces.get(name))
+        .find_map(|space| {
+            dafhne_engine::resolver::chain_evidence(
+                question,
+                space.engine.space(),
+                &space.dictionary,
+                space.engine.structural(),
+                space.engine.content(),
+                &space.params,
+            )
+        })
+}
+
 pub fn fuzzy_word_match(expected: &str, actual: &str) -> bool {
     let e = expected.to_lowercase();
     let a = actual.to_lowercase();
@@ -321,6 +290,26 @@ pub fn fuzzy_word_match(expected: &str, actual: &str) -> bool {
     false
 }
 
+/// Merge entity definitions into a dictionary. Entity entries override
+/// entries for the same word and are tagged `is_entity` (used by the
+/// definition_category() fast path).
+pub fn merge_entities(dictionary: Dictionary, entities: Dictionary) -> Dictionary {
+    let mut entry_map: std::collections::HashMap<String, DictionaryEntry> =
+        dictionary.entries.into_iter().map(|e| (e.word.clone(), e)).collect();
+
+    for mut entity_entry in entities.entries {
+        entity_entry.is_entity = true;
+        entry_map.insert(entity_entry.word.clone(), entity_entry);
+    }
+
+    let mut entries: Vec<DictionaryEntry> = entry_map.into_values().collect();
+    entries.sort_by(|a, b| a.word.cmp(&b.word));
+    let entry_words: Vec<String> = entries.iter().map(|e| e.word.clone()).collect();
+    let entry_set: std::collections::HashSet<String> = entry_words.iter().cloned().collect();
+
+    Dictionary { entries, entry_words, entry_set }
+}
+
 pub fn print_space_statistics(space: &GeometricSpace, _dictionary: &Dictionary) {
     println!("=== Space Statistics ===");
     println!(
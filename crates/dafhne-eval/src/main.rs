@@ -1,55 +1,19 @@
 use std::path::PathBuf;
+use std::time::Instant;
 
 use clap::Parser;
 use dafhne_core::*;
 use dafhne_cache::{AssemblerConfig, DictionaryAssembler, DictionaryCache, ManualFileCache, OllamaCache, WiktionaryCache};
+use dafhne_engine::ablation::{Ablation, Component};
 use dafhne_engine::strategy::StrategyConfig;
 use dafhne_engine::Engine;
 use dafhne_engine::multispace::{MultiSpace, SpaceConfig};
-use dafhne_eval::{evaluate, evaluate_multispace, print_space_statistics};
-use dafhne_parser::{load_dictionary, parse_grammar_text, parse_test_questions};
-
-/// Convert a descriptive sentence into a Yes/No question for verification.
-///
-/// "a dog is an animal." → "Is a dog an animal?"
-/// "a dog can make sound." → "Can a dog make sound?"
-/// "a dog is not a cat." → "Is a dog not a cat?"
-/// "a dog can live with a person." → "Can a dog live with a person?"
-fn sentence_to_question(sentence: &str) -> Option<String> {
-    let s = sentence.trim().trim_end_matches('.');
-    let words: Vec<&str> = s.split_whitespace().collect();
-    if words.len() < 3 {
-        return None;
-    }
-
-    // Find the verb: "is", "can", "has", "does"
-    // Pattern: [subject...] [verb] [rest...]
-    // "a dog is an animal" → verb at position 2
-    // "the sun is big" → verb at position 2
-    // "a dog can make sound" → verb at position 2
-    // "montmorency is a dog" → verb at position 1
-    let verbs = ["is", "can", "has", "does"];
-    let verb_pos = words.iter().position(|w| verbs.contains(w))?;
-
-    if verb_pos == 0 {
-        return None; // sentence starts with verb — can't restructure
-    }
-
-    let subject_part = words[..verb_pos].join(" ");
-    let verb = words[verb_pos];
-    let rest = words[verb_pos + 1..].join(" ");
-
-    // Construct question: "Is/Can [subject] [rest]?"
-    let q_verb = match verb {
-        "is" => "Is",
-        "can" => "Can",
-        "has" => "Has",
-        "does" => "Does",
-        _ => return None,
-    };
-
-    Some(format!("{} {} {}?", q_verb, subject_part, rest))
-}
+use dafhne_eval::ablation::{print_ablation_report, run_ablation, AblationReport};
+use dafhne_eval::output::{write_run, EvalRun, OutputFormat, SpaceSummary, Timings};
+use dafhne_eval::snapshot::{diff_snapshots, load_manifest, print_diff, record_snapshot, Snapshot};
+use dafhne_eval::generate::{generate_suite, sentence_to_question, suite_to_markdown, GeneratorConfig};
+use dafhne_eval::{evaluate, evaluate_multispace, merge_entities, print_space_statistics};
+use dafhne_parser::{load_dictionary, load_test_suite, parse_grammar_text};
 
 #[derive(Parser)]
 #[command(name = "dafhne-eval", about = "Evaluate DAFHNE engine on test questions")]
@@ -57,7 +21,7 @@ struct Cli {
     /// Path to dictionary file (closed mode)
     #[arg(long, default_value = "dictionaries/dict5.md")]
     dict: PathBuf,
-    /// Path to test questions file
+    /// Path to test questions file (.md, or structured .toml / .jsonl)
     #[arg(long, default_value = "dictionaries/dict5_test.md")]
     test: PathBuf,
     /// Path to grammar text file (optional, enables grammar reinforcement)
@@ -117,6 +81,66 @@ struct Cli {
     #[arg(long)]
     dump_space: Option<PathBuf>,
 
+    // ── Threshold calibration ───────────────────────────────────
+    /// Validation questions: fit yes/no thresholds to the trained space
+    /// (replaces the evolved thresholds; single-space mode only)
+    #[arg(long)]
+    calibrate: Option<PathBuf>,
+    /// Apply a stored calibration JSON instead of fitting one
+    #[arg(long, conflicts_with = "calibrate")]
+    calibration: Option<PathBuf>,
+    /// Write the calibration (thresholds and validation scores) as JSON
+    #[arg(long)]
+    calibration_out: Option<PathBuf>,
+
+    // ── Ablation ────────────────────────────────────────────────
+    /// Switch resolver components off (comma-separated): chain-gate, definition-lookup,
+    /// example-lookup, cross-space-chain, weighted-distance, connector-axis, special-patterns
+    #[arg(long, value_delimiter = ',')]
+    disable: Vec<Component>,
+    /// Rerun the suite with each resolver component switched off and print
+    /// a contribution table (accuracy/honesty/fitness deltas, answers lost)
+    #[arg(long)]
+    ablation: bool,
+    /// Write the ablation report as JSON
+    #[arg(long, requires = "ablation")]
+    ablation_out: Option<PathBuf>,
+
+    // ── Machine-readable output ─────────────────────────────────
+    /// Result format: text (tables on stdout), json, jsonl, junit or csv
+    #[arg(long, value_enum, default_value = "text")]
+    format: OutputFormat,
+    /// File for --format output (stdout keeps the human-readable report)
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    // ── Regression snapshot ─────────────────────────────────────
+    /// Snapshot manifest (TOML): answer every suite in it and diff against
+    /// --golden; exits non-zero when a correct answer turns wrong
+    #[arg(long)]
+    snapshot: Option<PathBuf>,
+    /// Golden snapshot file (JSON) used by --snapshot
+    #[arg(long, default_value = "dictionaries/golden_snapshot.json")]
+    golden: PathBuf,
+    /// Record the golden snapshot instead of comparing against it
+    #[arg(long, requires = "snapshot")]
+    update_golden: bool,
+
+    // ── Test generation ─────────────────────────────────────────
+    /// Generate a test suite (markdown) from the dictionary text and exit
+    #[arg(long)]
+    generate_tests: Option<PathBuf>,
+    /// Maximum generated questions per category (0 = no limit)
+    #[arg(long, default_value = "50")]
+    generate_max: usize,
+    /// Seed for sampling generated questions
+    #[arg(long, default_value = "42")]
+    generate_seed: u64,
+    /// File of candidate words for I-don't-know probes, one per line
+    /// (default: a built-in list; words in the dictionary are skipped)
+    #[arg(long)]
+    absent_words: Option<PathBuf>,
+
     // ── Ollama options ─────────────────────────────────────────────
     /// Ollama API base URL (only used with --cache-type ollama)
     #[arg(long, default_value = "http://localhost:11434")]
@@ -176,6 +200,7 @@ fn parse_strategy(genome: &GenomeFile) -> StrategyConfig {
             other => { eprintln!("Unknown negation_model: {}, using Inversion", other); NegationModel::Inversion }
         },
         use_connector_axis: genome.use_connector_axis,
+        ablation: Default::default(),
     }
 }
 
@@ -184,8 +209,16 @@ fn main() {
 
     println!("=== DAFHNE v0.1 \u{2014} Geometric Comprehension Engine ===\n");
 
+    if cli.format != OutputFormat::Text && cli.output.is_none() {
+        eprintln!(
+            "Error: --format {} needs --output (stdout carries the human-readable report)",
+            format!("{:?}", cli.format).to_lowercase()
+        );
+        std::process::exit(1);
+    }
+
     // ── Load parameters from genome or use defaults ──────────────
-    let (params, strategy): (EngineParams, StrategyConfig) = if let Some(genome_path) = &cli.genome {
+    let (params, mut strategy): (EngineParams, StrategyConfig) = if let Some(genome_path) = &cli.genome {
         let content = std::fs::read_to_string(genome_path).expect("Failed to read genome file");
         let genome: GenomeFile = serde_json::from_str(&content).expect("Failed to parse genome JSON");
         let strategy = parse_strategy(&genome);
@@ -196,6 +229,16 @@ fn main() {
     } else {
         (EngineParams::default(), StrategyConfig::default())
     };
+    if !cli.disable.is_empty() {
+        strategy.ablation = Ablation::without_all(&cli.disable);
+        println!("[Disabled: {}]", strategy.ablation.label());
+    }
+
+    // ── Regression snapshot (runs its own suites) ────────────────
+    if let Some(ref manifest_path) = cli.snapshot {
+        run_snapshot(&cli, manifest_path, &params, &strategy);
+        return;
+    }
 
     // ── Build or assemble dictionary ─────────────────────────────
     let mut dictionary = if let Some(text_path) = &cli.text {
@@ -285,30 +328,55 @@ fn main() {
             println!("[Entities: {} entries from {:?}]", entities_dict.entries.len(), entities_path);
 
             // Merge: entity entries override assembled entries for same word
-            let mut entry_map: std::collections::HashMap<String, DictionaryEntry> =
-                dictionary.entries.into_iter().map(|e| (e.word.clone(), e)).collect();
-
-            for mut entity_entry in entities_dict.entries {
-                entity_entry.is_entity = true; // tag as entity for definition_category() fast path
-                entry_map.insert(entity_entry.word.clone(), entity_entry);
-            }
-
-            let mut entries: Vec<DictionaryEntry> = entry_map.into_values().collect();
-            entries.sort_by(|a, b| a.word.cmp(&b.word));
-            let entry_words: Vec<String> = entries.iter().map(|e| e.word.clone()).collect();
-            let entry_set: std::collections::HashSet<String> = entry_words.iter().cloned().collect();
-
-            dictionary = Dictionary { entries, entry_words, entry_set };
+            dictionary = merge_entities(dictionary, entities_dict);
             println!("Dictionary after entity merge: {} entries", dictionary.entries.len());
         }
     }
 
     println!("Dictionary: {} entries", dictionary.entries.len());
 
+    // ── Test generation (no training needed) ────────────────────
+    if let Some(ref out_path) = cli.generate_tests {
+        let mut config = GeneratorConfig {
+            max_per_category: cli.generate_max,
+            seed: cli.generate_seed,
+            ..Default::default()
+        };
+        if let Some(ref path) = cli.absent_words {
+            let content = std::fs::read_to_string(path).expect("Failed to read absent words file");
+            config.absent_words = content
+                .lines()
+                .map(|l| l.trim().to_lowercase())
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .collect();
+        }
+
+        let suite = generate_suite(&dictionary, &params, &config);
+        let title = cli
+            .dict
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "dictionary".to_string());
+        std::fs::write(out_path, suite_to_markdown(&suite, &title))
+            .expect("Failed to write generated test suite");
+
+        println!("\n=== Generated Test Suite ===");
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for q in &suite.questions {
+            match counts.iter_mut().find(|(c, _)| *c == q.category) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((q.category.clone(), 1)),
+            }
+        }
+        for (category, n) in &counts {
+            println!("  {:<24} {}", category, n);
+        }
+        println!("  {} questions written to {:?}", suite.questions.len(), out_path);
+        return;
+    }
+
     // ── Parse test questions ──────────────────────────────────────
-    let test_content =
-        std::fs::read_to_string(&cli.test).expect("Failed to read test file");
-    let test_suite = parse_test_questions(&test_content);
+    let test_suite = load_test_suite(&cli.test).expect("Failed to read test file");
     println!("Test questions: {}", test_suite.questions.len());
 
     // ── Parse grammar text (optional) ─────────────────────────────
@@ -350,7 +418,9 @@ fn main() {
         println!("Strategy: {:?}", strategy);
         println!();
 
+        let train_start = Instant::now();
         let mut multi = MultiSpace::new(configs, &params, &strategy, build_mode);
+        let train_time = train_start.elapsed();
         multi.print_bridges();
 
         // Print per-space statistics
@@ -404,12 +474,14 @@ fn main() {
 
         // Evaluate
         println!("\n=== Multi-Space Test Results ===\n");
+        let eval_start = Instant::now();
         let report = evaluate_multispace(&multi, &test_suite);
+        let eval_time = eval_start.elapsed();
 
         for result in &report.results {
             let status = if result.correct { "PASS" } else { "FAIL" };
             println!(
-                "[{}] {} \u{2014} {} | expected: {} | actual: {} | dist: {:.4} | connector: {}",
+                "[{}] {} \u{2014} {} | expected: {} | actual: {} | dist: {:.4} | connector: {}{}",
                 status,
                 result.question_id,
                 result.question_text,
@@ -417,6 +489,7 @@ fn main() {
                 result.actual,
                 result.projection_distance.unwrap_or(f64::NAN),
                 result.connector_used.as_deref().unwrap_or("none"),
+                if result.evidence_missing { " | evidence not met" } else { "" },
             );
         }
 
@@ -433,6 +506,34 @@ fn main() {
             "  Total:     {}/{} correct",
             report.total_correct, report.total_questions
         );
+        println!("\n=== Breakdown ===");
+        print!("{}", report.breakdown);
+
+        if let Some(ref path) = cli.output {
+            let spaces = multi
+                .space_order
+                .iter()
+                .map(|name| SpaceSummary::new(name, multi.spaces[name].engine.space()))
+                .collect();
+            let run = EvalRun {
+                test: cli.test.display().to_string(),
+                knowledge: spaces_arg.clone(),
+                build_mode: format!("{:?}", build_mode),
+                ..EvalRun::new(&report, &params, &strategy, spaces, Timings::new(train_time, eval_time))
+            };
+            write_output(&run, cli.format, path);
+        }
+
+        if cli.ablation {
+            println!("\n=== Ablation ===");
+            let base = strategy.ablation.clone();
+            let ablation = run_ablation(&base, Component::ALL, |a| {
+                multi.set_ablation(a);
+                evaluate_multispace(&multi, &test_suite)
+            });
+            multi.set_ablation(&base);
+            finish_ablation(&ablation, cli.ablation_out.as_ref());
+        }
         return;
     }
 
@@ -462,16 +563,39 @@ fn main() {
     engine.set_mode(build_mode);
     println!("Build mode: {:?}", build_mode);
 
+    let train_start = Instant::now();
     if let Some(ref grammar) = grammar {
         engine.train_with_grammar(&dictionary, grammar);
     } else {
         engine.train(&dictionary);
     }
+    let train_time = train_start.elapsed();
     println!();
 
     // ── Space statistics ──────────────────────────────────────────
     print_space_statistics(engine.space(), &dictionary);
 
+    // ── Threshold calibration ─────────────────────────────────────
+    if let Some(ref path) = cli.calibrate {
+        let validation = load_test_suite(path).expect("Failed to read calibration questions");
+        println!("\n=== Threshold Calibration ===");
+        engine.calibrate(&validation);
+    } else if let Some(ref path) = cli.calibration {
+        let content = std::fs::read_to_string(path).expect("Failed to read calibration file");
+        let calibration: Calibration = serde_json::from_str(&content).expect("Failed to parse calibration file");
+        println!(
+            "Calibration from {:?}: yes_threshold {:.4}, no_threshold {:.4}",
+            path, calibration.yes_threshold, calibration.no_threshold
+        );
+        engine.set_calibration(Some(calibration));
+    }
+    if let (Some(path), Some(calibration)) = (&cli.calibration_out, engine.calibration()) {
+        let json = serde_json::to_string_pretty(calibration).expect("Failed to serialize calibration");
+        std::fs::write(path, json).expect("Failed to write calibration");
+        println!("[Calibration written to {:?}]", path);
+    }
+    let params = engine.effective_params();
+
     // ── Space dump ───────────────────────────────────────────────
     if let Some(ref path) = cli.dump_space {
         let json = serde_json::to_string_pretty(engine.space())
@@ -548,12 +672,14 @@ fn main() {
 
     // ── Test results ──────────────────────────────────────────────
     println!("\n=== Test Results ===\n");
+    let eval_start = Instant::now();
     let report = evaluate(&engine, &test_suite, &dictionary, &params, &strategy);
+    let eval_time = eval_start.elapsed();
 
     for result in &report.results {
         let status = if result.correct { "PASS" } else { "FAIL" };
         println!(
-            "[{}] {} \u{2014} {} | expected: {} | actual: {} | dist: {:.4} | connector: {}",
+            "[{}] {} \u{2014} {} | expected: {} | actual: {} | dist: {:.4} | connector: {}{}",
             status,
             result.question_id,
             result.question_text,
@@ -561,6 +687,7 @@ fn main() {
             result.actual,
             result.projection_distance.unwrap_or(f64::NAN),
             result.connector_used.as_deref().unwrap_or("none"),
+            if result.evidence_missing { " | evidence not met" } else { "" },
         );
     }
 
@@ -599,4 +726,82 @@ fn main() {
         "  Total:     {}/{} correct",
         report.total_correct, report.total_questions
     );
+    println!("\n=== Breakdown ===");
+    print!("{}", report.breakdown);
+
+    if let Some(ref path) = cli.output {
+        let knowledge = match (&cli.text, &cli.entities) {
+            (Some(text), _) => text,
+            (None, Some(entities)) if cli.dict == *"dictionaries/dict5.md" => entities,
+            _ => &cli.dict,
+        };
+        let run = EvalRun {
+            test: cli.test.display().to_string(),
+            knowledge: knowledge.display().to_string(),
+            build_mode: format!("{:?}", build_mode),
+            ..EvalRun::new(
+                &report,
+                &params,
+                &strategy,
+                vec![SpaceSummary::new("main", engine.space())],
+                Timings::new(train_time, eval_time),
+            )
+        };
+        write_output(&run, cli.format, path);
+    }
+
+    // ── Ablation ──────────────────────────────────────────────────
+    if cli.ablation {
+        println!("\n=== Ablation ===");
+        // Multi-space components never fire on a single space
+        let components: Vec<Component> =
+            Component::ALL.iter().copied().filter(|c| !c.is_multispace()).collect();
+        let ablation = run_ablation(&strategy.ablation, &components, |a| {
+            let ablated = StrategyConfig { ablation: a.clone(), ..strategy.clone() };
+            evaluate(&engine, &test_suite, &dictionary, &params, &ablated)
+        });
+        finish_ablation(&ablation, cli.ablation_out.as_ref());
+    }
+}
+
+fn write_output(run: &EvalRun, format: OutputFormat, path: &PathBuf) {
+    // Text has no file form: fall back to JSON when only --output is given
+    let format = if format == OutputFormat::Text { OutputFormat::Json } else { format };
+    let mut file = std::fs::File::create(path).expect("Failed to create output file");
+    write_run(run, format, &mut file).expect("Failed to write output file");
+    println!("[{} results written to {:?}]", format!("{:?}", format).to_lowercase(), path);
+}
+
+fn finish_ablation(report: &AblationReport, out: Option<&PathBuf>) {
+    print_ablation_report(report);
+    if let Some(path) = out {
+        let json = serde_json::to_string_pretty(report).expect("Failed to serialize ablation report");
+        std::fs::write(path, json).expect("Failed to write ablation report");
+        println!("[Ablation report written to {:?}]", path);
+    }
+}
+
+fn run_snapshot(cli: &Cli, manifest_path: &PathBuf, params: &EngineParams, strategy: &StrategyConfig) {
+    let manifest = load_manifest(manifest_path).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    println!("=== Regression Snapshot: {} suites from {:?} ===", manifest.suites.len(), manifest_path);
+    let current = record_snapshot(&manifest, params, strategy);
+
+    if cli.update_golden || !cli.golden.exists() {
+        let json = serde_json::to_string_pretty(&current).expect("Failed to serialize snapshot");
+        std::fs::write(&cli.golden, json).expect("Failed to write golden snapshot");
+        println!("\n[Golden snapshot written to {:?}]", cli.golden);
+        return;
+    }
+
+    let content = std::fs::read_to_string(&cli.golden).expect("Failed to read golden snapshot");
+    let golden: Snapshot = serde_json::from_str(&content).expect("Failed to parse golden snapshot");
+    println!("\n=== Diff against {:?} ===", cli.golden);
+    let diff = diff_snapshots(&golden, &current);
+    print_diff(&diff);
+    if diff.failed() {
+        std::process::exit(1);
+    }
 }
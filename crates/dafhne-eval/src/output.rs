@@ -0,0 +1,353 @@
+//! Machine-readable evaluation output (`dafhne-eval --format`).
+//!
+//! - `json`: one `EvalRun` document
+//! - `jsonl`: one `{"type": "question", ..}` line per question, then one
+//!   `{"type": "summary", ..}` line with everything except the questions
+//! - `junit`: JUnit XML, one test case per question, grouped by category
+//! - `csv`: one row per question
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use dafhne_core::*;
+use dafhne_engine::strategy::StrategyConfig;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable tables on stdout.
+    #[default]
+    Text,
+    Json,
+    Jsonl,
+    Junit,
+    Csv,
+}
+
+/// Everything one evaluation run reports.
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalRun {
+    /// Test suite path.
+    pub test: String,
+    /// Dictionary, text or space list the engine was trained on.
+    pub knowledge: String,
+    pub build_mode: String,
+    pub params: EngineParams,
+    pub strategy: StrategyConfig,
+    pub timings: Timings,
+    pub spaces: Vec<SpaceSummary>,
+    pub accuracy: f64,
+    pub honesty: f64,
+    pub fitness: f64,
+    pub total_correct: usize,
+    pub total_questions: usize,
+    pub breakdown: Breakdown,
+    pub questions: Vec<QuestionResult>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Timings {
+    pub train_secs: f64,
+    pub eval_secs: f64,
+}
+
+impl Timings {
+    pub fn new(train: Duration, eval: Duration) -> Self {
+        Self {
+            train_secs: train.as_secs_f64(),
+            eval_secs: eval.as_secs_f64(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpaceSummary {
+    pub name: String,
+    pub words: usize,
+    pub dimensions: usize,
+    pub distance_mean: f64,
+    pub distance_std_dev: f64,
+    pub connectors: Vec<ConnectorSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectorSummary {
+    pub pattern: String,
+    pub frequency: usize,
+    pub magnitude: f64,
+    pub uniformity: f64,
+    /// Dimension with the largest |force_direction| component.
+    pub primary_axis: usize,
+}
+
+impl SpaceSummary {
+    pub fn new(name: &str, space: &GeometricSpace) -> Self {
+        let stats = space.get_distance_stats();
+        let connectors = space
+            .connectors
+            .iter()
+            .map(|c| ConnectorSummary {
+                pattern: c.pattern.join(" "),
+                frequency: c.frequency,
+                magnitude: c.magnitude,
+                uniformity: c.uniformity,
+                primary_axis: c
+                    .force_direction
+                    .iter()
+                    .enumerate()
+                    .max_by(|(_, a), (_, b)| a.abs().partial_cmp(&b.abs()).unwrap_or(std::cmp::Ordering::Equal))
+                    .map_or(0, |(i, _)| i),
+            })
+            .collect();
+        Self {
+            name: name.to_string(),
+            words: space.words.len(),
+            dimensions: space.dimensions,
+            distance_mean: stats.mean,
+            distance_std_dev: stats.std_dev,
+            connectors,
+        }
+    }
+}
+
+impl EvalRun {
+    /// Assemble a run from a report; the caller fills in `test`,
+    /// `knowledge` and `build_mode`.
+    pub fn new(
+        report: &FitnessReport,
+        params: &EngineParams,
+        strategy: &StrategyConfig,
+        spaces: Vec<SpaceSummary>,
+        timings: Timings,
+    ) -> Self {
+        Self {
+            test: String::new(),
+            knowledge: String::new(),
+            build_mode: String::new(),
+            params: params.clone(),
+            strategy: strategy.clone(),
+            timings,
+            spaces,
+            accuracy: report.accuracy,
+            honesty: report.honesty,
+            fitness: report.fitness,
+            total_correct: report.total_correct,
+            total_questions: report.total_questions,
+            breakdown: report.breakdown.clone(),
+            questions: report.results.clone(),
+        }
+    }
+}
+
+/// Write `run` in `format`. `Text` writes nothing (the caller prints tables).
+pub fn write_run(run: &EvalRun, format: OutputFormat, out: &mut dyn Write) -> io::Result<()> {
+    match format {
+        OutputFormat::Text => Ok(()),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, run)?;
+            writeln!(out)
+        }
+        OutputFormat::Jsonl => write_jsonl(run, out),
+        OutputFormat::Junit => write_junit(run, out),
+        OutputFormat::Csv => write_csv(run, out),
+    }
+}
+
+fn write_jsonl(run: &EvalRun, out: &mut dyn Write) -> io::Result<()> {
+    for q in &run.questions {
+        let mut line = serde_json::to_value(q)?;
+        line["type"] = "question".into();
+        writeln!(out, "{}", line)?;
+    }
+    let mut summary = serde_json::to_value(run)?;
+    if let Some(obj) = summary.as_object_mut() {
+        obj.remove("questions");
+        obj.insert("type".to_string(), "summary".into());
+    }
+    writeln!(out, "{}", summary)
+}
+
+fn write_junit(run: &EvalRun, out: &mut dyn Write) -> io::Result<()> {
+    let failures = run.total_questions - run.total_correct;
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuites name="dafhne-eval" tests="{}" failures="{}" time="{:.3}">"#,
+        run.total_questions,
+        failures,
+        run.timings.train_secs + run.timings.eval_secs
+    )?;
+    writeln!(
+        out,
+        r#"  <testsuite name="{}" tests="{}" failures="{}" time="{:.3}">"#,
+        xml_escape(&run.test),
+        run.total_questions,
+        failures,
+        run.timings.eval_secs
+    )?;
+    writeln!(out, "    <properties>")?;
+    for (name, value) in [
+        ("knowledge", run.knowledge.clone()),
+        ("build_mode", run.build_mode.clone()),
+        ("accuracy", format!("{:.4}", run.accuracy)),
+        ("honesty", format!("{:.4}", run.honesty)),
+        ("fitness", format!("{:.4}", run.fitness)),
+        ("train_secs", format!("{:.3}", run.timings.train_secs)),
+    ] {
+        writeln!(out, r#"      <property name="{}" value="{}"/>"#, name, xml_escape(&value))?;
+    }
+    writeln!(out, "    </properties>")?;
+    for r in &run.questions {
+        let classname = if r.category.is_empty() { "uncategorized" } else { &r.category };
+        let name = format!("{}: {}", r.question_id, r.question_text);
+        if r.correct {
+            writeln!(
+                out,
+                r#"    <testcase classname="{}" name="{}"/>"#,
+                xml_escape(classname),
+                xml_escape(&name)
+            )?;
+        } else {
+            let mut message = format!("expected: {} | actual: {}", r.expected, r.actual);
+            if r.evidence_missing {
+                message.push_str(" | evidence not met");
+            }
+            writeln!(
+                out,
+                r#"    <testcase classname="{}" name="{}">"#,
+                xml_escape(classname),
+                xml_escape(&name)
+            )?;
+            writeln!(
+                out,
+                r#"      <failure message="{}">distance: {} | connector: {}</failure>"#,
+                xml_escape(&message),
+                r.projection_distance.map_or("none".to_string(), |d| format!("{:.4}", d)),
+                xml_escape(r.connector_used.as_deref().unwrap_or("none"))
+            )?;
+            writeln!(out, "    </testcase>")?;
+        }
+    }
+    writeln!(out, "  </testsuite>")?;
+    writeln!(out, "</testsuites>")
+}
+
+fn write_csv(run: &EvalRun, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "id,category,question,expected,actual,correct,distance,connector,evidence_missing")?;
+    for r in &run.questions {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{}",
+            csv_field(&r.question_id),
+            csv_field(&r.category),
+            csv_field(&r.question_text),
+            csv_field(&r.expected.to_string()),
+            csv_field(&r.actual.to_string()),
+            r.correct,
+            r.projection_distance.map_or(String::new(), |d| d.to_string()),
+            csv_field(r.connector_used.as_deref().unwrap_or("")),
+            r.evidence_missing,
+        )?;
+    }
+    Ok(())
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_run() -> EvalRun {
+        let question = |id: &str, category: &str, text: &str| TestQuestion {
+            id: id.to_string(),
+            question: text.to_string(),
+            expected: ExpectedAnswer::Yes,
+            chain: String::new(),
+            category: category.to_string(),
+            alternatives: vec![],
+            evidence: None,
+            tags: vec![],
+            difficulty: None,
+        };
+        let suite = TestSuite {
+            questions: vec![
+                question("Q01", "DIRECT", "Is a dog an animal?"),
+                question("Q02", "DIRECT", "Is a \"cat\", an animal?"),
+                question("Q03", "NEGATION", "Is the sun <cold>?"),
+            ],
+        };
+        let result = |q: &TestQuestion, correct: bool| QuestionResult {
+            question_id: q.id.clone(),
+            question_text: q.question.clone(),
+            expected: q.expected.clone(),
+            actual: if correct { Answer::Yes } else { Answer::No },
+            correct,
+            projection_distance: Some(0.25),
+            connector_used: Some("is".to_string()),
+            evidence_missing: false,
+            category: q.category.clone(),
+            elapsed_us: 50,
+        };
+        let report = FitnessReport::from_results(vec![
+            result(&suite.questions[0], true),
+            result(&suite.questions[1], true),
+            result(&suite.questions[2], false),
+        ]);
+        EvalRun::new(
+            &report,
+            &EngineParams::default(),
+            &StrategyConfig::default(),
+            vec![],
+            Timings::default(),
+        )
+    }
+
+    fn render(format: OutputFormat) -> String {
+        let mut buf = Vec::new();
+        write_run(&sample_run(), format, &mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn categories_keep_suite_order() {
+        let run = sample_run();
+        let cats: Vec<(&str, usize, usize)> =
+            run.breakdown.categories.iter().map(|c| (c.category.as_str(), c.total, c.correct)).collect();
+        assert_eq!(cats, vec![("DIRECT", 2, 2), ("NEGATION", 1, 0)]);
+    }
+
+    #[test]
+    fn formats_are_well_formed() {
+        let jsonl = render(OutputFormat::Jsonl);
+        let lines: Vec<serde_json::Value> = jsonl.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["type"], "question");
+        assert_eq!(lines[0]["category"], "DIRECT");
+        assert_eq!(lines[3]["type"], "summary");
+        assert!(lines[3].get("questions").is_none());
+
+        let junit = render(OutputFormat::Junit);
+        assert!(junit.contains(r#"tests="3" failures="1""#));
+        assert!(junit.contains("&lt;cold&gt;"));
+        assert_eq!(junit.matches("<failure ").count(), 1);
+
+        let csv = render(OutputFormat::Csv);
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.contains(r#""Is a ""cat"", an animal?""#));
+    }
+}
@@ -0,0 +1,423 @@
+//! Regression snapshots (golden files).
+//!
+//! A snapshot manifest (TOML) lists the bundled suites, each described with
+//! the same inputs as a `dafhne-eval` run (closed dictionary, grammar,
+//! multi-space, or open mode with a cache). `record_snapshot` answers every
+//! suite with one fixed genome and seed and keeps every answer, distance and
+//! connector. `diff_snapshots` compares a run against the golden file:
+//! right→wrong flips are regressions, wrong→right flips are fixes.
+//!
+//! Open-mode suites read the Ollama cache from disk only, so a snapshot never
+//! depends on a running LLM.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use dafhne_cache::{AssemblerConfig, DictionaryAssembler, DictionaryCache, ManualFileCache, OllamaCache, WiktionaryCache};
+use dafhne_core::*;
+use dafhne_engine::multispace::{MultiSpace, SpaceConfig};
+use dafhne_engine::strategy::StrategyConfig;
+use dafhne_engine::{BuildMode, Engine};
+use dafhne_parser::{load_dictionary, load_test_suite, parse_grammar_text};
+use serde::{Deserialize, Serialize};
+
+use crate::{evaluate, evaluate_multispace, merge_entities};
+
+// ─── Manifest ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SnapshotManifest {
+    /// Overrides the genome's rng_seed for every suite.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(rename = "suite")]
+    pub suites: Vec<SnapshotSuite>,
+}
+
+/// One suite. Exactly one of `dict`, `spaces` or `text` selects the knowledge source.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SnapshotSuite {
+    pub name: String,
+    pub test: PathBuf,
+    #[serde(default)]
+    pub dict: Option<PathBuf>,
+    #[serde(default)]
+    pub grammar: Option<PathBuf>,
+    /// Multi-space mode: "name:dict" pairs.
+    #[serde(default)]
+    pub spaces: Vec<String>,
+    /// Open mode: free text assembled through `cache`.
+    #[serde(default)]
+    pub text: Option<PathBuf>,
+    #[serde(default)]
+    pub entities: Option<PathBuf>,
+    #[serde(default)]
+    pub cache: Option<PathBuf>,
+    /// "manual", "wiktionary" or "ollama" (disk cache only).
+    #[serde(default = "default_cache_type")]
+    pub cache_type: String,
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    #[serde(default)]
+    pub max_words: Option<usize>,
+    /// "forcefield" (default) or "equilibrium".
+    #[serde(default)]
+    pub mode: Option<String>,
+}
+
+fn default_cache_type() -> String {
+    "manual".to_string()
+}
+
+/// Load a manifest; relative paths are resolved against its directory.
+pub fn load_manifest(path: &Path) -> Result<SnapshotManifest, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut manifest: SnapshotManifest =
+        toml::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let base = path.parent().unwrap_or(Path::new("."));
+    let resolve = |p: &mut PathBuf| {
+        if p.is_relative() {
+            *p = base.join(&*p);
+        }
+    };
+    for suite in &mut manifest.suites {
+        resolve(&mut suite.test);
+        for p in [&mut suite.dict, &mut suite.grammar, &mut suite.text, &mut suite.entities, &mut suite.cache]
+            .into_iter()
+            .flatten()
+        {
+            resolve(p);
+        }
+        for pair in &mut suite.spaces {
+            let (name, dict) = pair
+                .split_once(':')
+                .ok_or_else(|| format!("suite {}: invalid space '{}', expected name:path", suite.name, pair))?;
+            let mut dict = PathBuf::from(dict.trim());
+            resolve(&mut dict);
+            *pair = format!("{}:{}", name.trim(), dict.display());
+        }
+        let sources = suite.dict.is_some() as u8 + !suite.spaces.is_empty() as u8 + suite.text.is_some() as u8;
+        if sources != 1 {
+            return Err(format!("suite {}: set exactly one of dict, spaces, text", suite.name));
+        }
+    }
+    Ok(manifest)
+}
+
+// ─── Recording ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub params: EngineParams,
+    pub strategy: StrategyConfig,
+    pub suites: Vec<SuiteSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuiteSnapshot {
+    pub name: String,
+    pub total_correct: usize,
+    pub total_questions: usize,
+    pub answers: Vec<QuestionResult>,
+}
+
+/// Answer every suite in the manifest with `params` / `strategy`.
+pub fn record_snapshot(manifest: &SnapshotManifest, params: &EngineParams, strategy: &StrategyConfig) -> Snapshot {
+    let mut params = params.clone();
+    if let Some(seed) = manifest.seed {
+        params.rng_seed = seed;
+    }
+    let suites = manifest
+        .suites
+        .iter()
+        .map(|suite| {
+            let report = run_suite(suite, &params, strategy);
+            println!(
+                "  {:<28} {:>3}/{:<3} correct  fitness {:.4}",
+                suite.name, report.total_correct, report.total_questions, report.fitness
+            );
+            SuiteSnapshot {
+                name: suite.name.clone(),
+                total_correct: report.total_correct,
+                total_questions: report.total_questions,
+                // Timings vary run to run; keep the golden file stable
+                answers: report
+                    .results
+                    .into_iter()
+                    .map(|answer| QuestionResult { elapsed_us: 0, ..answer })
+                    .collect(),
+            }
+        })
+        .collect();
+    Snapshot {
+        params,
+        strategy: strategy.clone(),
+        suites,
+    }
+}
+
+fn run_suite(suite: &SnapshotSuite, params: &EngineParams, strategy: &StrategyConfig) -> FitnessReport {
+    let test = load_test_suite(&suite.test)
+        .unwrap_or_else(|e| panic!("Failed to read test file {}: {}", suite.test.display(), e));
+    let build_mode = match suite.mode.as_deref() {
+        Some("equilibrium") | Some("eq") => BuildMode::Equilibrium,
+        _ => BuildMode::ForceField,
+    };
+
+    if !suite.spaces.is_empty() {
+        let configs = suite
+            .spaces
+            .iter()
+            .filter_map(|pair| pair.split_once(':'))
+            .map(|(name, dict)| SpaceConfig {
+                name: name.to_string(),
+                dict_path: dict.to_string(),
+            })
+            .collect();
+        let multi = MultiSpace::new(configs, params, strategy, build_mode);
+        return evaluate_multispace(&multi, &test);
+    }
+
+    let dictionary = match (&suite.dict, &suite.text) {
+        (Some(dict), _) => load_dictionary(dict)
+            .unwrap_or_else(|e| panic!("Failed to read dictionary {}: {}", dict.display(), e)),
+        (None, Some(text)) => assemble_open(suite, text),
+        (None, None) => unreachable!("checked by load_manifest"),
+    };
+    let dictionary = match &suite.entities {
+        Some(path) => merge_entities(
+            dictionary,
+            load_dictionary(path).unwrap_or_else(|e| panic!("Failed to read entities {}: {}", path.display(), e)),
+        ),
+        None => dictionary,
+    };
+
+    let mut engine = Engine::with_strategy(params.clone(), strategy.clone());
+    engine.set_quiet(true);
+    engine.set_mode(build_mode);
+    match &suite.grammar {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)
+                .unwrap_or_else(|e| panic!("Failed to read grammar {}: {}", path.display(), e));
+            engine.train_with_grammar(&dictionary, &parse_grammar_text(&content));
+        }
+        None => engine.train(&dictionary),
+    }
+    evaluate(&engine, &test, &dictionary, &engine.effective_params(), strategy)
+}
+
+fn assemble_open(suite: &SnapshotSuite, text_path: &Path) -> Dictionary {
+    let text = std::fs::read_to_string(text_path)
+        .unwrap_or_else(|e| panic!("Failed to read text {}: {}", text_path.display(), e));
+    let cache_path = suite
+        .cache
+        .as_ref()
+        .unwrap_or_else(|| panic!("suite {}: text requires cache", suite.name));
+    let cache: Box<dyn DictionaryCache> = match suite.cache_type.as_str() {
+        "manual" => Box::new(ManualFileCache::load(cache_path).expect("Failed to load manual cache")),
+        "wiktionary" | "wikt" => Box::new(WiktionaryCache::load(cache_path).expect("Failed to load wiktionary cache")),
+        "ollama" => {
+            let ollama = OllamaCache::offline(cache_path).expect("Failed to open Ollama disk cache");
+            ollama.preload_disk_cache();
+            Box::new(ollama)
+        }
+        other => panic!("suite {}: unknown cache type '{}'", suite.name, other),
+    };
+    let config = AssemblerConfig {
+        max_depth: suite.max_depth.unwrap_or(3),
+        max_words: suite.max_words.unwrap_or(5000),
+        ..Default::default()
+    };
+    DictionaryAssembler::new(cache.as_ref(), config).assemble(&text).0
+}
+
+// ─── Diff ───────────────────────────────────────────────────────
+
+/// A question whose answer changed between the golden file and this run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Flip {
+    pub suite: String,
+    pub question_id: String,
+    pub question_text: String,
+    pub expected: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Default)]
+pub struct SnapshotDiff {
+    /// Right → wrong.
+    pub regressions: Vec<Flip>,
+    /// Wrong → right.
+    pub fixes: Vec<Flip>,
+    /// Different answer, same correctness.
+    pub changed: Vec<Flip>,
+    /// Same answer, different distance or connector.
+    pub moved: usize,
+    /// "suite/question" in the golden file but not in this run.
+    pub missing: Vec<String>,
+    /// "suite/question" in this run but not in the golden file.
+    pub added: Vec<String>,
+    /// Parameters or strategy differ from the golden file.
+    pub settings_changed: bool,
+}
+
+impl SnapshotDiff {
+    /// Regressions, or questions that disappeared from the run.
+    pub fn failed(&self) -> bool {
+        !self.regressions.is_empty() || !self.missing.is_empty()
+    }
+}
+
+pub fn diff_snapshots(golden: &Snapshot, current: &Snapshot) -> SnapshotDiff {
+    let mut diff = SnapshotDiff {
+        settings_changed: serde_json::to_value(&golden.params).ok() != serde_json::to_value(&current.params).ok()
+            || serde_json::to_value(&golden.strategy).ok() != serde_json::to_value(&current.strategy).ok(),
+        ..Default::default()
+    };
+
+    let index = |s: &Snapshot| -> HashMap<(String, String), QuestionResult> {
+        s.suites
+            .iter()
+            .flat_map(|suite| suite.answers.iter().map(move |a| ((suite.name.clone(), a.question_id.clone()), a.clone())))
+            .collect()
+    };
+    let now = index(current);
+    let before = index(golden);
+
+    for suite in &golden.suites {
+        for old in &suite.answers {
+            let key = (suite.name.clone(), old.question_id.clone());
+            let Some(new) = now.get(&key) else {
+                diff.missing.push(format!("{}/{}", suite.name, old.question_id));
+                continue;
+            };
+            let flip = Flip {
+                suite: suite.name.clone(),
+                question_id: old.question_id.clone(),
+                question_text: old.question_text.clone(),
+                expected: old.expected.to_string(),
+                before: old.actual.to_string(),
+                after: new.actual.to_string(),
+            };
+            match (old.correct, new.correct) {
+                (true, false) => diff.regressions.push(flip),
+                (false, true) => diff.fixes.push(flip),
+                _ if old.actual != new.actual => diff.changed.push(flip),
+                _ => {
+                    let distance_moved = match (old.projection_distance, new.projection_distance) {
+                        (Some(a), Some(b)) => (a - b).abs() > 1e-9,
+                        (a, b) => a.is_some() != b.is_some(),
+                    };
+                    if distance_moved || old.connector_used != new.connector_used {
+                        diff.moved += 1;
+                    }
+                }
+            }
+        }
+    }
+    for suite in &current.suites {
+        for new in &suite.answers {
+            if !before.contains_key(&(suite.name.clone(), new.question_id.clone())) {
+                diff.added.push(format!("{}/{}", suite.name, new.question_id));
+            }
+        }
+    }
+    diff
+}
+
+pub fn print_diff(diff: &SnapshotDiff) {
+    if diff.settings_changed {
+        println!("  WARNING: parameters or strategy differ from the golden snapshot");
+    }
+    let print_flips = |title: &str, flips: &[Flip]| {
+        if flips.is_empty() {
+            return;
+        }
+        println!("\n  {} ({}):", title, flips.len());
+        for f in flips {
+            println!(
+                "    [{}] {} \u{2014} {} | expected: {} | {} \u{2192} {}",
+                f.suite, f.question_id, f.question_text, f.expected, f.before, f.after
+            );
+        }
+    };
+    print_flips("REGRESSIONS (right \u{2192} wrong)", &diff.regressions);
+    print_flips("Fixes (wrong \u{2192} right)", &diff.fixes);
+    print_flips("Changed answers (same correctness)", &diff.changed);
+    if !diff.missing.is_empty() {
+        println!("\n  Missing from this run ({}): {}", diff.missing.len(), diff.missing.join(", "));
+    }
+    if !diff.added.is_empty() {
+        println!("\n  New questions ({}): {}", diff.added.len(), diff.added.join(", "));
+    }
+    println!(
+        "\n  Summary: {} regressions, {} fixes, {} changed, {} distance/connector changes, {} missing, {} new",
+        diff.regressions.len(),
+        diff.fixes.len(),
+        diff.changed.len(),
+        diff.moved,
+        diff.missing.len(),
+        diff.added.len(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(answers: &[(&str, Answer, bool, f64)]) -> Snapshot {
+        let answers = answers
+            .iter()
+            .map(|(id, actual, correct, dist)| QuestionResult {
+                question_id: id.to_string(),
+                question_text: format!("question {}", id),
+                expected: ExpectedAnswer::Yes,
+                actual: actual.clone(),
+                correct: *correct,
+                projection_distance: Some(*dist),
+                connector_used: None,
+                evidence_missing: false,
+                category: String::new(),
+                elapsed_us: 0,
+            })
+            .collect();
+        Snapshot {
+            params: EngineParams::default(),
+            strategy: StrategyConfig::default(),
+            suites: vec![SuiteSnapshot {
+                name: "dict5".to_string(),
+                total_correct: 0,
+                total_questions: 0,
+                answers,
+            }],
+        }
+    }
+
+    #[test]
+    fn diff_classifies_flips() {
+        let golden = snapshot(&[
+            ("Q01", Answer::Yes, true, 0.1),
+            ("Q02", Answer::No, false, 0.9),
+            ("Q03", Answer::Yes, true, 0.2),
+            ("Q04", Answer::Yes, true, 0.3),
+        ]);
+        let current = snapshot(&[
+            ("Q01", Answer::IDontKnow, false, 0.5),
+            ("Q02", Answer::Yes, true, 0.2),
+            ("Q03", Answer::Yes, true, 0.25),
+            ("Q05", Answer::Yes, true, 0.3),
+        ]);
+        let diff = diff_snapshots(&golden, &current);
+        assert_eq!(diff.regressions.len(), 1);
+        assert_eq!(diff.regressions[0].question_id, "Q01");
+        assert_eq!(diff.fixes[0].question_id, "Q02");
+        assert_eq!(diff.moved, 1);
+        assert_eq!(diff.missing, vec!["dict5/Q04"]);
+        assert_eq!(diff.added, vec!["dict5/Q05"]);
+        assert!(!diff.settings_changed);
+        assert!(diff.failed());
+
+        assert!(!diff_snapshots(&golden, &golden).failed());
+    }
+}
@@ -0,0 +1,332 @@
+//! CMA-ES (μ/μ_w, λ) over the normalized numeric genes.
+//!
+//! The search runs in the [0, 1]^16 cube produced by `optimizer::encode_params`;
+//! samples outside it are clamped before decoding. The initial mean is the
+//! weighted top-μ of the first (random) generation. Categorical genes are not
+//! covariance-adapted: each keeps a probability vector that moves toward the
+//! choices of the top μ every generation.
+
+use std::collections::HashMap;
+
+use dafhne_core::SimpleRng;
+use serde::{Deserialize, Serialize};
+
+use crate::genome::{Genome, ParamRanges};
+use crate::operators::gaussian;
+use crate::optimizer::*;
+
+const SIGMA0: f64 = 0.3;
+const SIGMA_MIN: f64 = 1e-4;
+const SIGMA_MAX: f64 = 1.0;
+/// Learning rate of the categorical distributions.
+const CATEGORY_RATE: f64 = 0.3;
+/// No categorical choice drops below this probability.
+const CATEGORY_FLOOR: f64 = 0.05;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CmaEs {
+    pub lambda: usize,
+    pub sigma: f64,
+    /// None until the first `tell`.
+    pub mean: Option<Vec<f64>>,
+    pub cov: Vec<Vec<f64>>,
+    pub pc: Vec<f64>,
+    pub ps: Vec<f64>,
+    pub category_probs: Vec<Vec<f64>>,
+    /// Number of covariance/step-size updates so far.
+    pub updates: usize,
+    /// Samples from the last `ask` (clamped to the cube), by genome id.
+    pending: HashMap<u64, Vec<f64>>,
+}
+
+/// Strategy constants derived from λ and the dimension (Hansen's defaults).
+struct Constants {
+    mu: usize,
+    weights: Vec<f64>,
+    mueff: f64,
+    cc: f64,
+    cs: f64,
+    c1: f64,
+    cmu: f64,
+    damps: f64,
+    chi_n: f64,
+}
+
+impl Constants {
+    fn new(lambda: usize) -> Self {
+        let n = NUM_GENES as f64;
+        let mu = (lambda / 2).max(1);
+        let raw: Vec<f64> = (0..mu)
+            .map(|i| (mu as f64 + 0.5).ln() - ((i + 1) as f64).ln())
+            .collect();
+        let sum: f64 = raw.iter().sum();
+        let weights: Vec<f64> = raw.iter().map(|w| w / sum).collect();
+        let mueff = 1.0 / weights.iter().map(|w| w * w).sum::<f64>();
+
+        let cc = (4.0 + mueff / n) / (n + 4.0 + 2.0 * mueff / n);
+        let cs = (mueff + 2.0) / (n + mueff + 5.0);
+        let c1 = 2.0 / ((n + 1.3).powi(2) + mueff);
+        let cmu = (1.0 - c1).min(2.0 * (mueff - 2.0 + 1.0 / mueff) / ((n + 2.0).powi(2) + mueff));
+        let damps = 1.0 + 2.0 * (((mueff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + cs;
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        Self { mu, weights, mueff, cc, cs, c1, cmu, damps, chi_n }
+    }
+}
+
+impl CmaEs {
+    pub fn new(lambda: usize) -> Self {
+        let mut cov = vec![vec![0.0; NUM_GENES]; NUM_GENES];
+        for (i, row) in cov.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        Self {
+            lambda: lambda.max(2),
+            sigma: SIGMA0,
+            mean: None,
+            cov,
+            pc: vec![0.0; NUM_GENES],
+            ps: vec![0.0; NUM_GENES],
+            category_probs: CATEGORY_SIZES.iter().map(|&k| vec![1.0 / k as f64; k]).collect(),
+            updates: 0,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Top-μ of `evaluated` by fitness, as (x, categories).
+    fn select(&self, evaluated: &[Genome], ranges: &ParamRanges, mu: usize) -> Vec<(Vec<f64>, [usize; 6])> {
+        let mut scored: Vec<&Genome> = evaluated.iter().filter(|g| g.fitness.is_some()).collect();
+        scored.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap_or(std::cmp::Ordering::Equal));
+        scored
+            .into_iter()
+            .take(mu)
+            .map(|g| {
+                let x = self
+                    .pending
+                    .get(&g.id)
+                    .cloned()
+                    .unwrap_or_else(|| encode_params(&g.params, ranges));
+                (x, encode_categories(g))
+            })
+            .collect()
+    }
+
+    fn update_categories(&mut self, top: &[(Vec<f64>, [usize; 6])], weights: &[f64]) {
+        for (c, probs) in self.category_probs.iter_mut().enumerate() {
+            let mut target = vec![0.0; probs.len()];
+            for ((_, cats), w) in top.iter().zip(weights) {
+                target[cats[c]] += w;
+            }
+            let total: f64 = target.iter().sum();
+            for (p, t) in probs.iter_mut().zip(&target) {
+                *p = ((1.0 - CATEGORY_RATE) * *p + CATEGORY_RATE * t / total).max(CATEGORY_FLOOR);
+            }
+            let norm: f64 = probs.iter().sum();
+            probs.iter_mut().for_each(|p| *p /= norm);
+        }
+    }
+}
+
+impl Optimizer for CmaEs {
+    fn ask(&mut self, n: usize, rng: &mut SimpleRng, id_counter: &mut u64, generation: usize) -> Vec<Genome> {
+        let ranges = ParamRanges::default();
+        let mean = self.mean.clone().unwrap_or_else(|| vec![0.5; NUM_GENES]);
+        let (eigenvalues, basis) = symmetric_eigen(&self.cov);
+        let scale: Vec<f64> = eigenvalues.iter().map(|v| v.max(1e-20).sqrt()).collect();
+
+        self.pending.clear();
+        (0..n)
+            .map(|_| {
+                let z: Vec<f64> = (0..NUM_GENES).map(|i| scale[i] * gaussian(rng)).collect();
+                let x: Vec<f64> = (0..NUM_GENES)
+                    .map(|i| {
+                        let step: f64 = (0..NUM_GENES).map(|j| basis[i][j] * z[j]).sum();
+                        (mean[i] + self.sigma * step).clamp(0.0, 1.0)
+                    })
+                    .collect();
+                let cats: [usize; 6] = std::array::from_fn(|c| sample_index(&self.category_probs[c], rng));
+
+                *id_counter += 1;
+                let genome = decode_genome(&x, &cats, &ranges, *id_counter, generation);
+                self.pending.insert(genome.id, x);
+                genome
+            })
+            .collect()
+    }
+
+    fn tell(&mut self, evaluated: &[Genome]) {
+        let ranges = ParamRanges::default();
+        let k = Constants::new(self.lambda);
+        let top = self.select(evaluated, &ranges, k.mu);
+        if top.is_empty() {
+            return;
+        }
+        // Renormalize when fewer than μ genomes were scored
+        let wsum: f64 = k.weights[..top.len()].iter().sum();
+        let weights: Vec<f64> = k.weights[..top.len()].iter().map(|w| w / wsum).collect();
+
+        let new_mean: Vec<f64> = (0..NUM_GENES)
+            .map(|i| top.iter().zip(&weights).map(|((x, _), w)| w * x[i]).sum())
+            .collect();
+        self.update_categories(&top, &weights);
+        self.pending.clear();
+
+        // The first (random) generation only seeds the mean
+        let Some(old_mean) = self.mean.replace(new_mean.clone()) else {
+            return;
+        };
+
+        let n = NUM_GENES;
+        let sigma = self.sigma;
+        let y_w: Vec<f64> = (0..n).map(|i| (new_mean[i] - old_mean[i]) / sigma).collect();
+
+        // C^{-1/2} y_w = B D^{-1} B^T y_w
+        let (eigenvalues, basis) = symmetric_eigen(&self.cov);
+        let bt_y: Vec<f64> = (0..n).map(|j| (0..n).map(|i| basis[i][j] * y_w[i]).sum::<f64>()).collect();
+        let scaled: Vec<f64> = (0..n).map(|j| bt_y[j] / eigenvalues[j].max(1e-20).sqrt()).collect();
+        let c_inv_sqrt_y: Vec<f64> = (0..n).map(|i| (0..n).map(|j| basis[i][j] * scaled[j]).sum()).collect();
+
+        let ps_rate = (k.cs * (2.0 - k.cs) * k.mueff).sqrt();
+        for (ps, y) in self.ps.iter_mut().zip(&c_inv_sqrt_y) {
+            *ps = (1.0 - k.cs) * *ps + ps_rate * y;
+        }
+        let ps_norm = self.ps.iter().map(|v| v * v).sum::<f64>().sqrt();
+        let g = (self.updates + 1) as i32;
+        let hsig = ps_norm / (1.0 - (1.0 - k.cs).powi(2 * g)).sqrt() / k.chi_n < 1.4 + 2.0 / (n as f64 + 1.0);
+        let hsig_f = if hsig { 1.0 } else { 0.0 };
+
+        let pc_rate = (k.cc * (2.0 - k.cc) * k.mueff).sqrt();
+        for (pc, y) in self.pc.iter_mut().zip(&y_w) {
+            *pc = (1.0 - k.cc) * *pc + hsig_f * pc_rate * y;
+        }
+
+        let ys: Vec<Vec<f64>> = top
+            .iter()
+            .map(|(x, _)| (0..n).map(|i| (x[i] - old_mean[i]) / sigma).collect())
+            .collect();
+        let decay = 1.0 - k.c1 - k.cmu;
+        let correction = (1.0 - hsig_f) * k.cc * (2.0 - k.cc);
+        for i in 0..n {
+            for j in 0..=i {
+                let rank_one = self.pc[i] * self.pc[j] + correction * self.cov[i][j];
+                let rank_mu: f64 = ys.iter().zip(&weights).map(|(y, w)| w * y[i] * y[j]).sum();
+                let v = decay * self.cov[i][j] + k.c1 * rank_one + k.cmu * rank_mu;
+                self.cov[i][j] = v;
+                self.cov[j][i] = v;
+            }
+        }
+
+        self.sigma = (sigma * ((k.cs / k.damps) * (ps_norm / k.chi_n - 1.0)).exp()).clamp(SIGMA_MIN, SIGMA_MAX);
+        self.updates += 1;
+    }
+
+    fn summary(&self) -> String {
+        let (eigenvalues, _) = symmetric_eigen(&self.cov);
+        let max = eigenvalues.iter().cloned().fold(f64::MIN, f64::max);
+        let min = eigenvalues.iter().cloned().fold(f64::MAX, f64::min).max(1e-20);
+        format!(
+            "CMA-ES: sigma={:.4}, axis ratio={:.1}, updates={}",
+            self.sigma,
+            (max / min).sqrt(),
+            self.updates
+        )
+    }
+}
+
+// ─── Linear Algebra ─────────────────────────────────────────────
+
+/// Cyclic Jacobi eigendecomposition of a symmetric matrix.
+/// Returns (eigenvalues, eigenvectors as columns).
+pub fn symmetric_eigen(matrix: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = matrix.len();
+    let mut a: Vec<Vec<f64>> = matrix.to_vec();
+    let mut v = vec![vec![0.0; n]; n];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for _sweep in 0..50 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off < 1e-22 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    let akp = row[p];
+                    let akq = row[q];
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (head, tail) = a.split_at_mut(q);
+                for (apk, aqk) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let vkp = row[p];
+                    let vkq = row[q];
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::population::initialize_population;
+
+    #[test]
+    fn eigen_reconstructs_matrix() {
+        let m = vec![vec![4.0, 1.0, 0.5], vec![1.0, 3.0, 0.2], vec![0.5, 0.2, 2.0]];
+        let (values, vectors) = symmetric_eigen(&m);
+        for i in 0..3 {
+            for j in 0..3 {
+                let r: f64 = (0..3).map(|k| vectors[i][k] * values[k] * vectors[j][k]).sum();
+                assert!((r - m[i][j]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn converges_on_toy_objective() {
+        // Maximize closeness of yes_threshold/no_threshold genes to 0.8
+        let ranges = ParamRanges::default();
+        let score = |g: &Genome| {
+            let x = encode_params(&g.params, &ranges);
+            -((x[6] - 0.8).powi(2) + (x[7] - 0.8).powi(2))
+        };
+        let mut rng = SimpleRng::new(11);
+        let mut ids = 0;
+        let mut population = initialize_population(12, &ranges, &mut rng, 0, &mut ids);
+        let mut cma = CmaEs::new(12);
+        for gen in 0..40 {
+            for g in population.iter_mut() {
+                g.fitness = Some(score(g));
+            }
+            cma.tell(&population);
+            population = cma.ask(12, &mut rng, &mut ids, gen + 1);
+        }
+        let mean = cma.mean.unwrap();
+        assert!((mean[6] - 0.8).abs() < 0.05, "mean[6] = {}", mean[6]);
+        assert!((mean[7] - 0.8).abs() < 0.05, "mean[7] = {}", mean[7]);
+    }
+}
@@ -7,162 +7,153 @@ use dafhne_engine::{BuildMode, Engine};
 use dafhne_engine::multispace::{MultiSpace, SpaceConfig};
 
 use crate::genome::{Genome, MultiSpaceGenome};
+use crate::manifest::{BenchmarkMode, LoadedBenchmark, Scoring};
 
 /// Result of evaluating a single genome.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvalResult {
     pub genome_id: u64,
+    /// Primary (first manifest) benchmark.
     pub primary_report: FitnessReport,
+    /// First secondary benchmark (dict12 in the legacy layout).
     pub cross_report: Option<FitnessReport>,
-    /// Dict18 cross-validation report (single-space, same thresholds).
+    /// Second secondary benchmark (dict18 in the legacy layout).
     #[serde(default)]
     pub cross18_report: Option<FitnessReport>,
-    /// Dual-space ensemble report on dict5 (when grammar provided).
+    /// Dual-space ensemble report on the primary benchmark (mode = "dual").
     /// This is used only for bonus scoring, not as the primary evaluation.
     pub dual_report: Option<FitnessReport>,
+    /// Primary holdout report (same trained engine, questions never used for fitness).
+    #[serde(default)]
+    pub holdout_report: Option<FitnessReport>,
+    /// Build cost of the primary engine: dimensions × learning passes × training
+    /// relations. Deterministic, unlike wall-clock time, so it is safe to cache
+    /// (build-cost objective in Pareto mode).
+    #[serde(default)]
+    pub train_cost: u64,
+    /// Every benchmark that ran, in manifest order.
+    #[serde(default)]
+    pub benchmarks: Vec<BenchmarkResult>,
     pub final_fitness: f64,
 }
 
-/// Evaluate a single genome against dict5 (and optionally dict12 and dict18).
+/// One benchmark's outcome for one genome.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub name: String,
+    pub weight: f64,
+    pub report: FitnessReport,
+    #[serde(default)]
+    pub dual_report: Option<FitnessReport>,
+}
+
+/// Evaluate a single genome against the manifest benchmarks.
 ///
-/// Fitness structure (when grammar is provided):
-///   1. Single-space dict5 → primary_report (thresholds must work for single-space)
-///   2. Single-space dict12 → cross_report (same thresholds, same mode — generalization test)
-///   3. Single-space dict18 → cross18_report (same thresholds — scaling test)
-///   4. Dual-space dict5 → dual_report (ensemble of dict-only + dict+grammar engines)
-///   5. final_fitness = base_fitness + dual_bonus
-///      where base_fitness depends on available cross-validation levels:
-///        - dict5+dict12+dict18: 0.5*primary + 0.3*cross12 + 0.2*cross18
-///        - dict5+dict12:        0.6*primary + 0.4*cross12
-///        - dict5 only:          primary
-///      Overfitting penalty applied when gap > 0.15
-///      and dual_bonus = max(0, dual_fitness - primary_fitness) * 0.15
+/// Fitness structure:
+///   1. Primary benchmark (first in the manifest) on `primary_test` → primary_report.
+///      `primary_test` is the benchmark's suite or the training split of it.
+///   2. Secondary benchmarks, same thresholds, only when primary ≥ scoring.gate
+///      (generalization and scaling tests)
+///   3. Dual-mode benchmarks also score a dict-only + dict+grammar ensemble
+///   4. final_fitness = scoring.combine(weights, fitnesses) + dual_bonus
+///      where combine is the weighted mean of the benchmarks that ran, minus the
+///      optional overfitting penalty when primary - worst secondary > threshold,
+///      and dual_bonus = Σ max(0, dual_fitness - fitness) * benchmark.dual_bonus
+///   5. Primary engine on `holdout` → holdout_report (reported and used to
+///      pick the final genome, never part of final_fitness)
 ///
-/// This structure forces thresholds to generalize across all dictionary sizes.
+/// The legacy dict5/dict12/dict18 layout is the manifest from
+/// `EvalManifest::legacy`: 0.5/0.3/0.2 weights, 0.5 penalty above a 0.15 gap.
 pub fn evaluate_genome(
     genome: &Genome,
-    dict5: &Dictionary,
-    test5: &TestSuite,
-    dict12: Option<&Dictionary>,
-    test12: Option<&TestSuite>,
-    grammar5: Option<&Dictionary>,
-    dict18: Option<&Dictionary>,
-    test18: Option<&TestSuite>,
-    grammar18: Option<&Dictionary>,
+    benchmarks: &[LoadedBenchmark],
+    primary_test: &TestSuite,
+    holdout: Option<&TestSuite>,
+    scoring: &Scoring,
     base_seed: u64,
-    cross_validation_threshold: f64,
 ) -> EvalResult {
     let engine_params = genome.to_engine_params(base_seed);
     let strategy = genome.to_strategy_config();
 
-    // ALWAYS evaluate dict5 with single-space (this is the primary evaluation)
-    let mut engine_dict5 = Engine::with_strategy(engine_params.clone(), strategy.clone());
-    engine_dict5.set_quiet(true);
-    engine_dict5.train(dict5);
-    let primary_report = dafhne_eval::evaluate(&engine_dict5, test5, dict5, &engine_params, &strategy);
-
-    // Dual-space ensemble evaluation on dict5 (when grammar provided)
-    // This uses the already-trained dict5 engine + a new grammar-enhanced engine
-    let dual_report = if let Some(grammar) = grammar5 {
-        let mut engine_gram = Engine::with_strategy(engine_params.clone(), strategy.clone());
-        engine_gram.set_quiet(true);
-        engine_gram.train_with_grammar(dict5, grammar);
-
-        Some(dafhne_eval::evaluate_dual(
-            &engine_dict5, &engine_gram,
-            test5, dict5,
-            &engine_params, &engine_params,
-            &strategy, &strategy,
-        ))
-    } else {
-        None
-    };
-
-    // Cross-validate on dict12 (single-space, same thresholds — generalization test)
-    let cross_report = if primary_report.fitness >= cross_validation_threshold {
-        if let (Some(d12), Some(t12)) = (dict12, test12) {
-            let mut engine12 = Engine::with_strategy(engine_params.clone(), strategy.clone());
-            engine12.set_quiet(true);
-            engine12.train(d12);
-            Some(dafhne_eval::evaluate(&engine12, t12, d12, &engine_params, &strategy))
-        } else {
-            None
+    let mut results: Vec<BenchmarkResult> = Vec::new();
+    let mut holdout_report = None;
+    let mut train_cost = 0;
+
+    for (i, bench) in benchmarks.iter().enumerate() {
+        let is_primary = i == 0;
+        if !is_primary && results[0].report.fitness < scoring.gate {
+            break;
         }
-    } else {
-        None
-    };
-
-    // Cross-validate on dict18 (single-space, same thresholds — scaling test)
-    // Uses grammar18 if provided (dual-space for dict18, same as dict5 gets grammar5)
-    let cross18_report = if primary_report.fitness >= cross_validation_threshold {
-        if let (Some(d18), Some(t18)) = (dict18, test18) {
-            let mut engine18 = Engine::with_strategy(engine_params.clone(), strategy.clone());
-            engine18.set_quiet(true);
-            if let Some(g18) = grammar18 {
-                engine18.train_with_grammar(d18, g18);
-            } else {
-                engine18.train(d18);
-            }
-            Some(dafhne_eval::evaluate(&engine18, t18, d18, &engine_params, &strategy))
-        } else {
-            None
+        let test = if is_primary { primary_test } else { &bench.test };
+        let dict = bench.dictionary();
+
+        // Single mode trains the grammar in; dual mode keeps the scored engine dictionary-only
+        let mut engine = Engine::with_strategy(engine_params.clone(), strategy.clone());
+        engine.set_quiet(true);
+        engine.set_mode(bench.build);
+        match (&bench.grammar, bench.mode) {
+            (Some(grammar), BenchmarkMode::Single) => engine.train_with_grammar(dict, grammar),
+            _ => engine.train(dict),
         }
-    } else {
-        None
-    };
-
-    // Compute final fitness:
-    //   Three-level: 0.5*primary + 0.3*cross12 + 0.2*cross18 (with overfitting penalty)
-    //   Two-level:   0.6*primary + 0.4*cross12 (with overfitting penalty)
-    //   One-level:   primary
-    //   dual_bonus = max(0, dual_fitness - primary_fitness) * 0.15
-    //   final = base + dual_bonus
-    let base_fitness = match (&cross_report, &cross18_report) {
-        (Some(cr12), Some(cr18)) => {
-            let primary = primary_report.fitness;
-            let cross12 = cr12.fitness;
-            let cross18 = cr18.fitness;
-            let base = 0.5 * primary + 0.3 * cross12 + 0.2 * cross18;
-            // Overfitting penalty: penalize if primary >> worst cross score
-            let worst_cross = cross12.min(cross18);
-            let gap = primary - worst_cross;
-            if gap > 0.15 {
-                (base - 0.5 * (gap - 0.15)).max(0.0)
-            } else {
-                base
-            }
+        if is_primary {
+            train_cost = (engine_params.dimensions * engine_params.learning_passes * engine.relation_count()) as u64;
         }
-        (Some(cr), None) => {
-            let primary = primary_report.fitness;
-            let cross = cr.fitness;
-            let base = 0.6 * primary + 0.4 * cross;
-            let gap = primary - cross;
-            if gap > 0.15 {
-                (base - 0.5 * (gap - 0.15)).max(0.0)
-            } else {
-                base
-            }
+        let report = dafhne_eval::evaluate(&engine, test, dict, &engine_params, &strategy);
+
+        if is_primary {
+            holdout_report = holdout
+                .map(|h| dafhne_eval::evaluate(&engine, h, dict, &engine_params, &strategy));
         }
-        _ => primary_report.fitness,
-    };
+
+        let dual_report = match (&bench.grammar, bench.mode) {
+            (Some(grammar), BenchmarkMode::Dual) => {
+                let mut engine_gram = Engine::with_strategy(engine_params.clone(), strategy.clone());
+                engine_gram.set_quiet(true);
+                engine_gram.set_mode(bench.build);
+                engine_gram.train_with_grammar(dict, grammar);
+                Some(dafhne_eval::evaluate_dual(
+                    &engine, &engine_gram,
+                    test, dict,
+                    &engine_params, &engine_params,
+                    &strategy, &strategy,
+                ))
+            }
+            _ => None,
+        };
+
+        results.push(BenchmarkResult {
+            name: bench.name.clone(),
+            weight: bench.weight,
+            report,
+            dual_report,
+        });
+    }
+
+    let scores: Vec<(f64, f64)> = results.iter().map(|r| (r.weight, r.report.fitness)).collect();
+    let base_fitness = scoring.combine(&scores);
 
     // Dual-space bonus: reward ensemble uplift over single-space
-    let dual_bonus = match &dual_report {
-        Some(dr) => {
-            let uplift = dr.fitness - primary_report.fitness;
-            if uplift > 0.0 { uplift * 0.15 } else { 0.0 }
-        }
-        None => 0.0,
-    };
+    let dual_bonus: f64 = results
+        .iter()
+        .zip(benchmarks)
+        .filter_map(|(r, b)| {
+            r.dual_report
+                .as_ref()
+                .map(|dr| (dr.fitness - r.report.fitness).max(0.0) * b.dual_bonus)
+        })
+        .sum();
 
     let final_fitness = base_fitness + dual_bonus;
 
     EvalResult {
         genome_id: genome.id,
-        primary_report,
-        cross_report,
-        cross18_report,
-        dual_report,
+        primary_report: results[0].report.clone(),
+        cross_report: results.get(1).map(|r| r.report.clone()),
+        cross18_report: results.get(2).map(|r| r.report.clone()),
+        dual_report: results[0].dual_report.clone(),
+        holdout_report,
+        train_cost,
+        benchmarks: results,
         final_fitness,
     }
 }
@@ -192,22 +183,30 @@ pub fn build_trained_space(
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiSpaceEvalResult {
     pub genome_id: u64,
+    /// Primary (first manifest) benchmark.
     pub report: FitnessReport,
+    /// Every benchmark that ran, in manifest order.
+    #[serde(default)]
+    pub benchmarks: Vec<BenchmarkResult>,
     pub final_fitness: f64,
 }
 
-/// Evaluate a MultiSpaceGenome by building a full MultiSpace and running the test suite.
+/// Evaluate a MultiSpaceGenome by building a full MultiSpace once and running
+/// every manifest benchmark's test suite against it. Benchmarks combine with
+/// the same weights, gate and gap penalty as single-space evaluation.
 pub fn evaluate_multi_genome(
     genome: &MultiSpaceGenome,
     space_configs: &[(String, PathBuf)],
-    test_suite: &TestSuite,
+    build_mode: BuildMode,
+    benchmarks: &[LoadedBenchmark],
+    scoring: &Scoring,
     base_seed: u64,
 ) -> MultiSpaceEvalResult {
     // Build per-space params map from genome
     let mut space_params: HashMap<String, (EngineParams, dafhne_engine::strategy::StrategyConfig)> =
         HashMap::new();
     for (name, sg) in &genome.spaces {
-        let ep = sg.to_engine_params(base_seed, genome.id, name);
+        let ep = sg.to_engine_params(base_seed, name);
         let sc = sg.to_strategy_config();
         space_params.insert(name.clone(), (ep, sc));
     }
@@ -229,14 +228,28 @@ pub fn evaluate_multi_genome(
         &space_params,
         &default_params,
         &default_strategy,
-        BuildMode::ForceField,
+        build_mode,
     );
 
-    let report = dafhne_eval::evaluate_multispace(&multi, test_suite);
+    let mut results: Vec<BenchmarkResult> = Vec::new();
+    for (i, bench) in benchmarks.iter().enumerate() {
+        if i > 0 && results[0].report.fitness < scoring.gate {
+            break;
+        }
+        results.push(BenchmarkResult {
+            name: bench.name.clone(),
+            weight: bench.weight,
+            report: dafhne_eval::evaluate_multispace(&multi, &bench.test),
+            dual_report: None,
+        });
+    }
+
+    let scores: Vec<(f64, f64)> = results.iter().map(|r| (r.weight, r.report.fitness)).collect();
 
     MultiSpaceEvalResult {
         genome_id: genome.id,
-        final_fitness: report.fitness,
-        report,
+        final_fitness: scoring.combine(&scores),
+        report: results[0].report.clone(),
+        benchmarks: results,
     }
 }
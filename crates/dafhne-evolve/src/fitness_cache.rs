@@ -0,0 +1,345 @@
+//! Persistent fitness cache.
+//!
+//! Every evaluation retrains several engines, yet elites survive unchanged and
+//! crossover regularly reproduces a genome that already exists. Results are
+//! therefore stored under a content key:
+//! - the genome's parameters and strategy choices (the RNG seed is derived
+//!   from those, see `Genome::to_engine_params`)
+//! - the base seed, scoring rules, and every benchmark's dictionary, grammar
+//!   and test-suite content, including the current train/holdout split
+//!
+//! Two genomes with the same genes share one evaluation, within a generation,
+//! across generations, across `resume`, and across separate runs on the same
+//! benchmarks. The cache file is append-only JSONL (`{"key": .., "result": ..}`).
+//! Engine code changes are invisible to the key: delete the file (or bump
+//! `CACHE_VERSION`) after changing how engines train or answer.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use dafhne_core::{Dictionary, EngineParams, TestSuite};
+use dafhne_engine::BuildMode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::genome::{Genome, MultiSpaceGenome};
+use crate::manifest::{LoadedBenchmark, Scoring};
+
+/// Mixed into every key; bump to invalidate old cache files.
+pub const CACHE_VERSION: u64 = 4;
+
+// ─── Content Hashing ────────────────────────────────────────────
+
+/// FNV-1a, 64 bit. Stable across runs and Rust versions, unlike `DefaultHasher`.
+#[derive(Debug, Clone, Copy)]
+pub struct ContentHasher(u64);
+
+impl Default for ContentHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl ContentHasher {
+    pub fn bytes(&mut self, data: &[u8]) -> &mut Self {
+        for b in data {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+        // Length terminator so ("ab", "c") and ("a", "bc") differ
+        self.0 ^= data.len() as u64;
+        self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        self
+    }
+
+    pub fn str(&mut self, s: &str) -> &mut Self {
+        self.bytes(s.as_bytes())
+    }
+
+    pub fn u64(&mut self, v: u64) -> &mut Self {
+        self.bytes(&v.to_le_bytes())
+    }
+
+    pub fn f64(&mut self, v: f64) -> &mut Self {
+        self.u64(v.to_bits())
+    }
+
+    /// Hash the JSON form of a serializable value.
+    pub fn json<T: Serialize>(&mut self, value: &T) -> &mut Self {
+        let json = serde_json::to_string(value).expect("Failed to serialize cache key input");
+        self.str(&json)
+    }
+
+    pub fn dictionary(&mut self, dict: &Dictionary) -> &mut Self {
+        self.json(&dict.entries)
+    }
+
+    pub fn test_suite(&mut self, suite: &TestSuite) -> &mut Self {
+        self.u64(suite.questions.len() as u64);
+        for q in &suite.questions {
+            self.str(&q.id)
+                .str(&q.question)
+                .json(&q.expected)
+                .str(&q.chain)
+                .str(&q.category)
+                .json(&q.alternatives)
+                .json(&q.evidence)
+                .json(&q.tags)
+                .json(&q.difficulty);
+        }
+        self
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Genes only: the effective seed is derived from the genes, so the stored one adds nothing.
+fn hash_params(h: &mut ContentHasher, params: &EngineParams) {
+    let mut p = params.clone();
+    p.rng_seed = 0;
+    h.json(&p);
+}
+
+/// Everything an evaluation depends on besides the genome.
+pub fn benchmark_fingerprint(
+    benchmarks: &[LoadedBenchmark],
+    primary_test: &TestSuite,
+    holdout: Option<&TestSuite>,
+    scoring: &Scoring,
+    base_seed: u64,
+) -> u64 {
+    let mut h = ContentHasher::default();
+    h.u64(CACHE_VERSION).u64(base_seed).f64(scoring.gate).json(&scoring.penalty);
+    for (i, b) in benchmarks.iter().enumerate() {
+        h.str(&b.name)
+            .f64(b.weight)
+            .json(&b.mode)
+            .str(&format!("{:?}", b.build))
+            .f64(b.dual_bonus);
+        match &b.dict {
+            Some(dict) => h.dictionary(dict),
+            None => h.str(""),
+        };
+        match &b.grammar {
+            Some(grammar) => h.dictionary(grammar),
+            None => h.str(""),
+        };
+        h.test_suite(if i == 0 { primary_test } else { &b.test });
+    }
+    match holdout {
+        Some(suite) => h.test_suite(suite),
+        None => h.str(""),
+    };
+    h.finish()
+}
+
+/// Cache key of a single-space genome under `fingerprint`.
+pub fn genome_key(fingerprint: u64, genome: &Genome) -> String {
+    let mut h = ContentHasher::default();
+    h.u64(fingerprint);
+    hash_params(&mut h, &genome.params);
+    h.json(&genome.to_strategy_config());
+    format!("{:016x}", h.finish())
+}
+
+/// Multi-space counterpart of `benchmark_fingerprint`: adds the space
+/// dictionaries (file content) and the build mode.
+pub fn multi_fingerprint(
+    benchmarks: &[LoadedBenchmark],
+    space_configs: &[(String, PathBuf)],
+    build_mode: BuildMode,
+    scoring: &Scoring,
+    base_seed: u64,
+) -> u64 {
+    let mut h = ContentHasher::default();
+    h.u64(benchmark_fingerprint(benchmarks, &benchmarks[0].test, None, scoring, base_seed))
+        .str(&format!("{:?}", build_mode));
+    for (name, path) in space_configs {
+        h.str(name).bytes(&std::fs::read(path).unwrap_or_default());
+    }
+    h.finish()
+}
+
+/// Cache key of a multi-space genome: per-space genes in `space_order`.
+pub fn multi_genome_key(fingerprint: u64, genome: &MultiSpaceGenome) -> String {
+    let mut h = ContentHasher::default();
+    h.u64(fingerprint);
+    for name in &genome.space_order {
+        h.str(name);
+        if let Some(sg) = genome.spaces.get(name) {
+            hash_params(&mut h, &sg.params);
+            h.json(&sg.to_strategy_config());
+        }
+    }
+    format!("{:016x}", h.finish())
+}
+
+// ─── Cache Store ────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct CacheLine<R> {
+    key: String,
+    result: R,
+}
+
+/// In-memory map of evaluation results, mirrored to an append-only JSONL file
+/// when a path is given.
+pub struct FitnessCache<R> {
+    path: Option<PathBuf>,
+    entries: HashMap<String, R>,
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl<R: Clone + Serialize + DeserializeOwned> FitnessCache<R> {
+    /// Load `path` if it exists. Unreadable lines (e.g. a run killed mid-write) are skipped.
+    pub fn open(path: Option<&Path>) -> Self {
+        let mut entries = HashMap::new();
+        if let Some(file) = path.and_then(|p| std::fs::File::open(p).ok()) {
+            for line in BufReader::new(file).lines().map_while(Result::ok) {
+                if let Ok(entry) = serde_json::from_str::<CacheLine<R>>(&line) {
+                    entries.insert(entry.key, entry.result);
+                }
+            }
+        }
+        if let Some(p) = path {
+            eprintln!("Fitness cache: {} entries from {}", entries.len(), p.display());
+        }
+        Self {
+            path: path.map(Path::to_path_buf),
+            entries,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&R> {
+        self.entries.get(key)
+    }
+
+    /// Store new results and append them to the cache file.
+    pub fn insert_all(&mut self, fresh: Vec<(String, R)>) {
+        if let Some(path) = &self.path {
+            if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir).expect("Failed to create fitness cache dir");
+            }
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .expect("Failed to open fitness cache");
+            for (key, result) in &fresh {
+                let line = serde_json::json!({ "key": key, "result": result });
+                writeln!(file, "{}", line).expect("Failed to write fitness cache");
+            }
+        }
+        self.entries.extend(fresh);
+    }
+
+    /// Resolve one result per key: cached ones are reused, each distinct
+    /// missing key is evaluated once by `evaluate` (which may run in parallel).
+    /// Returns results aligned with `keys`.
+    pub fn resolve<T: Sync>(
+        &mut self,
+        keys: &[String],
+        items: &[T],
+        evaluate: impl Fn(&[(&String, &T)]) -> Vec<R>,
+    ) -> Vec<R> {
+        let mut missing: Vec<(&String, &T)> = Vec::new();
+        let mut seen: HashSet<&String> = HashSet::new();
+        for (key, item) in keys.iter().zip(items) {
+            if self.entries.contains_key(key) || !seen.insert(key) {
+                self.hits += 1;
+            } else {
+                self.misses += 1;
+                missing.push((key, item));
+            }
+        }
+        let fresh = evaluate(&missing);
+        let fresh: Vec<(String, R)> = missing.iter().map(|(k, _)| (*k).clone()).zip(fresh).collect();
+        self.insert_all(fresh);
+        keys.iter().map(|k| self.entries[k].clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::population::initialize_population;
+    use crate::genome::ParamRanges;
+    use dafhne_core::SimpleRng;
+
+    #[test]
+    fn key_ignores_id_but_not_genes() {
+        let mut rng = SimpleRng::new(9);
+        let mut ids = 0;
+        let pop = initialize_population(2, &ParamRanges::default(), &mut rng, 0, &mut ids);
+
+        let mut twin = pop[0].clone();
+        twin.id = 999;
+        assert_eq!(genome_key(1, &pop[0]), genome_key(1, &twin));
+        assert_ne!(genome_key(1, &pop[0]), genome_key(2, &pop[0]));
+
+        twin.params.yes_threshold += 0.01;
+        assert_ne!(genome_key(1, &pop[0]), genome_key(1, &twin));
+    }
+
+    #[test]
+    fn shared_key_means_shared_seed() {
+        let mut rng = SimpleRng::new(9);
+        let mut ids = 0;
+        let pop = initialize_population(1, &ParamRanges::default(), &mut rng, 0, &mut ids);
+
+        let mut twin = pop[0].clone();
+        twin.id = 999;
+        assert_eq!(pop[0].to_engine_params(42).rng_seed, twin.to_engine_params(42).rng_seed);
+
+        twin.params.yes_threshold += 0.01;
+        assert_ne!(pop[0].to_engine_params(42).rng_seed, twin.to_engine_params(42).rng_seed);
+
+        let multi = MultiSpaceGenome::from_genome(&pop[0], &["math".to_string(), "grammar".to_string()]);
+        let mut multi_twin = multi.clone();
+        multi_twin.id = 999;
+        assert_eq!(multi_genome_key(1, &multi), multi_genome_key(1, &multi_twin));
+        let (a, b) = (&multi.spaces["math"], &multi_twin.spaces["math"]);
+        assert_eq!(a.to_engine_params(42, "math").rng_seed, b.to_engine_params(42, "math").rng_seed);
+        assert_ne!(a.to_engine_params(42, "math").rng_seed, a.to_engine_params(42, "grammar").rng_seed);
+    }
+
+    #[test]
+    fn persists_and_dedupes() {
+        let path = std::env::temp_dir().join(format!("dafhne_fitness_cache_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let keys: Vec<String> = ["a", "b", "a"].iter().map(|s| s.to_string()).collect();
+
+        let mut cache: FitnessCache<f64> = FitnessCache::open(Some(&path));
+        let calls = std::cell::Cell::new(0);
+        let results = cache.resolve(&keys, &[1.0, 2.0, 3.0], |todo| {
+            calls.set(calls.get() + todo.len());
+            todo.iter().map(|(_, v)| **v * 10.0).collect()
+        });
+        assert_eq!(results, vec![10.0, 20.0, 10.0]);
+        assert_eq!(calls.get(), 2);
+
+        let mut reopened: FitnessCache<f64> = FitnessCache::open(Some(&path));
+        assert_eq!(reopened.len(), 2);
+        let results = reopened.resolve(&keys, &[0.0, 0.0, 0.0], |todo| {
+            assert!(todo.is_empty());
+            vec![]
+        });
+        assert_eq!(results, vec![10.0, 20.0, 10.0]);
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use dafhne_core::EngineParams;
 
+use crate::fitness_cache::ContentHasher;
+
 // Import strategy types from dafhne-engine (the canonical source)
 pub use dafhne_engine::strategy::{
     ConnectorDetection, ForceFunction, MultiConnectorHandling, NegationModel,
@@ -77,15 +79,26 @@ pub struct Genome {
     pub fitness: Option<f64>,
     pub primary_fitness: Option<f64>,
     pub cross_fitness: Option<f64>,
+    #[serde(default)]
+    pub holdout_fitness: Option<f64>,
+}
+
+/// RNG seed offset derived from the genes alone, so two genomes with the same
+/// genes (an elite and its clone, or a crossover twin) train identically and
+/// can share one fitness-cache entry.
+fn gene_seed(params: &EngineParams, strategy: &StrategyConfig) -> u64 {
+    let mut p = params.clone();
+    p.rng_seed = 0;
+    ContentHasher::default().json(&p).json(strategy).finish()
 }
 
 impl Genome {
-    /// Build EngineParams from this genome with a unique per-genome RNG seed.
+    /// Build EngineParams from this genome with an RNG seed derived from its genes.
     pub fn to_engine_params(&self, base_seed: u64) -> EngineParams {
         let mut p = self.params.clone();
         p.rng_seed = base_seed
             .wrapping_mul(6364136223846793005)
-            .wrapping_add(self.id);
+            .wrapping_add(gene_seed(&self.params, &self.to_strategy_config()));
         p
     }
 
@@ -98,6 +111,7 @@ impl Genome {
             multi_connector: self.multi_connector,
             negation_model: self.negation_model,
             use_connector_axis: self.use_connector_axis,
+            ablation: Default::default(),
         }
     }
 }
@@ -118,15 +132,15 @@ pub struct SpaceGenome {
 }
 
 impl SpaceGenome {
-    /// Build EngineParams with a unique RNG seed per genome AND per space.
-    pub fn to_engine_params(&self, base_seed: u64, genome_id: u64, space_name: &str) -> EngineParams {
+    /// Build EngineParams with an RNG seed derived from this space's genes AND its name.
+    pub fn to_engine_params(&self, base_seed: u64, space_name: &str) -> EngineParams {
         let mut p = self.params.clone();
         let space_hash = space_name
             .bytes()
             .fold(0u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64));
         p.rng_seed = base_seed
             .wrapping_mul(6364136223846793005)
-            .wrapping_add(genome_id)
+            .wrapping_add(gene_seed(&self.params, &self.to_strategy_config()))
             .wrapping_add(space_hash);
         p
     }
@@ -140,6 +154,7 @@ impl SpaceGenome {
             multi_connector: self.multi_connector,
             negation_model: self.negation_model,
             use_connector_axis: self.use_connector_axis,
+            ablation: Default::default(),
         }
     }
 
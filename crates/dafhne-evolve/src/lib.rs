@@ -1,11 +1,19 @@
 pub mod analysis;
+pub mod cmaes;
 pub mod fitness;
+pub mod fitness_cache;
 pub mod genome;
 pub mod lineage;
+pub mod manifest;
 pub mod operators;
+pub mod optimizer;
+pub mod pareto;
 pub mod population;
 pub mod reporting;
 pub mod runner;
+pub mod space_diff;
+pub mod splits;
+pub mod tpe;
 
 pub use genome::{Genome, MultiSpaceGenome, SpaceGenome};
 pub use runner::{evolve, evolve_multi, resume, resume_multi, EvolutionConfig, MultiSpaceEvolutionConfig};
@@ -1,15 +1,18 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::{Parser, Subcommand};
 use dafhne_core::Comprehend;
 use dafhne_engine::multispace::{MultiSpace, SpaceConfig};
+use dafhne_engine::projection::{project, to_html, to_svg, ProjectionMethod, ProjectionOptions};
 use dafhne_engine::{BuildMode, Engine};
 use dafhne_evolve::analysis::run_full_analysis;
 use dafhne_evolve::fitness::build_trained_space;
 use dafhne_evolve::genome::{Genome, MultiSpaceGenome};
+use dafhne_evolve::optimizer::OptimizerKind;
+use dafhne_evolve::space_diff::{diff_spaces, print_space_diff, DiffOptions};
 use dafhne_evolve::runner::{evolve, evolve_multi, resume, resume_multi, EvolutionConfig, MultiSpaceEvolutionConfig};
-use dafhne_parser::{load_dictionary, parse_test_questions};
+use dafhne_parser::{load_dictionary, load_test_suite};
 
 #[derive(Parser)]
 #[command(name = "dafhne-evolve", about = "Evolutionary self-improvement for DAFHNE")]
@@ -22,10 +25,36 @@ struct Cli {
 enum Commands {
     /// Run the evolutionary algorithm (single-space)
     Run {
+        /// Evaluation manifest (TOML) listing benchmarks, weights and penalties.
+        /// Replaces the --dict5/--dict12/--dict18 flags.
+        #[arg(long, conflicts_with_all = ["dict5", "test5", "dict12", "test12", "grammar5", "dict18", "test18", "grammar18"])]
+        manifest: Option<PathBuf>,
+        #[arg(long, required_unless_present = "manifest")]
+        dict5: Option<PathBuf>,
+        #[arg(long, required_unless_present = "manifest")]
+        test5: Option<PathBuf>,
+        /// Holdout questions for dict5: reported per generation and used to
+        /// pick the final genome, never part of fitness
         #[arg(long)]
-        dict5: PathBuf,
+        holdout5: Option<PathBuf>,
+        /// K-fold cross-validation over --test5: the last fold is held out
+        /// for selection, the others rotate through training (0 = off)
+        #[arg(long, default_value = "0")]
+        folds: usize,
+        /// NSGA-II Pareto selection over accuracy, honesty, build cost and
+        /// dimensions (front reported per generation in STATUS.md)
+        #[arg(long, conflicts_with = "optimizer")]
+        pareto: bool,
+        /// Search algorithm: genetic algorithm, CMA-ES, or TPE surrogate
+        #[arg(long, value_enum, default_value = "ga")]
+        optimizer: OptimizerKind,
+        /// Fitness cache shared across generations, resumes and runs
+        /// (keyed by genes plus benchmark content)
+        #[arg(long, default_value = "fitness_cache.jsonl")]
+        fitness_cache: PathBuf,
+        /// Keep the fitness cache in memory only
         #[arg(long)]
-        test5: PathBuf,
+        no_fitness_cache: bool,
         #[arg(long)]
         dict12: Option<PathBuf>,
         #[arg(long)]
@@ -60,6 +89,51 @@ enum Commands {
         #[arg(long)]
         dict5: PathBuf,
     },
+    /// Export a 2-D/3-D projection of a generation's best space
+    Visualize {
+        /// Path to generation directory (e.g., results/gen_015/)
+        path: PathBuf,
+        /// Dictionary for rebuilding the space
+        #[arg(long)]
+        dict: PathBuf,
+        /// Projection: pca or tsne
+        #[arg(long, default_value = "pca")]
+        method: ProjectionMethod,
+        /// Output dimensions (2 or 3)
+        #[arg(long, default_value = "2")]
+        dims: usize,
+        /// t-SNE perplexity (effective neighbours per word)
+        #[arg(long, default_value = "10")]
+        perplexity: f64,
+        /// t-SNE gradient steps
+        #[arg(long, default_value = "1000")]
+        iterations: usize,
+        /// Words to highlight, comma-separated (e.g., dog,cat,animal)
+        #[arg(long, value_delimiter = ',')]
+        highlight: Vec<String>,
+        /// Output file; .svg, .html or .json
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Compare two space dumps (dafhne-eval --dump-space, gen_NNN/space_dump.json)
+    DiffSpaces {
+        /// Old space dump
+        old: PathBuf,
+        /// New space dump
+        new: PathBuf,
+        /// Neighbours per word for the neighbourhood overlap
+        #[arg(long, default_value = "5")]
+        k: usize,
+        /// k-means clusters per space (0 = sqrt(words / 2))
+        #[arg(long, default_value = "0")]
+        clusters: usize,
+        /// Rows shown per table
+        #[arg(long, default_value = "20")]
+        top: usize,
+        /// Write the full diff as JSON
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
     /// Run the best genome from results on a specific dictionary
     RunBest {
         /// Path to results directory
@@ -79,16 +153,26 @@ enum Commands {
 
     /// Run multi-space evolution with per-space parameters
     RunMulti {
+        /// Evaluation manifest (TOML) with a [multispace] section and one or
+        /// more benchmarks. Replaces --spaces/--test.
+        #[arg(long, conflicts_with_all = ["spaces", "test"])]
+        manifest: Option<PathBuf>,
         /// Space definitions: comma-separated name:path pairs
         /// e.g., "content:dictionaries/dict5.md,math:dictionaries/dict_math5.md,..."
-        #[arg(long)]
-        spaces: String,
+        #[arg(long, required_unless_present = "manifest")]
+        spaces: Option<String>,
         /// Path to unified test file
-        #[arg(long)]
-        test: PathBuf,
+        #[arg(long, required_unless_present = "manifest")]
+        test: Option<PathBuf>,
         /// Optional: path to seed genome JSON (bootstrap from existing best)
         #[arg(long)]
         seed_genome: Option<PathBuf>,
+        /// Fitness cache shared across generations, resumes and runs
+        #[arg(long, default_value = "fitness_cache_multi.jsonl")]
+        fitness_cache: PathBuf,
+        /// Keep the fitness cache in memory only
+        #[arg(long)]
+        no_fitness_cache: bool,
         #[arg(long, default_value = "40")]
         population: usize,
         #[arg(long, default_value = "30")]
@@ -123,8 +207,15 @@ fn main() {
     let cli = Cli::parse();
     match cli.command {
         Commands::Run {
+            manifest,
             dict5,
             test5,
+            holdout5,
+            folds,
+            pareto,
+            optimizer,
+            fitness_cache,
+            no_fitness_cache,
             dict12,
             test12,
             grammar5,
@@ -148,8 +239,14 @@ fn main() {
                 strategy_mutation_rate: 0.1,
                 cross_validation_threshold: 0.4,
                 base_seed: seed,
-                dict5_path: dict5,
-                dict5_test_path: test5,
+                manifest_path: manifest,
+                dict5_path: dict5.unwrap_or_default(),
+                dict5_test_path: test5.unwrap_or_default(),
+                dict5_holdout_path: holdout5,
+                folds,
+                pareto,
+                optimizer,
+                fitness_cache_path: (!no_fitness_cache).then_some(fitness_cache),
                 dict12_path: dict12,
                 dict12_test_path: test12,
                 grammar5_path: grammar5,
@@ -164,11 +261,47 @@ fn main() {
                 best.id,
                 best.fitness.unwrap_or(0.0)
             );
+            if let Some(holdout) = best.holdout_fitness {
+                println!("Selected by holdout score: {:.4} (saved to best_holdout_genome.json)", holdout);
+            }
             println!("Parameters: {:?}", best.params);
         }
         Commands::Analyze { path, dict5 } => {
             cmd_analyze(&path, &dict5);
         }
+        Commands::Visualize {
+            path,
+            dict,
+            method,
+            dims,
+            perplexity,
+            iterations,
+            highlight,
+            output,
+        } => {
+            let options = ProjectionOptions { method, dims, perplexity, iterations };
+            cmd_visualize(&path, &dict, &options, &highlight, &output);
+        }
+        Commands::DiffSpaces {
+            old,
+            new,
+            k,
+            clusters,
+            top,
+            output,
+        } => {
+            let load = |path: &PathBuf| -> dafhne_core::GeometricSpace {
+                let json = std::fs::read_to_string(path).expect("Failed to read space dump");
+                serde_json::from_str(&json).expect("Failed to parse space dump")
+            };
+            let diff = diff_spaces(&load(&old), &load(&new), &DiffOptions { k, clusters });
+            print_space_diff(&diff, top);
+            if let Some(path) = output {
+                let json = serde_json::to_string_pretty(&diff).expect("Failed to serialize space diff");
+                std::fs::write(&path, json).expect("Failed to write space diff");
+                println!("\n[Space diff written to {:?}]", path);
+            }
+        }
         Commands::RunBest {
             results,
             dict,
@@ -188,9 +321,12 @@ fn main() {
         // ── Multi-Space Commands ──
 
         Commands::RunMulti {
+            manifest,
             spaces,
             test,
             seed_genome,
+            fitness_cache,
+            no_fitness_cache,
             population,
             generations,
             results,
@@ -198,7 +334,7 @@ fn main() {
             elitism,
             tournament,
         } => {
-            let space_configs = parse_spaces_arg(&spaces);
+            let space_configs = spaces.as_deref().map(parse_spaces_arg).unwrap_or_default();
             let config = MultiSpaceEvolutionConfig {
                 population_size: population,
                 generations,
@@ -210,8 +346,10 @@ fn main() {
                 base_seed: seed,
                 results_dir: results,
                 space_configs,
-                test_path: test,
+                test_path: test.unwrap_or_default(),
+                manifest_path: manifest,
                 seed_genome_path: seed_genome,
+                fitness_cache_path: (!no_fitness_cache).then_some(fitness_cache),
             };
             let best = evolve_multi(&config);
             println!(
@@ -284,6 +422,40 @@ fn cmd_analyze(gen_path: &PathBuf, dict5_path: &PathBuf) {
     run_full_analysis(&space);
 }
 
+fn cmd_visualize(gen_path: &Path, dict_path: &Path, options: &ProjectionOptions, highlight: &[String], output: &Path) {
+    let best_json =
+        std::fs::read_to_string(gen_path.join("best_genome.json")).expect("Failed to read best_genome.json");
+    let best: Genome = serde_json::from_str(&best_json).expect("Failed to parse best_genome.json");
+
+    let dictionary = load_dictionary(dict_path).expect("Failed to read dictionary");
+    let space = build_trained_space(&best, &dictionary, None, 42);
+    let projection = project(&space, options);
+
+    let contents = match output.extension().and_then(|e| e.to_str()) {
+        Some("svg") => to_svg(&projection, highlight),
+        Some("html") | Some("htm") => to_html(&projection, highlight),
+        Some("json") => serde_json::to_string_pretty(&projection).expect("Failed to serialize projection"),
+        _ => {
+            eprintln!("Unknown output format for {} (expected .svg, .html or .json)", output.display());
+            std::process::exit(1);
+        }
+    };
+    std::fs::write(output, contents).expect("Failed to write projection");
+    println!(
+        "Genome {}: {} projection of {} words ({}-D) written to {}",
+        best.id,
+        projection.method,
+        projection.points.len(),
+        projection.dims,
+        output.display()
+    );
+    if !projection.variance_explained.is_empty() {
+        let shares: Vec<String> =
+            projection.variance_explained.iter().map(|v| format!("{:.1}%", v * 100.0)).collect();
+        println!("Variance explained: {}", shares.join(" + "));
+    }
+}
+
 fn cmd_run_best(results_dir: &PathBuf, dict_path: &PathBuf, test_path: &PathBuf) {
     // Find the highest-numbered gen_NNN directory
     let gen_dir = find_latest_gen_dir(results_dir);
@@ -297,8 +469,7 @@ fn cmd_run_best(results_dir: &PathBuf, dict_path: &PathBuf, test_path: &PathBuf)
 
     // Load dictionary and test
     let dictionary = load_dictionary(dict_path).expect("Failed to read dictionary");
-    let test_content = std::fs::read_to_string(test_path).expect("Failed to read test file");
-    let test_suite = parse_test_questions(&test_content);
+    let test_suite = load_test_suite(test_path).expect("Failed to read test file");
 
     // Build engine and evaluate
     let engine_params = best.to_engine_params(42);
@@ -328,6 +499,8 @@ fn cmd_run_best(results_dir: &PathBuf, dict_path: &PathBuf, test_path: &PathBuf)
     println!("  Accuracy: {:.4}", report.accuracy);
     println!("  Honesty:  {:.4}", report.honesty);
     println!("  FITNESS:  {:.4}", report.fitness);
+    println!("\n=== Breakdown ===");
+    print!("{}", report.breakdown);
 }
 
 fn cmd_run_multi_best(results_dir: &PathBuf, spaces_arg: &str, test_path: &PathBuf) {
@@ -352,7 +525,7 @@ fn cmd_run_multi_best(results_dir: &PathBuf, spaces_arg: &str, test_path: &PathB
     let mut space_params: HashMap<String, (dafhne_core::EngineParams, dafhne_engine::strategy::StrategyConfig)> =
         HashMap::new();
     for (name, sg) in &best.spaces {
-        let ep = sg.to_engine_params(42, best.id, name);
+        let ep = sg.to_engine_params(42, name);
         let sc = sg.to_strategy_config();
         space_params.insert(name.clone(), (ep, sc));
     }
@@ -378,8 +551,7 @@ fn cmd_run_multi_best(results_dir: &PathBuf, spaces_arg: &str, test_path: &PathB
     );
 
     // Load test suite and evaluate
-    let test_content = std::fs::read_to_string(test_path).expect("Failed to read test file");
-    let test_suite = parse_test_questions(&test_content);
+    let test_suite = load_test_suite(test_path).expect("Failed to read test file");
 
     println!("=== Test Results ===\n");
     let report = dafhne_eval::evaluate_multispace(&multi, &test_suite);
@@ -401,6 +573,8 @@ fn cmd_run_multi_best(results_dir: &PathBuf, spaces_arg: &str, test_path: &PathB
     println!("  Honesty:  {:.4}", report.honesty);
     println!("  FITNESS:  {:.4}", report.fitness);
     println!("  Score:    {}/{}", report.total_correct, report.total_questions);
+    println!("\n=== Breakdown ===");
+    print!("{}", report.breakdown);
 }
 
 /// Find the highest-numbered gen_NNN directory in results.
@@ -0,0 +1,448 @@
+//! Evaluation manifest: which benchmarks a genome is scored on, and how.
+//!
+//! ```toml
+//! gate = 0.4                          # secondary benchmarks run only above this primary fitness
+//!
+//! [penalty]                           # optional overfitting-gap penalty
+//! threshold = 0.15                    # allowed primary - worst-secondary gap
+//! weight = 0.5                        # fitness lost per unit of gap beyond it
+//!
+//! [[benchmark]]                       # the first benchmark is the primary
+//! name = "dict5"
+//! dict = "dict5.md"
+//! test = "dict5_test.md"
+//! grammar = "grammar5.md"
+//! mode = "dual"                       # single (default) | dual
+//! weight = 0.5
+//!
+//! [[benchmark]]
+//! name = "dict18"
+//! dict = "dict18.md"
+//! grammar = "grammar18.md"            # single mode: trained together with the dictionary
+//! test = "dict18_test.md"
+//! build = "equilibrium"               # forcefield (default) | equilibrium
+//! weight = 0.2
+//!
+//! [multispace]                        # multi-space evolution only
+//! build = "forcefield"
+//! [[multispace.space]]
+//! name = "content"
+//! dict = "dict5.md"
+//! ```
+//!
+//! Relative paths resolve against the manifest's directory. The legacy
+//! `--dict5/--dict12/--dict18` and `--spaces/--test` flags are translated into
+//! an equivalent manifest, weights and penalty included.
+
+use std::path::{Path, PathBuf};
+
+use dafhne_core::{Dictionary, TestSuite};
+use dafhne_engine::BuildMode;
+use dafhne_parser::{load_dictionary, load_test_suite, parse_grammar_text};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvalManifest {
+    /// Primary fitness needed before secondary benchmarks are evaluated
+    /// (default: the evolution config's cross_validation_threshold).
+    #[serde(default)]
+    pub gate: Option<f64>,
+    #[serde(default)]
+    pub penalty: Option<GapPenalty>,
+    #[serde(default, rename = "benchmark")]
+    pub benchmarks: Vec<Benchmark>,
+    #[serde(default)]
+    pub multispace: Option<MultiSpaceSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Benchmark {
+    pub name: String,
+    /// Dictionary to train on (single-space only; multi-space uses `[multispace]`).
+    #[serde(default)]
+    pub dict: Option<PathBuf>,
+    #[serde(default)]
+    pub grammar: Option<PathBuf>,
+    pub test: PathBuf,
+    /// Holdout questions (primary benchmark only; see `splits`).
+    #[serde(default)]
+    pub holdout: Option<PathBuf>,
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    #[serde(default)]
+    pub mode: BenchmarkMode,
+    /// "forcefield" (default) or "equilibrium".
+    #[serde(default)]
+    pub build: Option<String>,
+    /// Share of dual-space uplift added to fitness (dual mode only).
+    #[serde(default = "default_dual_bonus")]
+    pub dual_bonus: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BenchmarkMode {
+    /// One engine; trained with the grammar when one is given.
+    #[default]
+    Single,
+    /// Score the dictionary-only engine, plus a dict-only + dict+grammar
+    /// ensemble whose uplift earns a bonus.
+    Dual,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GapPenalty {
+    #[serde(default = "default_gap_threshold")]
+    pub threshold: f64,
+    #[serde(default = "default_gap_weight")]
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiSpaceSpec {
+    #[serde(default)]
+    pub build: Option<String>,
+    #[serde(default, rename = "space")]
+    pub spaces: Vec<ManifestSpace>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestSpace {
+    pub name: String,
+    pub dict: PathBuf,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+fn default_dual_bonus() -> f64 {
+    0.15
+}
+
+fn default_gap_threshold() -> f64 {
+    0.15
+}
+
+fn default_gap_weight() -> f64 {
+    0.5
+}
+
+impl Benchmark {
+    pub fn new(name: &str, dict: Option<&Path>, test: &Path, weight: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            dict: dict.map(Path::to_path_buf),
+            grammar: None,
+            test: test.to_path_buf(),
+            holdout: None,
+            weight,
+            mode: BenchmarkMode::Single,
+            build: None,
+            dual_bonus: default_dual_bonus(),
+        }
+    }
+}
+
+/// "forcefield" / "equilibrium" (same spellings as dafhne-eval's --mode).
+pub fn parse_build_mode(mode: Option<&str>) -> BuildMode {
+    match mode.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("forcefield") | Some("force-field") | Some("ff") => BuildMode::ForceField,
+        Some("equilibrium") | Some("eq") => BuildMode::Equilibrium,
+        Some(other) => panic!("Unknown build mode in manifest: '{}'", other),
+    }
+}
+
+impl EvalManifest {
+    /// Read a manifest and resolve its paths against the manifest's directory.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read manifest {:?}: {}", path, e))?;
+        let mut manifest: EvalManifest =
+            toml::from_str(&content).map_err(|e| format!("invalid manifest {:?}: {}", path, e))?;
+        let base = path.parent().unwrap_or(Path::new("."));
+        manifest.resolve_paths(base);
+        Ok(manifest)
+    }
+
+    fn resolve_paths(&mut self, base: &Path) {
+        let resolve = |p: &mut PathBuf| {
+            if p.is_relative() {
+                *p = base.join(&*p);
+            }
+        };
+        for b in &mut self.benchmarks {
+            for p in [&mut b.dict, &mut b.grammar, &mut b.holdout].into_iter().flatten() {
+                resolve(p);
+            }
+            resolve(&mut b.test);
+        }
+        if let Some(ms) = &mut self.multispace {
+            for s in &mut ms.spaces {
+                resolve(&mut s.dict);
+            }
+        }
+    }
+
+    /// Manifest equivalent of the legacy dict5/dict12/dict18 flags:
+    /// weights 0.5/0.3/0.2 (or 0.6/0.4 without dict18), gap penalty 0.5 above 0.15,
+    /// grammar5 as a dual-space bonus and grammar18 trained into dict18.
+    pub fn legacy(
+        dict5: &Path,
+        test5: &Path,
+        grammar5: Option<&Path>,
+        dict12: Option<(&Path, &Path)>,
+        dict18: Option<(&Path, &Path)>,
+        grammar18: Option<&Path>,
+    ) -> Self {
+        let (w5, w12, w18) = match (dict12, dict18) {
+            (Some(_), Some(_)) => (0.5, 0.3, 0.2),
+            (Some(_), None) | (None, Some(_)) => (0.6, 0.4, 0.4),
+            (None, None) => (1.0, 0.0, 0.0),
+        };
+
+        let mut primary = Benchmark::new("dict5", Some(dict5), test5, w5);
+        if let Some(g) = grammar5 {
+            primary.grammar = Some(g.to_path_buf());
+            primary.mode = BenchmarkMode::Dual;
+        }
+        let mut benchmarks = vec![primary];
+        if let Some((d, t)) = dict12 {
+            benchmarks.push(Benchmark::new("dict12", Some(d), t, w12));
+        }
+        if let Some((d, t)) = dict18 {
+            let mut b = Benchmark::new("dict18", Some(d), t, w18);
+            b.grammar = grammar18.map(Path::to_path_buf);
+            benchmarks.push(b);
+        }
+
+        Self {
+            gate: None,
+            penalty: Some(GapPenalty {
+                threshold: default_gap_threshold(),
+                weight: default_gap_weight(),
+            }),
+            benchmarks,
+            multispace: None,
+        }
+    }
+
+    /// Manifest equivalent of the legacy `--spaces` / `--test` flags.
+    pub fn legacy_multi(spaces: &[(String, PathBuf)], test: &Path) -> Self {
+        Self {
+            gate: None,
+            penalty: None,
+            benchmarks: vec![Benchmark::new("unified", None, test, 1.0)],
+            multispace: Some(MultiSpaceSpec {
+                build: None,
+                spaces: spaces
+                    .iter()
+                    .map(|(name, dict)| ManifestSpace {
+                        name: name.clone(),
+                        dict: dict.clone(),
+                    })
+                    .collect(),
+            }),
+        }
+    }
+
+    pub fn primary(&self) -> &Benchmark {
+        self.benchmarks
+            .first()
+            .expect("Evaluation manifest has no [[benchmark]] entries")
+    }
+
+    /// (name, dict) pairs of the `[multispace]` section, in order.
+    pub fn space_configs(&self) -> Vec<(String, PathBuf)> {
+        self.multispace
+            .as_ref()
+            .map(|ms| ms.spaces.iter().map(|s| (s.name.clone(), s.dict.clone())).collect())
+            .unwrap_or_default()
+    }
+}
+
+// ─── Loaded Benchmarks ──────────────────────────────────────────
+
+/// A benchmark with its files read, ready to evaluate genomes against.
+#[derive(Debug, Clone)]
+pub struct LoadedBenchmark {
+    pub name: String,
+    pub dict: Option<Dictionary>,
+    pub grammar: Option<Dictionary>,
+    pub test: TestSuite,
+    pub weight: f64,
+    pub mode: BenchmarkMode,
+    pub build: BuildMode,
+    pub dual_bonus: f64,
+}
+
+impl LoadedBenchmark {
+    pub fn load(b: &Benchmark) -> Self {
+        let dict = b.dict.as_ref().map(|p| {
+            load_dictionary(p).unwrap_or_else(|e| panic!("Failed to read {} dictionary {:?}: {}", b.name, p, e))
+        });
+        let grammar = b.grammar.as_ref().map(|p| {
+            let content = std::fs::read_to_string(p)
+                .unwrap_or_else(|e| panic!("Failed to read {} grammar {:?}: {}", b.name, p, e));
+            let g = parse_grammar_text(&content);
+            eprintln!("Loaded {} grammar: {} sections", b.name, g.entries.len());
+            g
+        });
+        let test = load_test_suite(&b.test)
+            .unwrap_or_else(|e| panic!("Failed to read {} test suite {:?}: {}", b.name, b.test, e));
+
+        if b.mode == BenchmarkMode::Dual && grammar.is_none() {
+            panic!("Benchmark {} uses mode = \"dual\" but has no grammar", b.name);
+        }
+
+        Self {
+            name: b.name.clone(),
+            dict,
+            grammar,
+            test,
+            weight: b.weight,
+            mode: b.mode,
+            build: parse_build_mode(b.build.as_deref()),
+            dual_bonus: b.dual_bonus,
+        }
+    }
+
+    /// Dictionary for single-space evaluation (required there).
+    pub fn dictionary(&self) -> &Dictionary {
+        self.dict
+            .as_ref()
+            .unwrap_or_else(|| panic!("Benchmark {} has no dict (required for single-space evolution)", self.name))
+    }
+}
+
+/// Scoring rules shared by single- and multi-space evaluation.
+#[derive(Debug, Clone)]
+pub struct Scoring {
+    pub gate: f64,
+    pub penalty: Option<GapPenalty>,
+}
+
+impl Scoring {
+    pub fn from_manifest(manifest: &EvalManifest, default_gate: f64) -> Self {
+        Self {
+            gate: manifest.gate.unwrap_or(default_gate),
+            penalty: manifest.penalty.clone(),
+        }
+    }
+
+    /// Combine (weight, fitness) of every benchmark that ran; the first is the primary.
+    /// Weighted mean over the benchmarks that ran, minus the gap penalty when the
+    /// primary outscores the worst secondary by more than the threshold.
+    pub fn combine(&self, scores: &[(f64, f64)]) -> f64 {
+        let Some(&(_, primary)) = scores.first() else {
+            return 0.0;
+        };
+        let total_weight: f64 = scores.iter().map(|(w, _)| w).sum();
+        if scores.len() == 1 || total_weight <= 0.0 {
+            return primary;
+        }
+
+        let base = scores.iter().map(|(w, f)| w * f).sum::<f64>() / total_weight;
+        let worst = scores[1..].iter().map(|(_, f)| *f).fold(f64::INFINITY, f64::min);
+        match &self.penalty {
+            Some(p) if primary - worst > p.threshold => {
+                (base - p.weight * (primary - worst - p.threshold)).max(0.0)
+            }
+            _ => base,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest_with_defaults() {
+        let manifest: EvalManifest = toml::from_str(
+            r#"
+[penalty]
+threshold = 0.1
+
+[[benchmark]]
+name = "dict5"
+dict = "dict5.md"
+test = "dict5_test.md"
+grammar = "grammar5.md"
+mode = "dual"
+
+[[benchmark]]
+name = "domain"
+dict = "domain.md"
+test = "domain_test.toml"
+weight = 0.25
+build = "equilibrium"
+
+[multispace]
+[[multispace.space]]
+name = "content"
+dict = "dict5.md"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.benchmarks.len(), 2);
+        assert_eq!(manifest.primary().mode, BenchmarkMode::Dual);
+        assert_eq!(manifest.primary().weight, 1.0);
+        assert_eq!(manifest.benchmarks[1].weight, 0.25);
+        assert_eq!(parse_build_mode(manifest.benchmarks[1].build.as_deref()), BuildMode::Equilibrium);
+        let penalty = manifest.penalty.as_ref().unwrap();
+        assert_eq!((penalty.threshold, penalty.weight), (0.1, 0.5));
+        assert_eq!(manifest.space_configs()[0].0, "content");
+
+        let mut resolved = manifest.clone();
+        resolved.resolve_paths(Path::new("/data"));
+        assert_eq!(resolved.benchmarks[1].test, PathBuf::from("/data/domain_test.toml"));
+    }
+
+    #[test]
+    fn shipped_manifests_load() {
+        let single = EvalManifest::load(Path::new("../../dictionaries/evolve_manifest.toml")).unwrap();
+        let names: Vec<&str> = single.benchmarks.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["dict5", "dict12", "dict18"]);
+        assert!(single.benchmarks[1].test.ends_with("dictionaries/dict12_test.toml"));
+
+        let multi = EvalManifest::load(Path::new("../../dictionaries/multispace_manifest.toml")).unwrap();
+        assert_eq!(multi.space_configs().len(), 5);
+        assert!(multi.benchmarks.iter().all(|b| b.dict.is_none()));
+
+        // Every file they reference is there and parses
+        for b in single.benchmarks.iter().chain(&multi.benchmarks) {
+            let loaded = LoadedBenchmark::load(b);
+            assert!(!loaded.test.questions.is_empty(), "{} has no questions", b.name);
+        }
+        for (name, dict) in multi.space_configs() {
+            let d = load_dictionary(&dict).unwrap_or_else(|e| panic!("space {} dict {:?}: {}", name, dict, e));
+            assert!(!d.entries.is_empty(), "space {} dict {:?} is empty", name, dict);
+        }
+    }
+
+    #[test]
+    fn legacy_scoring_matches_fixed_weights() {
+        let m = EvalManifest::legacy(
+            Path::new("d5"),
+            Path::new("t5"),
+            None,
+            Some((Path::new("d12"), Path::new("t12"))),
+            Some((Path::new("d18"), Path::new("t18"))),
+            None,
+        );
+        let weights: Vec<f64> = m.benchmarks.iter().map(|b| b.weight).collect();
+        assert_eq!(weights, vec![0.5, 0.3, 0.2]);
+
+        let scoring = Scoring::from_manifest(&m, 0.4);
+        // No gap: plain weighted mean
+        let f = scoring.combine(&[(0.5, 0.8), (0.3, 0.7), (0.2, 0.7)]);
+        assert!((f - (0.4 + 0.21 + 0.14)).abs() < 1e-12);
+        // Gap 0.4 > 0.15 → minus 0.5 * 0.25
+        let f = scoring.combine(&[(0.5, 0.9), (0.3, 0.5), (0.2, 0.6)]);
+        assert!((f - (0.45 + 0.15 + 0.12 - 0.125)).abs() < 1e-12);
+        // Primary alone (gated): primary fitness
+        assert_eq!(scoring.combine(&[(0.5, 0.3)]), 0.3);
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use dafhne_core::{EngineParams, SimpleRng};
 
 use crate::genome::*;
+use crate::pareto::ParetoRank;
 
 // ─── Shared Helpers ─────────────────────────────────────────────
 
@@ -157,6 +158,7 @@ pub fn mutate(
     child.fitness = None;
     child.primary_fitness = None;
     child.cross_fitness = None;
+    child.holdout_fitness = None;
 
     mutate_params(&mut child.params, ranges, mutation_rate, rng);
 
@@ -190,6 +192,7 @@ pub fn crossover(
     child.fitness = None;
     child.primary_fitness = None;
     child.cross_fitness = None;
+    child.holdout_fitness = None;
 
     crossover_params(&mut child.params, &parent_b.params, rng);
 
@@ -227,6 +230,28 @@ pub fn tournament_select<'a>(
     best.unwrap()
 }
 
+/// Crowded tournament (NSGA-II): lower front wins, ties go to the less crowded.
+/// `ranks` is aligned with `population`.
+pub fn crowded_tournament_select<'a>(
+    population: &'a [Genome],
+    ranks: &[ParetoRank],
+    tournament_size: usize,
+    rng: &mut SimpleRng,
+) -> &'a Genome {
+    let mut best: Option<usize> = None;
+    for _ in 0..tournament_size {
+        let idx = rng.next_u64() as usize % population.len();
+        let better = match best {
+            None => true,
+            Some(b) => ranks[idx].better_than(&ranks[b]),
+        };
+        if better {
+            best = Some(idx);
+        }
+    }
+    &population[best.unwrap()]
+}
+
 // ─── Multi-Space Mutation ───────────────────────────────────────
 
 /// Mutate a multi-space genome: independently mutate each space's params and strategies.
@@ -326,7 +351,7 @@ fn mutate_usize(value: usize, range: (usize, usize), rng: &mut SimpleRng) -> usi
 }
 
 /// Box-Muller transform: convert two uniform [0,1] samples into a standard normal sample.
-fn gaussian(rng: &mut SimpleRng) -> f64 {
+pub(crate) fn gaussian(rng: &mut SimpleRng) -> f64 {
     let u1 = rng.next_f64().max(1e-10);
     let u2 = rng.next_f64();
     (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
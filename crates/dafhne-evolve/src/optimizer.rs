@@ -0,0 +1,241 @@
+//! Pluggable optimizers for single-space evolution.
+//!
+//! The GA (tournament + crossover + mutation in `operators.rs`) needs large
+//! populations to move continuous thresholds. The alternatives here work on
+//! an encoded genome instead:
+//! - 16 numeric genes (every EngineParams field except the seed), each
+//!   normalized to [0, 1] over `ParamRanges`; integers are rounded on decode
+//! - 6 categorical genes (the Tier 2 strategy enums and `use_connector_axis`)
+//!
+//! Every optimizer is ask/tell: `ask` proposes a generation, the runner
+//! evaluates it, `tell` feeds the fitness back. State is serializable so
+//! checkpoint/resume carries it.
+
+use dafhne_core::{EngineParams, SimpleRng};
+use serde::{Deserialize, Serialize};
+
+use crate::cmaes::CmaEs;
+use crate::genome::*;
+use crate::tpe::Tpe;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+pub enum OptimizerKind {
+    /// Genetic algorithm: tournament selection, crossover, mutation.
+    #[default]
+    Ga,
+    /// CMA-ES over the numeric genes; categoricals follow an adaptive distribution.
+    CmaEs,
+    /// Tree-structured Parzen estimator (surrogate model, numeric and categorical).
+    Tpe,
+}
+
+/// Ask/tell interface shared by the non-GA optimizers.
+pub trait Optimizer {
+    /// Propose `n` new genomes for `generation`. Advances `id_counter` once per genome.
+    fn ask(&mut self, n: usize, rng: &mut SimpleRng, id_counter: &mut u64, generation: usize) -> Vec<Genome>;
+    /// Feed back evaluated genomes (fitness set).
+    fn tell(&mut self, evaluated: &[Genome]);
+    /// One-line state summary for logs and STATUS.md.
+    fn summary(&self) -> String;
+}
+
+/// Serializable optimizer state (stored in checkpoint.json).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OptimizerState {
+    CmaEs(CmaEs),
+    Tpe(Tpe),
+}
+
+impl OptimizerState {
+    /// Fresh state for `kind`; `None` for the GA, which lives in the runner.
+    pub fn new(kind: OptimizerKind, population_size: usize) -> Option<Self> {
+        match kind {
+            OptimizerKind::Ga => None,
+            OptimizerKind::CmaEs => Some(OptimizerState::CmaEs(CmaEs::new(population_size))),
+            OptimizerKind::Tpe => Some(OptimizerState::Tpe(Tpe::new())),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OptimizerState::CmaEs(_) => "CMA-ES",
+            OptimizerState::Tpe(_) => "TPE",
+        }
+    }
+
+    fn inner(&mut self) -> &mut dyn Optimizer {
+        match self {
+            OptimizerState::CmaEs(o) => o,
+            OptimizerState::Tpe(o) => o,
+        }
+    }
+}
+
+impl Optimizer for OptimizerState {
+    fn ask(&mut self, n: usize, rng: &mut SimpleRng, id_counter: &mut u64, generation: usize) -> Vec<Genome> {
+        self.inner().ask(n, rng, id_counter, generation)
+    }
+
+    fn tell(&mut self, evaluated: &[Genome]) {
+        self.inner().tell(evaluated)
+    }
+
+    fn summary(&self) -> String {
+        match self {
+            OptimizerState::CmaEs(o) => o.summary(),
+            OptimizerState::Tpe(o) => o.summary(),
+        }
+    }
+}
+
+// ─── Genome Encoding ────────────────────────────────────────────
+
+/// Number of numeric genes.
+pub const NUM_GENES: usize = 16;
+
+/// Cardinality of each categorical gene:
+/// force_function, connector_detection, space_init, multi_connector, negation_model, use_connector_axis.
+pub const CATEGORY_SIZES: [usize; 6] = [
+    ForceFunction::ALL.len(),
+    ConnectorDetection::ALL.len(),
+    SpaceInitialization::ALL.len(),
+    MultiConnectorHandling::ALL.len(),
+    NegationModel::ALL.len(),
+    2,
+];
+
+fn norm(v: f64, (lo, hi): (f64, f64)) -> f64 {
+    if hi > lo { ((v - lo) / (hi - lo)).clamp(0.0, 1.0) } else { 0.0 }
+}
+
+fn norm_usize(v: usize, (lo, hi): (usize, usize)) -> f64 {
+    norm(v as f64, (lo as f64, hi as f64))
+}
+
+fn denorm(x: f64, (lo, hi): (f64, f64)) -> f64 {
+    lo + x.clamp(0.0, 1.0) * (hi - lo)
+}
+
+fn denorm_usize(x: f64, (lo, hi): (usize, usize)) -> usize {
+    denorm(x, (lo as f64, hi as f64)).round() as usize
+}
+
+/// Numeric genes of `params`, each in [0, 1].
+pub fn encode_params(params: &EngineParams, r: &ParamRanges) -> Vec<f64> {
+    vec![
+        norm_usize(params.dimensions, r.dimensions),
+        norm_usize(params.learning_passes, r.learning_passes),
+        norm(params.force_magnitude, r.force_magnitude),
+        norm(params.force_decay, r.force_decay),
+        norm_usize(params.connector_min_frequency, r.connector_min_frequency),
+        norm_usize(params.connector_max_length, r.connector_max_length),
+        norm(params.yes_threshold, r.yes_threshold),
+        norm(params.no_threshold, r.no_threshold),
+        norm(params.negation_inversion, r.negation_inversion),
+        norm(params.bidirectional_force, r.bidirectional_force),
+        norm(params.grammar_weight, r.grammar_weight),
+        norm_usize(params.max_follow_per_hop, r.max_follow_per_hop),
+        norm_usize(params.max_chain_hops, r.max_chain_hops),
+        norm(params.weighted_distance_alpha, r.weighted_distance_alpha),
+        norm_usize(params.uniformity_num_buckets, r.uniformity_num_buckets),
+        norm(params.uniformity_threshold, r.uniformity_threshold),
+    ]
+}
+
+/// Inverse of `encode_params`: values are clamped to [0, 1] and integers rounded.
+pub fn decode_params(x: &[f64], r: &ParamRanges) -> EngineParams {
+    EngineParams {
+        dimensions: denorm_usize(x[0], r.dimensions),
+        learning_passes: denorm_usize(x[1], r.learning_passes),
+        force_magnitude: denorm(x[2], r.force_magnitude),
+        force_decay: denorm(x[3], r.force_decay),
+        connector_min_frequency: denorm_usize(x[4], r.connector_min_frequency),
+        connector_max_length: denorm_usize(x[5], r.connector_max_length),
+        yes_threshold: denorm(x[6], r.yes_threshold),
+        no_threshold: denorm(x[7], r.no_threshold),
+        negation_inversion: denorm(x[8], r.negation_inversion),
+        bidirectional_force: denorm(x[9], r.bidirectional_force),
+        grammar_weight: denorm(x[10], r.grammar_weight),
+        max_follow_per_hop: denorm_usize(x[11], r.max_follow_per_hop),
+        max_chain_hops: denorm_usize(x[12], r.max_chain_hops),
+        weighted_distance_alpha: denorm(x[13], r.weighted_distance_alpha),
+        uniformity_num_buckets: denorm_usize(x[14], r.uniformity_num_buckets),
+        uniformity_threshold: denorm(x[15], r.uniformity_threshold),
+        rng_seed: 0, // overridden by to_engine_params()
+    }
+}
+
+fn index_of<T: PartialEq>(all: &[T], v: &T) -> usize {
+    all.iter().position(|x| x == v).unwrap_or(0)
+}
+
+/// Categorical genes of `genome` as indices into each enum's `ALL`.
+pub fn encode_categories(genome: &Genome) -> [usize; 6] {
+    [
+        index_of(ForceFunction::ALL, &genome.force_function),
+        index_of(ConnectorDetection::ALL, &genome.connector_detection),
+        index_of(SpaceInitialization::ALL, &genome.space_init),
+        index_of(MultiConnectorHandling::ALL, &genome.multi_connector),
+        index_of(NegationModel::ALL, &genome.negation_model),
+        genome.use_connector_axis as usize,
+    ]
+}
+
+/// Build a fresh (unevaluated) genome from encoded genes.
+pub fn decode_genome(x: &[f64], cats: &[usize; 6], ranges: &ParamRanges, id: u64, generation: usize) -> Genome {
+    Genome {
+        params: decode_params(x, ranges),
+        force_function: ForceFunction::ALL[cats[0]],
+        connector_detection: ConnectorDetection::ALL[cats[1]],
+        space_init: SpaceInitialization::ALL[cats[2]],
+        multi_connector: MultiConnectorHandling::ALL[cats[3]],
+        negation_model: NegationModel::ALL[cats[4]],
+        use_connector_axis: cats[5] == 1,
+        id,
+        generation,
+        parent_ids: vec![],
+        fitness: None,
+        primary_fitness: None,
+        cross_fitness: None,
+        holdout_fitness: None,
+    }
+}
+
+/// Sample an index from (unnormalized) probabilities.
+pub(crate) fn sample_index(probs: &[f64], rng: &mut SimpleRng) -> usize {
+    let total: f64 = probs.iter().sum();
+    let mut r = rng.next_f64() * total;
+    for (i, p) in probs.iter().enumerate() {
+        if r < *p {
+            return i;
+        }
+        r -= p;
+    }
+    probs.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::population::initialize_population;
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let ranges = ParamRanges::default();
+        let mut rng = SimpleRng::new(3);
+        let mut ids = 0;
+        for genome in initialize_population(10, &ranges, &mut rng, 0, &mut ids) {
+            let x = encode_params(&genome.params, &ranges);
+            assert_eq!(x.len(), NUM_GENES);
+            assert!(x.iter().all(|v| (0.0..=1.0).contains(v)));
+
+            let cats = encode_categories(&genome);
+            let back = decode_genome(&x, &cats, &ranges, genome.id, 0);
+            assert_eq!(back.params.dimensions, genome.params.dimensions);
+            assert_eq!(back.params.max_chain_hops, genome.params.max_chain_hops);
+            assert!((back.params.yes_threshold - genome.params.yes_threshold).abs() < 1e-12);
+            assert_eq!(back.negation_model, genome.negation_model);
+            assert_eq!(back.use_connector_axis, genome.use_connector_axis);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! NSGA-II multi-objective selection.
+//!
+//! Scalar fitness folds accuracy and honesty into one number, so a genome
+//! that trades a little accuracy for far fewer wrong "I don't know" answers
+//! loses to one that does the opposite. In Pareto mode selection instead
+//! keeps the non-dominated set over four objectives:
+//! - accuracy (maximize)
+//! - honesty (maximize)
+//! - build cost on dict5: dimensions × learning passes × training relations
+//!   (minimize; a deterministic stand-in for training time, which varies
+//!   run to run and would otherwise be frozen into the fitness cache)
+//! - dimensions (minimize)
+//!
+//! Survival is (μ+λ): parents and offspring compete together, ranked by
+//! front and then by crowding distance.
+
+use serde::{Deserialize, Serialize};
+
+use crate::fitness::EvalResult;
+use crate::genome::Genome;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Objectives {
+    pub accuracy: f64,
+    pub honesty: f64,
+    pub train_cost: u64,
+    pub dimensions: usize,
+}
+
+impl Objectives {
+    pub fn from_eval(genome: &Genome, result: &EvalResult) -> Self {
+        Self {
+            accuracy: result.primary_report.accuracy,
+            honesty: result.primary_report.honesty,
+            train_cost: result.train_cost,
+            dimensions: genome.params.dimensions,
+        }
+    }
+
+    /// All objectives as "larger is better".
+    fn as_maximized(&self) -> [f64; 4] {
+        [
+            self.accuracy,
+            self.honesty,
+            -(self.train_cost as f64),
+            -(self.dimensions as f64),
+        ]
+    }
+
+    /// `self` is no worse on every objective and strictly better on one.
+    pub fn dominates(&self, other: &Objectives) -> bool {
+        let a = self.as_maximized();
+        let b = other.as_maximized();
+        a.iter().zip(&b).all(|(x, y)| x >= y) && a.iter().zip(&b).any(|(x, y)| x > y)
+    }
+}
+
+/// Front index (0 = non-dominated) and crowding distance of one genome.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ParetoRank {
+    pub front: usize,
+    pub crowding: f64,
+}
+
+impl ParetoRank {
+    /// Crowded-comparison operator: lower front wins, then larger crowding.
+    pub fn better_than(&self, other: &ParetoRank) -> bool {
+        self.front < other.front || (self.front == other.front && self.crowding > other.crowding)
+    }
+}
+
+/// One member of a reported front.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParetoPoint {
+    pub genome_id: u64,
+    pub objectives: Objectives,
+    pub fitness: f64,
+}
+
+/// Fast non-dominated sort. Returns fronts as lists of indices.
+pub fn non_dominated_sort(objs: &[Objectives]) -> Vec<Vec<usize>> {
+    let n = objs.len();
+    let mut dominated_by: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut domination_count = vec![0usize; n];
+    let mut fronts: Vec<Vec<usize>> = vec![Vec::new()];
+
+    for p in 0..n {
+        for q in 0..n {
+            if objs[p].dominates(&objs[q]) {
+                dominated_by[p].push(q);
+            } else if objs[q].dominates(&objs[p]) {
+                domination_count[p] += 1;
+            }
+        }
+        if domination_count[p] == 0 {
+            fronts[0].push(p);
+        }
+    }
+
+    let mut i = 0;
+    while !fronts[i].is_empty() {
+        let mut next = Vec::new();
+        for &p in &fronts[i] {
+            for &q in &dominated_by[p] {
+                domination_count[q] -= 1;
+                if domination_count[q] == 0 {
+                    next.push(q);
+                }
+            }
+        }
+        i += 1;
+        fronts.push(next);
+    }
+    fronts.pop();
+    fronts
+}
+
+/// Crowding distance for the members of one front (aligned with `front`).
+/// Boundary points get infinity so the extremes of each objective survive.
+pub fn crowding_distance(objs: &[Objectives], front: &[usize]) -> Vec<f64> {
+    let mut distance = vec![0.0; front.len()];
+    if front.len() <= 2 {
+        return vec![f64::INFINITY; front.len()];
+    }
+
+    for m in 0..4 {
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&a, &b| {
+            objs[front[a]].as_maximized()[m]
+                .partial_cmp(&objs[front[b]].as_maximized()[m])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let lo = objs[front[order[0]]].as_maximized()[m];
+        let hi = objs[front[order[order.len() - 1]]].as_maximized()[m];
+        // An objective nobody differs on says nothing about crowding
+        if hi - lo <= f64::EPSILON {
+            continue;
+        }
+        distance[order[0]] = f64::INFINITY;
+        distance[order[order.len() - 1]] = f64::INFINITY;
+        for w in 1..order.len() - 1 {
+            let prev = objs[front[order[w - 1]]].as_maximized()[m];
+            let next = objs[front[order[w + 1]]].as_maximized()[m];
+            distance[order[w]] += (next - prev) / (hi - lo);
+        }
+    }
+    distance
+}
+
+/// Rank every genome: front index plus crowding distance within its front.
+pub fn rank_all(objs: &[Objectives]) -> Vec<ParetoRank> {
+    let mut ranks = vec![
+        ParetoRank {
+            front: 0,
+            crowding: 0.0
+        };
+        objs.len()
+    ];
+    for (f, front) in non_dominated_sort(objs).iter().enumerate() {
+        for (&idx, d) in front.iter().zip(crowding_distance(objs, front)) {
+            ranks[idx] = ParetoRank {
+                front: f,
+                crowding: d,
+            };
+        }
+    }
+    ranks
+}
+
+/// NSGA-II environmental selection: the `size` best indices by crowded comparison,
+/// in that order.
+pub fn select_survivors(ranks: &[ParetoRank], size: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..ranks.len()).collect();
+    order.sort_by(|&a, &b| {
+        ranks[a].front.cmp(&ranks[b].front).then_with(|| {
+            ranks[b]
+                .crowding
+                .partial_cmp(&ranks[a].crowding)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    });
+    order.truncate(size);
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obj(accuracy: f64, honesty: f64, train_cost: u64, dimensions: usize) -> Objectives {
+        Objectives {
+            accuracy,
+            honesty,
+            train_cost,
+            dimensions,
+        }
+    }
+
+    #[test]
+    fn domination_needs_one_strict_improvement() {
+        let a = obj(0.8, 0.9, 100, 8);
+        assert!(a.dominates(&obj(0.8, 0.8, 100, 8)));
+        assert!(!a.dominates(&a));
+        // Trade-off: neither dominates
+        let b = obj(0.9, 0.5, 100, 8);
+        assert!(!a.dominates(&b) && !b.dominates(&a));
+        // Cheaper build dominates when scores tie
+        assert!(obj(0.8, 0.9, 50, 8).dominates(&a));
+    }
+
+    #[test]
+    fn fronts_and_survivors() {
+        let objs = vec![
+            obj(0.9, 0.5, 100, 8), // front 0 (best accuracy)
+            obj(0.7, 0.9, 100, 8), // front 0 (best honesty)
+            obj(0.6, 0.4, 100, 8), // front 1 (dominated by both)
+            obj(0.8, 0.7, 100, 8), // front 0 (in between)
+        ];
+        let fronts = non_dominated_sort(&objs);
+        assert_eq!(fronts.len(), 2);
+        assert_eq!(fronts[1], vec![2]);
+
+        let ranks = rank_all(&objs);
+        assert_eq!(ranks[2].front, 1);
+        // Extremes of the front are infinitely crowded, so they are kept first
+        assert!(ranks[0].crowding.is_infinite() && ranks[1].crowding.is_infinite());
+        assert!(ranks[3].crowding.is_finite());
+
+        let survivors = select_survivors(&ranks, 3);
+        assert!(!survivors.contains(&2));
+    }
+}
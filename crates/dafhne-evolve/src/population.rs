@@ -108,6 +108,7 @@ fn random_genome(
         fitness: None,
         primary_fitness: None,
         cross_fitness: None,
+        holdout_fitness: None,
     }
 }
 
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use dafhne_core::ExpectedAnswer;
+use dafhne_core::{AnswerKind, Breakdown, ExpectedAnswer};
 
 use crate::fitness::EvalResult;
 use crate::genome::*;
@@ -61,17 +61,34 @@ pub fn write_status_md(
         .find(|er| er.genome_id == latest.best_genome_id);
     if let Some(best_eval) = best_eval {
         md.push_str(&format!(
-            "- Primary (dict5): {:.4}\n",
+            "- Primary ({}): {:.4}\n",
+            benchmark_name(best_eval, 0),
             best_eval.primary_report.fitness
         ));
-        if let Some(ref cr) = best_eval.cross_report {
-            md.push_str(&format!("- Cross (dict12): {:.4}\n", cr.fitness));
+        if let Some(ref hr) = best_eval.holdout_report {
+            md.push_str(&format!(
+                "- Holdout ({}): {:.4} (train - holdout gap: {:+.4})\n",
+                benchmark_name(best_eval, 0),
+                hr.fitness,
+                best_eval.primary_report.fitness - hr.fitness
+            ));
+        }
+        if best_eval.benchmarks.is_empty() {
+            if let Some(ref cr) = best_eval.cross_report {
+                md.push_str(&format!("- Cross (dict12): {:.4}\n", cr.fitness));
+            }
+        }
+        for b in best_eval.benchmarks.iter().skip(1) {
+            md.push_str(&format!(
+                "- Cross ({}): {:.4} (weight {:.2})\n",
+                b.name, b.report.fitness, b.weight
+            ));
         }
         if let Some(ref dr) = best_eval.dual_report {
             let uplift = dr.fitness - best_eval.primary_report.fitness;
             md.push_str(&format!(
-                "- Dual-space (dict5): {:.4} (uplift: {:+.4})\n",
-                dr.fitness, uplift
+                "- Dual-space ({}): {:.4} (uplift: {:+.4})\n",
+                benchmark_name(best_eval, 0), dr.fitness, uplift
             ));
         }
     }
@@ -85,6 +102,67 @@ pub fn write_status_md(
         latest.best_genome_id
     ));
 
+    // Non-GA optimizer state
+    if let Some(ref summary) = latest.optimizer_summary {
+        md.push_str(&format!("### Optimizer: {}\n\n", summary));
+    }
+
+    // Train/holdout split and holdout-based selection
+    if let Some(ref split) = latest.split {
+        md.push_str("### Holdout Selection:\n");
+        md.push_str(&format!(
+            "- Split: train {} / holdout {}{}\n",
+            split.train_size,
+            split.holdout_size,
+            split.fold_label()
+        ));
+        if let (Some(top), Some(id)) = (latest.top_holdout_fitness, latest.top_holdout_genome_id) {
+            md.push_str(&format!("- Top holdout this generation: {:.4} (ID {})\n", top, id));
+        }
+        let selected = all_stats
+            .iter()
+            .filter_map(|s| s.top_holdout_fitness.zip(s.top_holdout_genome_id).map(|(f, id)| (f, id, s.generation)))
+            .fold(None, |acc: Option<(f64, u64, usize)>, cur| match acc {
+                Some(best) if best.0 >= cur.0 => Some(best),
+                _ => Some(cur),
+            });
+        if let Some((f, id, gen)) = selected {
+            md.push_str(&format!(
+                "- Selected genome (best holdout so far): ID {} holdout {:.4} (gen {})\n\n",
+                id, f, gen
+            ));
+        }
+    }
+
+    // Pareto front (NSGA-II mode)
+    if !latest.pareto_front.is_empty() {
+        md.push_str(&format!(
+            "### Pareto Front ({} non-dominated):\n",
+            latest.pareto_front.len()
+        ));
+        md.push_str("| Genome | Accuracy | Honesty | Build cost | Dims | Fitness |\n");
+        md.push_str("|--------|----------|---------|----------|------|---------|\n");
+        let mut front = latest.pareto_front.clone();
+        front.sort_by(|a, b| {
+            b.objectives
+                .accuracy
+                .partial_cmp(&a.objectives.accuracy)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        for p in &front {
+            md.push_str(&format!(
+                "| {} | {:.4} | {:.4} | {} | {} | {:.4} |\n",
+                p.genome_id,
+                p.objectives.accuracy,
+                p.objectives.honesty,
+                p.objectives.train_cost,
+                p.objectives.dimensions,
+                p.fitness
+            ));
+        }
+        md.push('\n');
+    }
+
     // Best genome parameters
     if let Some(best) = current_population.first() {
         md.push_str("### Best Genome Parameters:\n");
@@ -183,12 +261,16 @@ pub fn write_status_md(
                 qr.question_id, qr.expected, status
             ));
         }
+        md.push_str(&breakdown_md(&best_eval.primary_report.breakdown));
     }
 
-    // Cross-validation question accuracy (dict12)
+    // Cross-validation question accuracy (first secondary benchmark)
     if let Some(best_eval) = best_eval {
         if let Some(ref cross_report) = best_eval.cross_report {
-            md.push_str("\n### Cross-Validation Accuracy (dict12, Best Genome):\n");
+            md.push_str(&format!(
+                "\n### Cross-Validation Accuracy ({}, Best Genome):\n",
+                benchmark_name(best_eval, 1)
+            ));
             md.push_str(&format!(
                 "- Cross Fitness: {:.4}  (accuracy: {:.4}, honesty: {:.4})\n",
                 cross_report.fitness, cross_report.accuracy, cross_report.honesty
@@ -217,10 +299,13 @@ pub fn write_status_md(
             }
         }
 
-        // Dual-space ensemble accuracy (dict5, if available)
+        // Dual-space ensemble accuracy (primary, if available)
         if let Some(ref dual_report) = best_eval.dual_report {
             let uplift = dual_report.fitness - best_eval.primary_report.fitness;
-            md.push_str("\n### Dual-Space Ensemble Accuracy (dict5, Best Genome):\n");
+            md.push_str(&format!(
+                "\n### Dual-Space Ensemble Accuracy ({}, Best Genome):\n",
+                benchmark_name(best_eval, 0)
+            ));
             md.push_str(&format!(
                 "- Dual Fitness: {:.4}  (accuracy: {:.4}, honesty: {:.4})\n",
                 dual_report.fitness, dual_report.accuracy, dual_report.honesty
@@ -251,9 +336,13 @@ pub fn write_status_md(
     md.push_str("\n### Fitness History:\n");
     md.push_str("```\n");
     for stat in all_stats {
+        let holdout = match (stat.best_holdout_fitness, stat.top_holdout_fitness) {
+            (Some(h), Some(top)) => format!("  holdout {:.4}  top holdout {:.4}", h, top),
+            _ => String::new(),
+        };
         md.push_str(&format!(
-            "Gen {:3}: best {:.4}  avg {:.4}  (ID {})\n",
-            stat.generation, stat.best_fitness, stat.average_fitness, stat.best_genome_id
+            "Gen {:3}: best {:.4}  avg {:.4}{}  (ID {})\n",
+            stat.generation, stat.best_fitness, stat.average_fitness, holdout, stat.best_genome_id
         ));
     }
     md.push_str("```\n");
@@ -398,11 +487,13 @@ pub fn write_suggestions_md(
 
             md.push_str("\n## Cross-Validation Analysis\n");
             md.push_str(&format!(
-                "- Primary fitness (dict5): {:.4}\n",
+                "- Primary fitness ({}): {:.4}\n",
+                benchmark_name(best_eval, 0),
                 primary_fitness
             ));
             md.push_str(&format!(
-                "- Cross fitness (dict12): {:.4}\n",
+                "- Cross fitness ({}): {:.4}\n",
+                benchmark_name(best_eval, 1),
                 cross_fitness
             ));
             md.push_str(&format!("- Gap: {:.4}\n\n", gap));
@@ -413,14 +504,20 @@ pub fn write_suggestions_md(
                     "The primary-cross fitness gap ({:.4}) exceeds the 0.15 threshold.\n",
                     gap
                 ));
-                md.push_str("The model may be memorizing dict5 patterns rather than learning general comprehension.\n\n");
+                md.push_str(&format!(
+                    "The model may be memorizing {} patterns rather than learning general comprehension.\n\n",
+                    benchmark_name(best_eval, 0)
+                ));
                 md.push_str("Consider:\n");
                 md.push_str("- Reducing dimensions (currently encourages memorization)\n");
                 md.push_str("- Increasing learning passes (more time to generalize)\n");
                 md.push_str("- Higher connector_min_frequency (filters out rare/noise connectors)\n");
                 md.push_str("- Increasing cross-validation weight beyond 0.3\n");
             } else {
-                md.push_str("✅ No significant overfitting detected. The model generalizes well to dict12.\n");
+                md.push_str(&format!(
+                    "✅ No significant overfitting detected. The model generalizes well to {}.\n",
+                    benchmark_name(best_eval, 1)
+                ));
             }
         }
     }
@@ -431,7 +528,15 @@ pub fn write_suggestions_md(
 
 // ─── Helpers ────────────────────────────────────────────────────
 
-fn write_json<T: serde::Serialize + ?Sized>(path: &Path, value: &T) {
+/// Manifest name of the i-th benchmark; legacy results (no `benchmarks`) are dict5/dict12/dict18.
+fn benchmark_name(result: &EvalResult, i: usize) -> &str {
+    match result.benchmarks.get(i) {
+        Some(b) => &b.name,
+        None => ["dict5", "dict12", "dict18"].get(i).copied().unwrap_or("?"),
+    }
+}
+
+pub(crate) fn write_json<T: serde::Serialize + ?Sized>(path: &Path, value: &T) {
     let json = serde_json::to_string_pretty(value).unwrap();
     std::fs::write(path, json).unwrap();
 }
@@ -500,6 +605,19 @@ pub fn write_multi_status_md(
             best_eval.report.honesty,
         ));
 
+        if best_eval.benchmarks.len() > 1 {
+            md.push_str("### Benchmarks:\n");
+            md.push_str("| Benchmark | Weight | Fitness | Correct |\n");
+            md.push_str("|-----------|--------|---------|---------|\n");
+            for b in &best_eval.benchmarks {
+                md.push_str(&format!(
+                    "| {} | {:.2} | {:.4} | {}/{} |\n",
+                    b.name, b.weight, b.report.fitness, b.report.total_correct, b.report.total_questions
+                ));
+            }
+            md.push('\n');
+        }
+
         md.push_str("### Question Results (Best Genome):\n");
         md.push_str("| Question | Expected | Result |\n");
         md.push_str("|----------|----------|--------|\n");
@@ -510,6 +628,7 @@ pub fn write_multi_status_md(
                 qr.question_id, qr.expected, status
             ));
         }
+        md.push_str(&breakdown_md(&best_eval.report.breakdown));
     }
 
     // Per-space parameter comparison (best genome)
@@ -571,3 +690,34 @@ pub fn write_multi_status_md(
     let path = config.results_dir.join("STATUS.md");
     std::fs::write(path, md).unwrap();
 }
+
+/// Per-category scores and the Yes/No/IDK/Word confusion matrix as markdown.
+fn breakdown_md(breakdown: &Breakdown) -> String {
+    let mut md = String::new();
+    md.push_str("\n### Per-Category Accuracy (Best Genome):\n");
+    md.push_str("| Category | Correct | Accuracy | Honesty |\n");
+    md.push_str("|----------|---------|----------|---------|\n");
+    for c in &breakdown.categories {
+        let score = |n: usize, v: f64| if n == 0 { "-".to_string() } else { format!("{:.2}", v) };
+        md.push_str(&format!(
+            "| {} | {}/{} | {} | {} |\n",
+            if c.category.is_empty() { "(none)" } else { &c.category },
+            c.correct,
+            c.total,
+            score(c.answerable, c.accuracy),
+            score(c.total - c.answerable, c.honesty),
+        ));
+    }
+
+    md.push_str("\n### Confusion Matrix (expected \\ actual):\n");
+    md.push_str("| | Yes | No | IDK | Word |\n");
+    md.push_str("|---|-----|----|-----|------|\n");
+    for expected in AnswerKind::ALL {
+        md.push_str(&format!("| **{}** |", expected.label()));
+        for actual in AnswerKind::ALL {
+            md.push_str(&format!(" {} |", breakdown.confusion.get(expected, actual)));
+        }
+        md.push('\n');
+    }
+    md
+}
@@ -5,15 +5,21 @@ use std::sync::Arc;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use dafhne_core::SimpleRng;
-use dafhne_parser::{load_dictionary, parse_grammar_text, parse_test_questions};
+use dafhne_parser::load_test_suite;
 
+use crate::fitness_cache::{benchmark_fingerprint, genome_key, multi_fingerprint, multi_genome_key, FitnessCache};
 use crate::fitness::{build_trained_space, evaluate_genome, evaluate_multi_genome, EvalResult, MultiSpaceEvalResult};
 use crate::genome::{Genome, MultiSpaceGenome, ParamRanges};
 use crate::lineage::LineageTracker;
-use crate::operators::{crossover, crossover_multi, mutate, mutate_multi, tournament_select, tournament_select_multi};
+use crate::manifest::{parse_build_mode, BenchmarkMode, EvalManifest, LoadedBenchmark, Scoring};
+use crate::optimizer::{Optimizer, OptimizerKind, OptimizerState};
+use crate::operators::{crossover, crossover_multi, crowded_tournament_select, mutate, mutate_multi,
+                        tournament_select, tournament_select_multi};
+use crate::pareto::{rank_all, select_survivors, Objectives, ParetoPoint, ParetoRank};
 use crate::population::{initialize_population, initialize_multi_population};
 use crate::reporting::{save_generation, save_space_dump, write_status_md, write_suggestions_md,
-                        save_multi_generation, write_multi_status_md};
+                        save_multi_generation, write_multi_status_md, write_json};
+use crate::splits::{GenerationSplit, SplitPlan};
 
 // ─── Configuration ──────────────────────────────────────────────
 
@@ -28,8 +34,29 @@ pub struct EvolutionConfig {
     pub strategy_mutation_rate: f64,
     pub cross_validation_threshold: f64,
     pub base_seed: u64,
+    /// Evaluation manifest (TOML). When set, the dict5/dict12/dict18 fields are ignored.
+    #[serde(default)]
+    pub manifest_path: Option<PathBuf>,
+    #[serde(default)]
     pub dict5_path: PathBuf,
+    #[serde(default)]
     pub dict5_test_path: PathBuf,
+    /// Holdout suite for dict5: scored every generation, never part of fitness.
+    #[serde(default)]
+    pub dict5_holdout_path: Option<PathBuf>,
+    /// K-fold cross-validation over the dict5 suite (0 = off).
+    #[serde(default)]
+    pub folds: usize,
+    /// NSGA-II selection over accuracy, honesty, build cost and dimensions
+    /// instead of scalar fitness.
+    #[serde(default)]
+    pub pareto: bool,
+    /// Search algorithm that proposes each generation.
+    #[serde(default)]
+    pub optimizer: OptimizerKind,
+    /// Persistent fitness cache (JSONL). None keeps the cache in memory only.
+    #[serde(default)]
+    pub fitness_cache_path: Option<PathBuf>,
     pub dict12_path: Option<PathBuf>,
     pub dict12_test_path: Option<PathBuf>,
     #[serde(default)]
@@ -43,6 +70,23 @@ pub struct EvolutionConfig {
     pub results_dir: PathBuf,
 }
 
+impl EvolutionConfig {
+    /// The manifest file, or the legacy dict5/dict12/dict18 layout as a manifest.
+    pub fn manifest(&self) -> EvalManifest {
+        match &self.manifest_path {
+            Some(path) => EvalManifest::load(path).unwrap_or_else(|e| panic!("{}", e)),
+            None => EvalManifest::legacy(
+                &self.dict5_path,
+                &self.dict5_test_path,
+                self.grammar5_path.as_deref(),
+                self.dict12_path.as_deref().zip(self.dict12_test_path.as_deref()),
+                self.dict18_path.as_deref().zip(self.dict18_test_path.as_deref()),
+                self.grammar18_path.as_deref(),
+            ),
+        }
+    }
+}
+
 // ─── Per-generation Statistics ───────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +98,23 @@ pub struct GenerationStats {
     pub best_genome_id: u64,
     pub population_size: usize,
     pub eval_results: Vec<EvalResult>,
+    /// Train/holdout split used this generation.
+    #[serde(default)]
+    pub split: Option<GenerationSplit>,
+    /// Holdout fitness of the best-by-fitness genome.
+    #[serde(default)]
+    pub best_holdout_fitness: Option<f64>,
+    /// Highest holdout fitness in the population, and which genome had it.
+    #[serde(default)]
+    pub top_holdout_fitness: Option<f64>,
+    #[serde(default)]
+    pub top_holdout_genome_id: Option<u64>,
+    /// Non-dominated genomes this generation (Pareto mode only).
+    #[serde(default)]
+    pub pareto_front: Vec<ParetoPoint>,
+    /// State of the optimizer that proposed this generation (non-GA only).
+    #[serde(default)]
+    pub optimizer_summary: Option<String>,
 }
 
 // ─── Checkpoint ─────────────────────────────────────────────────
@@ -66,6 +127,9 @@ pub struct Checkpoint {
     pub config: EvolutionConfig,
     pub lineage: LineageTracker,
     pub all_generation_stats: Vec<GenerationStats>,
+    /// CMA-ES / TPE state; None for the GA.
+    #[serde(default)]
+    pub optimizer_state: Option<OptimizerState>,
 }
 
 // ─── Main Evolution ─────────────────────────────────────────────
@@ -75,8 +139,6 @@ pub fn evolve(config: &EvolutionConfig) -> Genome {
     let ranges = ParamRanges::default();
     let mut rng = SimpleRng::new(config.base_seed);
     let mut id_counter: u64 = 0;
-    let lineage = LineageTracker::new();
-    let all_stats: Vec<GenerationStats> = Vec::new();
     let population = initialize_population(
         config.population_size,
         &ranges,
@@ -85,7 +147,16 @@ pub fn evolve(config: &EvolutionConfig) -> Genome {
         &mut id_counter,
     );
 
-    evolve_inner(config, population, id_counter, rng, lineage, all_stats, 0)
+    let start = Checkpoint {
+        generation: 0,
+        population,
+        id_counter,
+        config: config.clone(),
+        lineage: LineageTracker::new(),
+        all_generation_stats: Vec::new(),
+        optimizer_state: OptimizerState::new(config.optimizer, config.population_size),
+    };
+    evolve_inner(start, rng)
 }
 
 /// Resume evolution from a checkpoint.
@@ -93,7 +164,7 @@ pub fn resume(results_dir: &PathBuf) -> Genome {
     let checkpoint_path = results_dir.join("checkpoint.json");
     let content =
         std::fs::read_to_string(&checkpoint_path).expect("Failed to read checkpoint.json");
-    let checkpoint: Checkpoint =
+    let mut checkpoint: Checkpoint =
         serde_json::from_str(&content).expect("Failed to parse checkpoint.json");
 
     eprintln!(
@@ -109,26 +180,34 @@ pub fn resume(results_dir: &PathBuf) -> Genome {
             .wrapping_add(checkpoint.generation as u64 * 1000),
     );
 
-    evolve_inner(
-        &checkpoint.config,
-        checkpoint.population,
-        checkpoint.id_counter,
-        rng,
-        checkpoint.lineage,
-        checkpoint.all_generation_stats,
-        checkpoint.generation,
-    )
+    // Checkpoints written before optimizers existed carry no state
+    if checkpoint.optimizer_state.is_none() {
+        checkpoint.optimizer_state =
+            OptimizerState::new(checkpoint.config.optimizer, checkpoint.config.population_size);
+    }
+
+    evolve_inner(checkpoint, rng)
 }
 
-fn evolve_inner(
-    config: &EvolutionConfig,
-    mut population: Vec<Genome>,
-    mut id_counter: u64,
-    mut rng: SimpleRng,
-    mut lineage: LineageTracker,
-    mut all_stats: Vec<GenerationStats>,
-    start_gen: usize,
-) -> Genome {
+fn evolve_inner(start: Checkpoint, mut rng: SimpleRng) -> Genome {
+    let Checkpoint {
+        generation: start_gen,
+        mut population,
+        mut id_counter,
+        config,
+        mut lineage,
+        all_generation_stats: mut all_stats,
+        optimizer_state: mut optimizer,
+    } = start;
+    let config = &config;
+    assert!(
+        !(config.pareto && optimizer.is_some()),
+        "--pareto selection only works with the GA optimizer"
+    );
+    if let Some(opt) = &optimizer {
+        eprintln!("Optimizer: {}", opt.name());
+    }
+
     // Setup graceful shutdown
     let shutdown = Arc::new(AtomicBool::new(false));
     let shutdown_clone = shutdown.clone();
@@ -137,107 +216,161 @@ fn evolve_inner(
         shutdown_clone.store(true, Ordering::SeqCst);
     });
 
-    // Load data files once
-    let dict5 = load_dictionary(&config.dict5_path).expect("Failed to read dict5");
-
-    let test5_content =
-        std::fs::read_to_string(&config.dict5_test_path).expect("Failed to read dict5_test");
-    let test5 = parse_test_questions(&test5_content);
-
-    let dict12 = config.dict12_path.as_ref().map(|p| {
-        load_dictionary(p).expect("Failed to read dict12")
-    });
-    let test12 = config.dict12_test_path.as_ref().map(|p| {
-        let content = std::fs::read_to_string(p).expect("Failed to read dict12_test");
-        parse_test_questions(&content)
-    });
-
-    let grammar5 = config.grammar5_path.as_ref().map(|p| {
-        let content = std::fs::read_to_string(p).expect("Failed to read grammar5");
-        let g = parse_grammar_text(&content);
-        eprintln!("Loaded grammar5: {} sections", g.entries.len());
-        g
-    });
+    // Load benchmarks once
+    let manifest = config.manifest();
+    let scoring = Scoring::from_manifest(&manifest, config.cross_validation_threshold);
+    let benchmarks: Vec<LoadedBenchmark> = manifest.benchmarks.iter().map(LoadedBenchmark::load).collect();
+    for b in &benchmarks {
+        eprintln!(
+            "Benchmark {}: {} questions, weight {:.2}{}",
+            b.name,
+            b.test.questions.len(),
+            b.weight,
+            if b.mode == BenchmarkMode::Dual { " (dual)" } else { "" }
+        );
+    }
+    let primary = &benchmarks[0];
 
-    let dict18 = config.dict18_path.as_ref().map(|p| {
-        load_dictionary(p).expect("Failed to read dict18")
-    });
-    let test18 = config.dict18_test_path.as_ref().map(|p| {
-        let content = std::fs::read_to_string(p).expect("Failed to read dict18_test");
-        parse_test_questions(&content)
-    });
-    let grammar18 = config.grammar18_path.as_ref().map(|p| {
-        let content = std::fs::read_to_string(p).expect("Failed to read grammar18");
-        let g = parse_grammar_text(&content);
-        eprintln!("Loaded grammar18: {} sections", g.entries.len());
-        g
+    let holdout_path = config.dict5_holdout_path.as_ref().or(manifest.primary().holdout.as_ref());
+    let holdout = holdout_path.map(|p| {
+        load_test_suite(p).expect("Failed to read holdout test suite")
     });
+    let split_plan = SplitPlan::new(primary.test.clone(), holdout, config.folds, config.base_seed);
+    if let SplitPlan::Folds { folds } = &split_plan {
+        eprintln!(
+            "Cross-validation: {} folds, fold {} held out for selection, the rest rotating",
+            folds.len(),
+            folds.len() - 1
+        );
+    }
 
     // Create results directory
     std::fs::create_dir_all(&config.results_dir).expect("Failed to create results dir");
 
     let ranges = ParamRanges::default();
-    let mut best_ever: Option<Genome> = None;
+    // Best genome so far, with the selection fold its holdout score came from
+    let mut best_ever: Option<(Genome, Option<usize>)> = None;
 
     // Adaptive mutation: increase rate when fitness stalls
     let mut effective_mutation_rate = config.mutation_rate;
     let mut stall_counter: usize = 0;
     let mut prev_best_fitness: f64 = 0.0;
 
+    // Elites, Pareto survivors and duplicate offspring reuse earlier evaluations
+    let mut cache: FitnessCache<EvalResult> = FitnessCache::open(config.fitness_cache_path.as_deref());
+
     for gen in start_gen..config.generations {
         if shutdown.load(Ordering::SeqCst) {
-            save_checkpoint(gen, &population, id_counter, config, &lineage, &all_stats);
+            save_checkpoint(gen, &population, id_counter, config, &lineage, &all_stats, optimizer.as_ref());
             break;
         }
 
         eprintln!("=== Generation {} ===", gen);
 
-        // ── EVALUATE (parallel) ──
+        let (train_suite, holdout_suite, split) = split_plan.for_generation(gen);
+        if split_plan.has_holdout() {
+            eprintln!(
+                "  Split: train {} / holdout {}{}",
+                split.train_size,
+                split.holdout_size,
+                split.fold_label()
+            );
+        }
+
+        // ── EVALUATE (parallel, cached) ──
         let base_seed = config.base_seed;
-        let threshold = config.cross_validation_threshold;
-        let eval_results: Vec<EvalResult> = population
-            .par_iter()
-            .map(|genome| {
-                evaluate_genome(
-                    genome,
-                    &dict5,
-                    &test5,
-                    dict12.as_ref(),
-                    test12.as_ref(),
-                    grammar5.as_ref(),
-                    dict18.as_ref(),
-                    test18.as_ref(),
-                    grammar18.as_ref(),
-                    base_seed,
-                    threshold,
-                )
-            })
-            .collect();
+        let fingerprint =
+            benchmark_fingerprint(&benchmarks, &train_suite, holdout_suite.as_ref(), &scoring, base_seed);
+        let keys: Vec<String> = population.iter().map(|g| genome_key(fingerprint, g)).collect();
+        let (hits, misses) = (cache.hits, cache.misses);
+        let mut eval_results: Vec<EvalResult> = cache.resolve(&keys, &population, |todo| {
+            todo.par_iter()
+                .map(|(_, genome)| {
+                    evaluate_genome(
+                        genome,
+                        &benchmarks,
+                        &train_suite,
+                        holdout_suite.as_ref(),
+                        &scoring,
+                        base_seed,
+                    )
+                })
+                .collect()
+        });
+        for (result, genome) in eval_results.iter_mut().zip(&population) {
+            result.genome_id = genome.id;
+        }
+        eprintln!(
+            "  Fitness cache: {} reused, {} evaluated",
+            cache.hits - hits,
+            cache.misses - misses
+        );
 
         // Write fitness back to genomes
         for (genome, result) in population.iter_mut().zip(eval_results.iter()) {
             genome.fitness = Some(result.final_fitness);
             genome.primary_fitness = Some(result.primary_report.fitness);
             genome.cross_fitness = result.cross_report.as_ref().map(|cr| cr.fitness);
+            genome.holdout_fitness = result.holdout_report.as_ref().map(|hr| hr.fitness);
         }
 
-        // ── SORT by fitness descending ──
-        population.sort_by(|a, b| {
-            b.fitness
-                .unwrap_or(0.0)
-                .partial_cmp(&a.fitness.unwrap_or(0.0))
+        // ── PARETO SURVIVAL (μ+λ) ──
+        let mut pareto_ranks: Vec<ParetoRank> = Vec::new();
+        let mut pareto_front: Vec<ParetoPoint> = Vec::new();
+        if config.pareto {
+            let objs: Vec<Objectives> = population
+                .iter()
+                .zip(&eval_results)
+                .map(|(g, r)| Objectives::from_eval(g, r))
+                .collect();
+            let ranks = rank_all(&objs);
+            let mut keep = select_survivors(&ranks, config.population_size);
+            // Front first, best scalar fitness first within a front
+            keep.sort_by(|&a, &b| {
+                ranks[a].front.cmp(&ranks[b].front).then_with(|| {
+                    population[b]
+                        .fitness
+                        .partial_cmp(&population[a].fitness)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+            });
+
+            pareto_front = keep
+                .iter()
+                .filter(|&&i| ranks[i].front == 0)
+                .map(|&i| ParetoPoint {
+                    genome_id: population[i].id,
+                    objectives: objs[i],
+                    fitness: population[i].fitness.unwrap_or(0.0),
+                })
+                .collect();
+            pareto_ranks = keep.iter().map(|&i| ranks[i]).collect();
+            eval_results = keep.iter().map(|&i| eval_results[i].clone()).collect();
+            population = keep.iter().map(|&i| population[i].clone()).collect();
+        } else {
+            // ── SORT by fitness descending ──
+            population.sort_by(|a, b| {
+                b.fitness
+                    .unwrap_or(0.0)
+                    .partial_cmp(&a.fitness.unwrap_or(0.0))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+
+        // Track best ever — by holdout score when a holdout exists, so the
+        // final genome is chosen on questions fitness never saw
+        let top_holdout = population.iter().max_by(|a, b| {
+            selection_key(a)
+                .partial_cmp(&selection_key(b))
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
-
-        // Track best ever
-        if let Some(current_best) = population.first() {
-            let current_fitness = current_best.fitness.unwrap_or(0.0);
-            let is_new_best = match &best_ever {
-                None => true,
-                Some(prev) => current_fitness > prev.fitness.unwrap_or(0.0),
-            };
-            if is_new_best {
-                best_ever = Some(current_best.clone());
+        let candidate = if split_plan.has_holdout() { top_holdout } else { population.first() };
+        if let Some(current_best) = candidate {
+            if is_new_best(best_ever.as_ref(), current_best, split.selection_fold, split_plan.has_holdout()) {
+                best_ever = Some((current_best.clone(), split.selection_fold));
+                if split_plan.has_holdout() {
+                    write_json(&config.results_dir.join("best_holdout_genome.json"), current_best);
+                }
             }
         }
 
@@ -257,6 +390,12 @@ fn evolve_inner(
             best_genome_id: population.first().map(|g| g.id).unwrap_or(0),
             population_size: population.len(),
             eval_results: eval_results.clone(),
+            split: split_plan.has_holdout().then_some(split),
+            best_holdout_fitness: population.first().and_then(|g| g.holdout_fitness),
+            top_holdout_fitness: top_holdout.and_then(|g| g.holdout_fitness),
+            top_holdout_genome_id: top_holdout.filter(|g| g.holdout_fitness.is_some()).map(|g| g.id),
+            pareto_front,
+            optimizer_summary: optimizer.as_ref().map(|o| o.summary()),
         };
 
         // Update lineage
@@ -272,7 +411,7 @@ fn evolve_inner(
 
         // Save space dump for the best genome
         if let Some(best) = population.first() {
-            let space = build_trained_space(best, &dict5, grammar5.as_ref(), config.base_seed);
+            let space = build_trained_space(best, primary.dictionary(), primary.grammar.as_ref(), config.base_seed);
             save_space_dump(gen, &space, config);
         }
 
@@ -287,7 +426,10 @@ fn evolve_inner(
             config,
         );
 
-        print_generation_summary(gen, &population);
+        print_generation_summary(gen, &population, &stats_holdout_line(all_stats.last()));
+        if let Some(front) = all_stats.last().map(|s| &s.pareto_front).filter(|f| !f.is_empty()) {
+            eprintln!("  Pareto front: {} genomes", front.len());
+        }
 
         // ── ADAPTIVE MUTATION ──
         let current_best = population.first().and_then(|g| g.fitness).unwrap_or(0.0);
@@ -302,31 +444,45 @@ fn evolve_inner(
             2 => config.mutation_rate * 1.5,
             _ => (config.mutation_rate * 2.5).min(0.6),
         };
-        if stall_counter >= 2 {
+        if stall_counter >= 2 && optimizer.is_none() {
             eprintln!("  Mutation rate: {:.2} (stall: {})", effective_mutation_rate, stall_counter);
         }
 
-        // ── BREED next generation ──
-        if gen < config.generations - 1 {
+        // ── PROPOSE next generation (CMA-ES / TPE) ──
+        if let Some(opt) = optimizer.as_mut().filter(|_| gen < config.generations - 1) {
+            opt.tell(&population);
+            population = opt.ask(config.population_size, &mut rng, &mut id_counter, gen + 1);
+            eprintln!("  {}", opt.summary());
+        }
+        // ── BREED next generation (GA) ──
+        else if gen < config.generations - 1 {
             let mut next_population: Vec<Genome> =
                 Vec::with_capacity(config.population_size);
 
-            // Elitism: top N survive unchanged
-            for elite in population.iter().take(config.elitism_count) {
-                let mut preserved = elite.clone();
-                preserved.generation = gen + 1;
-                preserved.fitness = None;
-                preserved.primary_fitness = None;
-                preserved.cross_fitness = None;
-                next_population.push(preserved);
-            }
+            let target_size = if config.pareto {
+                // (μ+λ): all survivors compete again alongside a full brood
+                next_population.extend(population.iter().cloned());
+                population.len() + config.population_size
+            } else {
+                // Elitism: top N survive unchanged
+                for elite in population.iter().take(config.elitism_count) {
+                    let mut preserved = elite.clone();
+                    preserved.generation = gen + 1;
+                    preserved.fitness = None;
+                    preserved.primary_fitness = None;
+                    preserved.cross_fitness = None;
+                    preserved.holdout_fitness = None;
+                    next_population.push(preserved);
+                }
+                config.population_size
+            };
 
             // Fill remaining with crossover + mutation
-            while next_population.len() < config.population_size {
+            while next_population.len() < target_size {
                 let parent_a =
-                    tournament_select(&population, config.tournament_size, &mut rng);
+                    select_parent(&population, &pareto_ranks, config.tournament_size, &mut rng);
                 let parent_b =
-                    tournament_select(&population, config.tournament_size, &mut rng);
+                    select_parent(&population, &pareto_ranks, config.tournament_size, &mut rng);
 
                 id_counter += 1;
                 let child = if rng.next_f64() < config.crossover_rate {
@@ -366,13 +522,14 @@ fn evolve_inner(
                 config,
                 &lineage,
                 &all_stats,
+                optimizer.as_ref(),
             );
             break;
         }
     }
 
     // Return best ever
-    best_ever.unwrap_or_else(|| {
+    best_ever.map(|(genome, _)| genome).unwrap_or_else(|| {
         population
             .into_iter()
             .max_by(|a, b| {
@@ -384,7 +541,58 @@ fn evolve_inner(
     })
 }
 
-fn print_generation_summary(_gen: usize, population: &[Genome]) {
+/// Crowded tournament in Pareto mode (`ranks` non-empty), scalar tournament otherwise.
+fn select_parent<'a>(
+    population: &'a [Genome],
+    ranks: &[ParetoRank],
+    tournament_size: usize,
+    rng: &mut SimpleRng,
+) -> &'a Genome {
+    if ranks.is_empty() {
+        tournament_select(population, tournament_size, rng)
+    } else {
+        crowded_tournament_select(population, ranks, tournament_size, rng)
+    }
+}
+
+/// Holdout score first, training fitness as tie-break.
+fn selection_key(genome: &Genome) -> (f64, f64) {
+    (
+        genome.holdout_fitness.unwrap_or(0.0),
+        genome.fitness.unwrap_or(0.0),
+    )
+}
+
+/// Whether `candidate`, scored on `selection_fold`, replaces the best so far.
+/// Holdout scores are only comparable on the same questions, so a score from a
+/// different selection fold never displaces the incumbent.
+fn is_new_best(
+    prev: Option<&(Genome, Option<usize>)>,
+    candidate: &Genome,
+    selection_fold: Option<usize>,
+    has_holdout: bool,
+) -> bool {
+    match prev {
+        None => true,
+        Some((_, fold)) if has_holdout && *fold != selection_fold => false,
+        Some((prev, _)) if has_holdout => selection_key(candidate) > selection_key(prev),
+        Some((prev, _)) => candidate.fitness.unwrap_or(0.0) > prev.fitness.unwrap_or(0.0),
+    }
+}
+
+fn stats_holdout_line(stats: Option<&GenerationStats>) -> String {
+    match stats.and_then(|s| s.best_holdout_fitness.map(|h| (h, s))) {
+        Some((h, s)) => format!(
+            "  Holdout: {:.4}  Top holdout: {:.4} (ID {})",
+            h,
+            s.top_holdout_fitness.unwrap_or(0.0),
+            s.top_holdout_genome_id.unwrap_or(0)
+        ),
+        None => String::new(),
+    }
+}
+
+fn print_generation_summary(_gen: usize, population: &[Genome], holdout_line: &str) {
     let best = population
         .first()
         .and_then(|g| g.fitness)
@@ -396,6 +604,9 @@ fn print_generation_summary(_gen: usize, population: &[Genome]) {
         "  Best: {:.4}  Avg: {:.4}  Best ID: {}",
         best, avg, best_id
     );
+    if !holdout_line.is_empty() {
+        eprintln!("{}", holdout_line);
+    }
 }
 
 fn save_checkpoint(
@@ -405,6 +616,7 @@ fn save_checkpoint(
     config: &EvolutionConfig,
     lineage: &LineageTracker,
     all_stats: &[GenerationStats],
+    optimizer: Option<&OptimizerState>,
 ) {
     let checkpoint = Checkpoint {
         generation: gen,
@@ -413,6 +625,7 @@ fn save_checkpoint(
         config: config.clone(),
         lineage: lineage.clone(),
         all_generation_stats: all_stats.to_vec(),
+        optimizer_state: optimizer.cloned(),
     };
     let path = config.results_dir.join("checkpoint.json");
     let json = serde_json::to_string_pretty(&checkpoint).unwrap();
@@ -436,12 +649,36 @@ pub struct MultiSpaceEvolutionConfig {
     pub base_seed: u64,
     pub results_dir: PathBuf,
     /// Space definitions: Vec<(name, dict_path)>
+    #[serde(default)]
     pub space_configs: Vec<(String, PathBuf)>,
     /// Path to unified test file
+    #[serde(default)]
     pub test_path: PathBuf,
+    /// Evaluation manifest (TOML) with a `[multispace]` section. When set,
+    /// space_configs and test_path are ignored.
+    #[serde(default)]
+    pub manifest_path: Option<PathBuf>,
     /// Optional: path to seed genome JSON (bootstrap from existing v11 best)
     #[serde(default)]
     pub seed_genome_path: Option<PathBuf>,
+    /// Persistent fitness cache (JSONL). None keeps the cache in memory only.
+    #[serde(default)]
+    pub fitness_cache_path: Option<PathBuf>,
+}
+
+impl MultiSpaceEvolutionConfig {
+    /// The manifest file, or the legacy `--spaces` / `--test` layout as a manifest.
+    pub fn manifest(&self) -> EvalManifest {
+        let manifest = match &self.manifest_path {
+            Some(path) => EvalManifest::load(path).unwrap_or_else(|e| panic!("{}", e)),
+            None => EvalManifest::legacy_multi(&self.space_configs, &self.test_path),
+        };
+        assert!(
+            !manifest.space_configs().is_empty(),
+            "Multi-space evolution needs a [multispace] section with at least one [[multispace.space]]"
+        );
+        manifest
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -470,7 +707,7 @@ pub fn evolve_multi(config: &MultiSpaceEvolutionConfig) -> MultiSpaceGenome {
     let mut rng = SimpleRng::new(config.base_seed);
     let mut id_counter: u64 = 0;
 
-    let space_names: Vec<String> = config.space_configs.iter().map(|(n, _)| n.clone()).collect();
+    let space_names: Vec<String> = config.manifest().space_configs().into_iter().map(|(n, _)| n).collect();
 
     // Load seed genome if provided — accept either single-space Genome or MultiSpaceGenome
     let (seed_single, seed_multi) = match config.seed_genome_path.as_ref() {
@@ -552,10 +789,11 @@ fn evolve_multi_inner(
         shutdown_clone.store(true, Ordering::SeqCst);
     });
 
-    // Load test suite once
-    let test_content =
-        std::fs::read_to_string(&config.test_path).expect("Failed to read test file");
-    let test_suite = parse_test_questions(&test_content);
+    // Load benchmarks once
+    let manifest = config.manifest();
+    let scoring = Scoring::from_manifest(&manifest, 0.0);
+    let benchmarks: Vec<LoadedBenchmark> = manifest.benchmarks.iter().map(LoadedBenchmark::load).collect();
+    let build_mode = parse_build_mode(manifest.multispace.as_ref().and_then(|ms| ms.build.as_deref()));
 
     std::fs::create_dir_all(&config.results_dir).expect("Failed to create results dir");
 
@@ -567,7 +805,9 @@ fn evolve_multi_inner(
     let mut stall_counter: usize = 0;
     let mut prev_best_fitness: f64 = 0.0;
 
-    let space_configs = config.space_configs.clone();
+    let space_configs = manifest.space_configs();
+    let mut cache: FitnessCache<MultiSpaceEvalResult> = FitnessCache::open(config.fitness_cache_path.as_deref());
+    let fingerprint = multi_fingerprint(&benchmarks, &space_configs, build_mode, &scoring, config.base_seed);
 
     for gen in start_gen..config.generations {
         if shutdown.load(Ordering::SeqCst) {
@@ -577,14 +817,25 @@ fn evolve_multi_inner(
 
         eprintln!("=== Multi-Space Generation {} ===", gen);
 
-        // ── EVALUATE (parallel) ──
+        // ── EVALUATE (parallel, cached) ──
         let base_seed = config.base_seed;
-        let eval_results: Vec<MultiSpaceEvalResult> = population
-            .par_iter()
-            .map(|genome| {
-                evaluate_multi_genome(genome, &space_configs, &test_suite, base_seed)
-            })
-            .collect();
+        let keys: Vec<String> = population.iter().map(|g| multi_genome_key(fingerprint, g)).collect();
+        let (hits, misses) = (cache.hits, cache.misses);
+        let mut eval_results: Vec<MultiSpaceEvalResult> = cache.resolve(&keys, &population, |todo| {
+            todo.par_iter()
+                .map(|(_, genome)| {
+                    evaluate_multi_genome(genome, &space_configs, build_mode, &benchmarks, &scoring, base_seed)
+                })
+                .collect()
+        });
+        for (result, genome) in eval_results.iter_mut().zip(&population) {
+            result.genome_id = genome.id;
+        }
+        eprintln!(
+            "  Fitness cache: {} reused, {} evaluated",
+            cache.hits - hits,
+            cache.misses - misses
+        );
 
         // Write fitness back
         for (genome, result) in population.iter_mut().zip(eval_results.iter()) {
@@ -748,3 +999,29 @@ fn save_multi_checkpoint(
     std::fs::write(path, json).expect("Failed to write checkpoint");
     eprintln!("Multi-space checkpoint saved at generation {}.", gen);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored(id: u64, fitness: f64, holdout: f64) -> Genome {
+        let mut ids = id - 1;
+        let mut genome = initialize_population(1, &ParamRanges::default(), &mut SimpleRng::new(id), 0, &mut ids)
+            .remove(0);
+        genome.fitness = Some(fitness);
+        genome.holdout_fitness = Some(holdout);
+        genome
+    }
+
+    #[test]
+    fn best_ever_ignores_scores_from_another_fold() {
+        let incumbent = (scored(1, 0.5, 0.4), Some(4));
+        let better = scored(2, 0.9, 0.9);
+        assert!(is_new_best(Some(&incumbent), &better, Some(4), true));
+        assert!(!is_new_best(Some(&incumbent), &better, Some(2), true));
+        assert!(!is_new_best(Some(&incumbent), &scored(3, 0.9, 0.3), Some(4), true));
+        assert!(is_new_best(None, &better, Some(2), true));
+        // Without a holdout, training fitness decides
+        assert!(is_new_best(Some(&(scored(1, 0.5, 0.0), None)), &scored(2, 0.6, 0.0), None, false));
+    }
+}
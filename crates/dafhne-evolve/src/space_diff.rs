@@ -0,0 +1,574 @@
+//! Word-by-word comparison of two trained spaces (`dafhne-evolve diff-spaces`).
+//!
+//! Random initialisation makes raw coordinates incomparable, so the old space
+//! is first aligned onto the new one with a Procrustes fit (rotation or
+//! reflection plus uniform scale) over the words both contain. Then:
+//! - displacement: distance from a word's aligned old position to its new one
+//! - neighbourhood: Jaccard overlap of its k nearest neighbours (independent
+//!   of the alignment; computed over shared words only)
+//! - connectors: added and removed patterns, and the angle between the
+//!   aligned old and new force directions of shared ones
+//! - clusters: k-means over the shared words in each space (same k, seeded by
+//!   word order, so a rigid motion changes nothing); clusters without an
+//!   exact counterpart are matched by Jaccard and reported. Threshold
+//!   clustering (`analysis::compute_clusters`) chains most of a trained space
+//!   into one cluster, which hides changes.
+
+use std::collections::{BTreeSet, HashMap};
+
+use dafhne_core::{euclidean_distance, GeometricSpace, SimpleRng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    /// Neighbours per word for the neighbourhood overlap.
+    pub k: usize,
+    /// k-means clusters per space (0 = √(words / 2)).
+    pub clusters: usize,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self { k: 5, clusters: 0 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordShift {
+    pub word: String,
+    /// Aligned old → new distance, in new-space units.
+    pub displacement: f64,
+    /// Displacement / RMS distance of the new space's words from their centroid.
+    pub relative: f64,
+    /// |old k-NN ∩ new k-NN| / |old k-NN ∪ new k-NN|.
+    pub jaccard: f64,
+    pub lost_neighbors: Vec<String>,
+    pub gained_neighbors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorShift {
+    pub connector: String,
+    /// Angle between the aligned old and the new force direction.
+    pub angle_degrees: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterChange {
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+    pub jaccard: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpaceDiff {
+    pub shared_words: usize,
+    pub added_words: Vec<String>,
+    pub removed_words: Vec<String>,
+    /// Uniform scale applied to the old space.
+    pub scale: f64,
+    /// Procrustes disparity: residual / total sum of squares, 0 = identical shape.
+    pub disparity: f64,
+    pub mean_relative_displacement: f64,
+    pub mean_jaccard: f64,
+    /// Largest relative displacement first.
+    pub words: Vec<WordShift>,
+    pub added_connectors: Vec<String>,
+    pub removed_connectors: Vec<String>,
+    /// Largest angle first.
+    pub connectors: Vec<ConnectorShift>,
+    pub clusters: usize,
+    /// Lowest Jaccard first.
+    pub cluster_changes: Vec<ClusterChange>,
+}
+
+/// Compare `old` against `new`.
+pub fn diff_spaces(old: &GeometricSpace, new: &GeometricSpace, options: &DiffOptions) -> SpaceDiff {
+    let mut shared: Vec<&String> = old.words.keys().filter(|w| new.words.contains_key(*w)).collect();
+    shared.sort();
+    let mut added_words: Vec<String> = new.words.keys().filter(|w| !old.words.contains_key(*w)).cloned().collect();
+    let mut removed_words: Vec<String> = old.words.keys().filter(|w| !new.words.contains_key(*w)).cloned().collect();
+    added_words.sort();
+    removed_words.sort();
+
+    // Pad to a common dimensionality so genomes with different dims compare
+    let d = old.dimensions.max(new.dimensions);
+    let pad = |v: &[f64]| {
+        let mut p = v.to_vec();
+        p.resize(d, 0.0);
+        p
+    };
+    let x: Vec<Vec<f64>> = shared.iter().map(|w| pad(&old.words[*w].position)).collect();
+    let y: Vec<Vec<f64>> = shared.iter().map(|w| pad(&new.words[*w].position)).collect();
+    let (xc, _) = centre(&x);
+    let (yc, y_mean) = centre(&y);
+
+    // Procrustes: minimise |s·X·R − Y| over orthogonal R and scale s
+    let mut m = vec![vec![0.0; d]; d];
+    for (xr, yr) in xc.iter().zip(&yc) {
+        for i in 0..d {
+            for j in 0..d {
+                m[i][j] += xr[i] * yr[j];
+            }
+        }
+    }
+    let (rotation, trace) = orthogonal_procrustes(&m);
+    let norm_x: f64 = xc.iter().map(|r| dot(r, r)).sum();
+    let norm_y: f64 = yc.iter().map(|r| dot(r, r)).sum();
+    let scale = if norm_x > 1e-20 { trace / norm_x } else { 1.0 };
+    let aligned: Vec<Vec<f64>> = xc
+        .iter()
+        .map(|r| rotate(r, &rotation).iter().zip(&y_mean).map(|(v, mu)| scale * v + mu).collect())
+        .collect();
+    let residual: f64 = aligned.iter().zip(&y).map(|(a, b)| squared_distance(a, b)).sum();
+    let disparity = if norm_y > 1e-20 { residual / norm_y } else { 0.0 };
+    let rms = (norm_y / shared.len().max(1) as f64).sqrt().max(1e-12);
+
+    // Neighbourhoods
+    let knn_old = nearest(&x, options.k);
+    let knn_new = nearest(&y, options.k);
+    let mut words: Vec<WordShift> = shared
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let before: BTreeSet<usize> = knn_old[i].iter().copied().collect();
+            let after: BTreeSet<usize> = knn_new[i].iter().copied().collect();
+            let union = before.union(&after).count();
+            let displacement = euclidean_distance(&aligned[i], &y[i]);
+            WordShift {
+                word: (*word).clone(),
+                displacement,
+                relative: displacement / rms,
+                jaccard: if union == 0 { 1.0 } else { before.intersection(&after).count() as f64 / union as f64 },
+                lost_neighbors: before.difference(&after).map(|&j| shared[j].clone()).collect(),
+                gained_neighbors: after.difference(&before).map(|&j| shared[j].clone()).collect(),
+            }
+        })
+        .collect();
+    let n = words.len().max(1) as f64;
+    let mean_relative_displacement = words.iter().map(|w| w.relative).sum::<f64>() / n;
+    let mean_jaccard = words.iter().map(|w| w.jaccard).sum::<f64>() / n;
+    words.sort_by(|a, b| b.relative.partial_cmp(&a.relative).unwrap_or(std::cmp::Ordering::Equal).then(a.word.cmp(&b.word)));
+
+    // Connectors
+    let old_connectors: HashMap<String, &Vec<f64>> =
+        old.connectors.iter().map(|c| (c.pattern.join(" "), &c.force_direction)).collect();
+    let new_connectors: HashMap<String, &Vec<f64>> =
+        new.connectors.iter().map(|c| (c.pattern.join(" "), &c.force_direction)).collect();
+    let mut added_connectors: Vec<String> =
+        new_connectors.keys().filter(|c| !old_connectors.contains_key(*c)).cloned().collect();
+    let mut removed_connectors: Vec<String> =
+        old_connectors.keys().filter(|c| !new_connectors.contains_key(*c)).cloned().collect();
+    added_connectors.sort();
+    removed_connectors.sort();
+    let mut connectors: Vec<ConnectorShift> = new_connectors
+        .iter()
+        .filter_map(|(pattern, after)| {
+            let before = rotate(&pad(old_connectors.get(pattern)?), &rotation);
+            let after = pad(after);
+            let norms = (dot(&before, &before) * dot(&after, &after)).sqrt();
+            let cos = if norms > 1e-20 { (dot(&before, &after) / norms).clamp(-1.0, 1.0) } else { 1.0 };
+            Some(ConnectorShift { connector: pattern.clone(), angle_degrees: cos.acos().to_degrees() })
+        })
+        .collect();
+    connectors.sort_by(|a, b| {
+        b.angle_degrees.partial_cmp(&a.angle_degrees).unwrap_or(std::cmp::Ordering::Equal).then(a.connector.cmp(&b.connector))
+    });
+
+    // Clusters
+    let names: Vec<String> = shared.iter().map(|w| (*w).clone()).collect();
+    let k = match options.clusters {
+        0 => ((shared.len() as f64 / 2.0).sqrt().round() as usize).max(1),
+        k => k,
+    };
+    let before = kmeans(&x, k);
+    let after = kmeans(&y, k);
+    let mut cluster_changes: Vec<ClusterChange> = Vec::new();
+    let best_match = |cluster: &BTreeSet<usize>, pool: &[BTreeSet<usize>]| -> (BTreeSet<usize>, f64) {
+        pool.iter()
+            .map(|other| {
+                let j = cluster.intersection(other).count() as f64 / cluster.union(other).count() as f64;
+                (other.clone(), j)
+            })
+            .fold((BTreeSet::new(), -1.0), |best, c| if c.1 > best.1 { c } else { best })
+    };
+    let label = |set: &BTreeSet<usize>| set.iter().map(|&i| names[i].clone()).collect::<Vec<_>>();
+    for cluster in &before {
+        let (other, jaccard) = best_match(cluster, &after);
+        if jaccard < 1.0 {
+            cluster_changes.push(ClusterChange { before: label(cluster), after: label(&other), jaccard });
+        }
+    }
+    for cluster in &after {
+        let (other, jaccard) = best_match(cluster, &before);
+        let listed = cluster_changes.iter().any(|c| c.after == label(cluster));
+        if jaccard < 1.0 && !listed {
+            cluster_changes.push(ClusterChange { before: label(&other), after: label(cluster), jaccard });
+        }
+    }
+    cluster_changes.sort_by(|a, b| a.jaccard.partial_cmp(&b.jaccard).unwrap_or(std::cmp::Ordering::Equal));
+
+    SpaceDiff {
+        shared_words: shared.len(),
+        added_words,
+        removed_words,
+        scale,
+        disparity,
+        mean_relative_displacement,
+        mean_jaccard,
+        words,
+        added_connectors,
+        removed_connectors,
+        connectors,
+        clusters: before.len().max(after.len()),
+        cluster_changes,
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+/// Row vector times matrix.
+fn rotate(v: &[f64], r: &[Vec<f64>]) -> Vec<f64> {
+    (0..r.len()).map(|j| v.iter().zip(r).map(|(x, row)| x * row[j]).sum()).collect()
+}
+
+/// Centred rows and the mean that was subtracted.
+fn centre(rows: &[Vec<f64>]) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let d = rows.first().map_or(0, |r| r.len());
+    let n = rows.len().max(1) as f64;
+    let mut mean = vec![0.0; d];
+    for r in rows {
+        for (m, x) in mean.iter_mut().zip(r) {
+            *m += x / n;
+        }
+    }
+    let centred = rows.iter().map(|r| r.iter().zip(&mean).map(|(x, m)| x - m).collect()).collect();
+    (centred, mean)
+}
+
+/// R = U·Vᵀ from the SVD M = U·Σ·Vᵀ, plus trace(Σ). The SVD comes from the
+/// eigendecomposition of MᵀM; directions with no singular value are filled
+/// in with Gram-Schmidt so R stays orthogonal.
+fn orthogonal_procrustes(m: &[Vec<f64>]) -> (Vec<Vec<f64>>, f64) {
+    let d = m.len();
+    let mut mtm = vec![vec![0.0; d]; d];
+    for i in 0..d {
+        for j in 0..d {
+            mtm[i][j] = (0..d).map(|k| m[k][i] * m[k][j]).sum();
+        }
+    }
+    let (values, vectors) = jacobi_eigen(mtm);
+    let largest = values.iter().copied().fold(0.0f64, f64::max).sqrt();
+
+    let mut u: Vec<Option<Vec<f64>>> = Vec::with_capacity(d);
+    let mut trace = 0.0;
+    for (value, v) in values.iter().zip(&vectors) {
+        let sigma = value.max(0.0).sqrt();
+        if sigma > 1e-9 * largest.max(1e-300) {
+            trace += sigma;
+            u.push(Some((0..d).map(|i| dot(&m[i], v) / sigma).collect()));
+        } else {
+            u.push(None);
+        }
+    }
+    let mut basis: Vec<Vec<f64>> = u.iter().flatten().cloned().collect();
+    let u: Vec<Vec<f64>> = u
+        .into_iter()
+        .map(|col| {
+            col.unwrap_or_else(|| {
+                let mut fill = vec![0.0; d];
+                for k in 0..d {
+                    let mut e = vec![0.0; d];
+                    e[k] = 1.0;
+                    for b in &basis {
+                        let p = dot(&e, b);
+                        e.iter_mut().zip(b).for_each(|(x, bi)| *x -= p * bi);
+                    }
+                    let norm = dot(&e, &e).sqrt();
+                    if norm > 1e-6 {
+                        fill = e.into_iter().map(|x| x / norm).collect();
+                        break;
+                    }
+                }
+                basis.push(fill.clone());
+                fill
+            })
+        })
+        .collect();
+
+    let mut r = vec![vec![0.0; d]; d];
+    for (ui, vi) in u.iter().zip(&vectors) {
+        for p in 0..d {
+            for q in 0..d {
+                r[p][q] += ui[p] * vi[q];
+            }
+        }
+    }
+    (r, trace)
+}
+
+/// Cyclic Jacobi eigendecomposition of a symmetric matrix: eigenvalues and
+/// their unit eigenvectors.
+fn jacobi_eigen(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = a.len();
+    let mut v: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+    let scale: f64 = a.iter().flatten().map(|x| x * x).sum::<f64>().max(1e-300);
+    for _ in 0..100 {
+        let off: f64 = (0..n).flat_map(|p| (0..n).filter(move |&q| q != p).map(move |q| (p, q))).map(|(p, q)| a[p][q] * a[p][q]).sum();
+        if off <= 1e-24 * scale {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (upper, lower) = a.split_at_mut(q);
+                for (pk, qk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    let (x, y) = (*pk, *qk);
+                    *pk = c * x - s * y;
+                    *qk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+            }
+        }
+    }
+    let values = (0..n).map(|i| a[i][i]).collect();
+    let vectors = (0..n).map(|i| (0..n).map(|k| v[k][i]).collect()).collect();
+    (values, vectors)
+}
+
+/// Indices of each row's `k` nearest other rows.
+fn nearest(rows: &[Vec<f64>], k: usize) -> Vec<Vec<usize>> {
+    rows.iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let mut dists: Vec<(usize, f64)> = rows
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(j, b)| (j, squared_distance(a, b)))
+                .collect();
+            dists.sort_by(|x, y| x.1.partial_cmp(&y.1).unwrap_or(std::cmp::Ordering::Equal).then(x.0.cmp(&y.0)));
+            dists.into_iter().take(k).map(|(j, _)| j).collect()
+        })
+        .collect()
+}
+
+/// k-means++ (fixed seed) followed by Lloyd iterations; non-empty clusters
+/// as sets of row indices.
+fn kmeans(rows: &[Vec<f64>], k: usize) -> Vec<BTreeSet<usize>> {
+    let n = rows.len();
+    let k = k.min(n);
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut rng = SimpleRng::new(42);
+    let mut centres: Vec<Vec<f64>> = vec![rows[(rng.next_u64() % n as u64) as usize].clone()];
+    while centres.len() < k {
+        let weights: Vec<f64> = rows
+            .iter()
+            .map(|r| centres.iter().map(|c| squared_distance(r, c)).fold(f64::INFINITY, f64::min))
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            break;
+        }
+        let mut pick = rng.next_f64() * total;
+        let index = weights.iter().position(|w| {
+            pick -= w;
+            pick <= 0.0
+        });
+        centres.push(rows[index.unwrap_or(n - 1)].clone());
+    }
+
+    let mut assignment = vec![usize::MAX; n];
+    for _ in 0..100 {
+        let next: Vec<usize> = rows
+            .iter()
+            .map(|r| {
+                (0..centres.len())
+                    .min_by(|&a, &b| {
+                        squared_distance(r, &centres[a]).partial_cmp(&squared_distance(r, &centres[b])).unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .unwrap_or(0)
+            })
+            .collect();
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+        for (c, centre) in centres.iter_mut().enumerate() {
+            let members: Vec<&Vec<f64>> = rows.iter().zip(&assignment).filter(|(_, &a)| a == c).map(|(r, _)| r).collect();
+            if !members.is_empty() {
+                for (d, value) in centre.iter_mut().enumerate() {
+                    *value = members.iter().map(|m| m[d]).sum::<f64>() / members.len() as f64;
+                }
+            }
+        }
+    }
+
+    let mut groups: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); centres.len()];
+    for (i, &c) in assignment.iter().enumerate() {
+        groups[c].insert(i);
+    }
+    groups.retain(|g| !g.is_empty());
+    groups.sort();
+    groups
+}
+
+/// At most `limit` names, then a count of the rest.
+fn preview(names: &[String], limit: usize) -> String {
+    if names.len() <= limit {
+        names.join(", ")
+    } else {
+        format!("{}, … (+{} more)", names[..limit].join(", "), names.len() - limit)
+    }
+}
+
+/// Print the diff; `top` limits the per-word and per-connector tables.
+pub fn print_space_diff(diff: &SpaceDiff, top: usize) {
+    println!("=== Space Diff ===");
+    println!(
+        "  Words:       {} shared, {} added, {} removed",
+        diff.shared_words,
+        diff.added_words.len(),
+        diff.removed_words.len()
+    );
+    if !diff.added_words.is_empty() {
+        println!("    added:     {}", preview(&diff.added_words, top));
+    }
+    if !diff.removed_words.is_empty() {
+        println!("    removed:   {}", preview(&diff.removed_words, top));
+    }
+    println!("  Alignment:   scale {:.4}, disparity {:.4}", diff.scale, diff.disparity);
+    println!(
+        "  Mean shift:  {:.4} (relative), mean k-NN Jaccard {:.3}",
+        diff.mean_relative_displacement, diff.mean_jaccard
+    );
+
+    println!("\n=== Most Moved Words ===");
+    println!("  {:<18} {:>9} {:>8} {:>7}  neighbours", "Word", "Shift", "Rel", "Jacc");
+    for w in diff.words.iter().take(top) {
+        let mut changes: Vec<String> = w.lost_neighbors.iter().map(|n| format!("-{}", n)).collect();
+        changes.extend(w.gained_neighbors.iter().map(|n| format!("+{}", n)));
+        println!(
+            "  {:<18} {:>9.4} {:>8.3} {:>7.2}  {}",
+            w.word,
+            w.displacement,
+            w.relative,
+            w.jaccard,
+            changes.join(" ")
+        );
+    }
+
+    println!("\n=== Connectors ===");
+    if !diff.added_connectors.is_empty() {
+        println!("  Added:   {}", preview(&diff.added_connectors, top));
+    }
+    if !diff.removed_connectors.is_empty() {
+        println!("  Removed: {}", preview(&diff.removed_connectors, top));
+    }
+    for c in diff.connectors.iter().take(top) {
+        println!("  {:<18} turned {:>6.1}°", c.connector, c.angle_degrees);
+    }
+
+    println!("\n=== Clusters ===");
+    println!("  k = {}, {} clusters without an exact counterpart", diff.clusters, diff.cluster_changes.len());
+    for change in diff.cluster_changes.iter().take(top) {
+        println!("  [{:.2}] {{{}}}", change.jaccard, preview(&change.before, top));
+        println!("       → {{{}}}", preview(&change.after, top));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dafhne_core::{Connector, WordPoint};
+
+    fn space(points: &[(&str, [f64; 3])], connectors: &[(&str, [f64; 3])]) -> GeometricSpace {
+        GeometricSpace {
+            dimensions: 3,
+            words: points
+                .iter()
+                .map(|(w, p)| (w.to_string(), WordPoint { word: w.to_string(), position: p.to_vec() }))
+                .collect(),
+            connectors: connectors
+                .iter()
+                .map(|(c, d)| Connector {
+                    pattern: vec![c.to_string()],
+                    force_direction: d.to_vec(),
+                    magnitude: 1.0,
+                    frequency: 1,
+                    uniformity: 1.0,
+                })
+                .collect(),
+            distance_stats: None,
+        }
+    }
+
+    /// 90° about z, then ×2 and shifted.
+    fn transform(p: [f64; 3]) -> [f64; 3] {
+        [-2.0 * p[1] + 1.0, 2.0 * p[0] - 3.0, 2.0 * p[2] + 0.5]
+    }
+
+    const POINTS: [(&str, [f64; 3]); 6] = [
+        ("dog", [0.0, 0.0, 0.0]),
+        ("cat", [0.2, 0.0, 0.1]),
+        ("animal", [0.1, 0.3, 0.0]),
+        ("sun", [3.0, 2.0, 1.0]),
+        ("hot", [3.2, 2.1, 1.0]),
+        ("light", [2.9, 2.3, 1.2]),
+    ];
+
+    #[test]
+    fn rigid_motion_is_no_change() {
+        let old = space(&POINTS, &[("is", [1.0, 0.0, 0.0])]);
+        let moved: Vec<(&str, [f64; 3])> = POINTS.iter().map(|(w, p)| (*w, transform(*p))).collect();
+        let new = space(&moved, &[("is", [0.0, 1.0, 0.0])]);
+        let diff = diff_spaces(&old, &new, &DiffOptions { k: 2, clusters: 2 });
+        assert!((diff.scale - 2.0).abs() < 1e-9);
+        assert!(diff.disparity < 1e-12);
+        assert!(diff.words.iter().all(|w| w.displacement < 1e-9 && w.jaccard == 1.0));
+        assert!(diff.connectors[0].angle_degrees < 1e-6);
+        assert!(diff.cluster_changes.is_empty());
+    }
+
+    #[test]
+    fn reports_moved_words_connectors_and_clusters() {
+        let old = space(&POINTS, &[("is", [1.0, 0.0, 0.0]), ("not", [0.0, 0.0, 1.0])]);
+        let mut points = POINTS.to_vec();
+        points[1].1 = [3.1, 2.2, 1.1]; // cat joins the sun cluster
+        points.push(("tree", [0.0, 1.0, 5.0]));
+        let new = space(&points, &[("is", [1.0, 0.0, 0.0]), ("can", [0.0, 1.0, 0.0])]);
+        let diff = diff_spaces(&old, &new, &DiffOptions { k: 2, clusters: 2 });
+
+        assert_eq!(diff.added_words, vec!["tree"]);
+        assert_eq!(diff.words[0].word, "cat");
+        assert!(diff.words[0].jaccard < 1.0);
+        assert_eq!((diff.added_connectors.clone(), diff.removed_connectors.clone()), (vec!["can".to_string()], vec!["not".to_string()]));
+        assert!(diff
+            .cluster_changes
+            .iter()
+            .any(|c| c.after.contains(&"cat".to_string()) && c.after.contains(&"sun".to_string())));
+    }
+}
@@ -0,0 +1,208 @@
+//! Train/holdout splits for evolution.
+//!
+//! Evolution tunes thresholds against whatever questions it is scored on, so
+//! the score that selects the final genome must come from questions the
+//! fitness function never saw:
+//! - Fixed holdout: `--holdout5` is a separate suite, scored on the same
+//!   trained dict5 engine but never fed into fitness.
+//! - Cross-validation: `--folds K` splits the dict5 suite into K folds. The
+//!   last fold is the selection fold: it is the holdout every generation and
+//!   never enters fitness, so holdout scores stay comparable across
+//!   generations. With K >= 3 one of the remaining folds also sits out of
+//!   training in rotation, so fitness never settles on a single question set.
+
+use dafhne_core::{SimpleRng, TestSuite};
+use serde::{Deserialize, Serialize};
+
+/// How the dict5 questions are divided between fitness and selection.
+#[derive(Debug, Clone)]
+pub enum SplitPlan {
+    /// No holdout: fitness and selection use the same suite (legacy behaviour).
+    None { train: TestSuite },
+    /// Separate holdout suite.
+    Fixed { train: TestSuite, holdout: TestSuite },
+    /// K folds over a question pool: the last is held out for selection,
+    /// the others rotate through training.
+    Folds { folds: Vec<TestSuite> },
+}
+
+/// The split used for one generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationSplit {
+    /// Training fold left out this generation (cross-validation with K >= 3).
+    pub fold: Option<usize>,
+    /// Fixed holdout fold scored for selection (cross-validation mode only).
+    #[serde(default)]
+    pub selection_fold: Option<usize>,
+    pub train_size: usize,
+    pub holdout_size: usize,
+}
+
+impl GenerationSplit {
+    /// Fold annotation for logs and reports, e.g. " (selection fold 4, fold 1 left out)".
+    pub fn fold_label(&self) -> String {
+        match (self.selection_fold, self.fold) {
+            (Some(sel), Some(k)) => format!(" (selection fold {}, fold {} left out)", sel, k),
+            (Some(sel), None) => format!(" (selection fold {})", sel),
+            (None, Some(k)) => format!(" (fold {})", k),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+impl SplitPlan {
+    pub fn new(pool: TestSuite, holdout: Option<TestSuite>, folds: usize, seed: u64) -> Self {
+        match (holdout, folds) {
+            (Some(_), k) if k >= 2 => {
+                panic!("--holdout5 and --folds are exclusive: folds are cut from --test5")
+            }
+            (_, k) if k >= 2 => SplitPlan::Folds {
+                folds: split_folds(&pool, k, seed),
+            },
+            (Some(holdout), _) => SplitPlan::Fixed {
+                train: pool,
+                holdout,
+            },
+            (None, _) => SplitPlan::None { train: pool },
+        }
+    }
+
+    pub fn has_holdout(&self) -> bool {
+        !matches!(self, SplitPlan::None { .. })
+    }
+
+    /// Train and holdout suites for a generation. The selection fold is fixed;
+    /// the left-out training fold rotates by generation.
+    pub fn for_generation(&self, gen: usize) -> (TestSuite, Option<TestSuite>, GenerationSplit) {
+        let (train, holdout, fold, selection_fold) = match self {
+            SplitPlan::None { train } => (train.clone(), None, None, None),
+            SplitPlan::Fixed { train, holdout } => (train.clone(), Some(holdout.clone()), None, None),
+            SplitPlan::Folds { folds } => {
+                let selection = folds.len() - 1;
+                let rotating = (selection >= 2).then(|| gen % selection);
+                let (train, holdout) = train_holdout_from_folds(folds, selection, rotating);
+                (train, Some(holdout), rotating, Some(selection))
+            }
+        };
+        let split = GenerationSplit {
+            fold,
+            selection_fold,
+            train_size: train.questions.len(),
+            holdout_size: holdout.as_ref().map(|h| h.questions.len()).unwrap_or(0),
+        };
+        (train, holdout, split)
+    }
+}
+
+/// Shuffle the pool deterministically and deal it into `k` folds.
+/// Fold sizes differ by at most one; question order inside a fold follows the pool.
+pub fn split_folds(pool: &TestSuite, k: usize, seed: u64) -> Vec<TestSuite> {
+    let k = k.max(1).min(pool.questions.len().max(1));
+    let mut order: Vec<usize> = (0..pool.questions.len()).collect();
+    let mut rng = SimpleRng::new(seed);
+    for i in (1..order.len()).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        order.swap(i, j);
+    }
+
+    let mut assignment = vec![0usize; pool.questions.len()];
+    for (slot, &idx) in order.iter().enumerate() {
+        assignment[idx] = slot % k;
+    }
+
+    (0..k)
+        .map(|fold| TestSuite {
+            questions: pool
+                .questions
+                .iter()
+                .zip(&assignment)
+                .filter(|(_, &a)| a == fold)
+                .map(|(q, _)| q.clone())
+                .collect(),
+        })
+        .collect()
+}
+
+/// Fold `k` is the holdout; the rest, in order and minus `skip`, are the training suite.
+pub fn train_holdout_from_folds(folds: &[TestSuite], k: usize, skip: Option<usize>) -> (TestSuite, TestSuite) {
+    let train = TestSuite {
+        questions: folds
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != k && Some(*i) != skip)
+            .flat_map(|(_, f)| f.questions.iter().cloned())
+            .collect(),
+    };
+    (train, folds[k].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dafhne_core::TestQuestion;
+
+    fn pool(n: usize) -> TestSuite {
+        TestSuite {
+            questions: (0..n)
+                .map(|i| TestQuestion {
+                    id: format!("Q{:02}", i),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn folds_partition_the_pool() {
+        let folds = split_folds(&pool(20), 3, 7);
+        assert_eq!(folds.len(), 3);
+        let mut ids: Vec<String> = folds
+            .iter()
+            .flat_map(|f| f.questions.iter().map(|q| q.id.clone()))
+            .collect();
+        ids.sort();
+        assert_eq!(ids, pool(20).questions.iter().map(|q| q.id.clone()).collect::<Vec<_>>());
+        assert!(folds.iter().all(|f| f.questions.len() == 6 || f.questions.len() == 7));
+        // Deterministic for a seed
+        assert_eq!(
+            folds[0].questions.iter().map(|q| &q.id).collect::<Vec<_>>(),
+            split_folds(&pool(20), 3, 7)[0].questions.iter().map(|q| &q.id).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn selection_fold_is_fixed_and_never_trained() {
+        let plan = SplitPlan::new(pool(10), None, 5, 1);
+        let (_, first, _) = plan.for_generation(0);
+        let first: Vec<String> = first.unwrap().questions.into_iter().map(|q| q.id).collect();
+        let mut left_out = Vec::new();
+        for gen in 0..8 {
+            let (train, holdout, split) = plan.for_generation(gen);
+            let holdout = holdout.unwrap();
+            assert_eq!(split.selection_fold, Some(4));
+            assert_eq!(split.fold, Some(gen % 4));
+            assert_eq!(holdout.questions.iter().map(|q| q.id.clone()).collect::<Vec<_>>(), first);
+            // Selection fold plus one rotating fold sit out of training
+            assert_eq!(train.questions.len() + holdout.questions.len(), 8);
+            assert!(holdout
+                .questions
+                .iter()
+                .all(|h| train.questions.iter().all(|t| t.id != h.id)));
+            left_out.push(split.fold.unwrap());
+        }
+        left_out.sort();
+        left_out.dedup();
+        assert_eq!(left_out, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn two_folds_train_on_the_other_fold() {
+        let plan = SplitPlan::new(pool(10), None, 2, 1);
+        for gen in 0..3 {
+            let (train, holdout, split) = plan.for_generation(gen);
+            assert_eq!(split.fold, None);
+            assert_eq!(split.selection_fold, Some(1));
+            assert_eq!(train.questions.len() + holdout.unwrap().questions.len(), 10);
+        }
+    }
+}
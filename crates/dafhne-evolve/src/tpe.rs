@@ -0,0 +1,202 @@
+//! Tree-structured Parzen estimator (TPE) surrogate optimizer.
+//!
+//! Every evaluated genome becomes a trial. Trials are split at the γ quantile
+//! of fitness into "good" and "bad"; each set gets a density model:
+//! - numeric genes: per-gene Parzen windows (Gaussian kernels on [0, 1])
+//!   mixed with a uniform prior
+//! - categorical genes: smoothed frequency counts
+//!
+//! New genomes are the best of `CANDIDATES` draws from the good density,
+//! scored by l(x) / g(x). Until `STARTUP_TRIALS` trials exist, genomes are
+//! drawn uniformly at random.
+
+use dafhne_core::SimpleRng;
+use serde::{Deserialize, Serialize};
+
+use crate::genome::{Genome, ParamRanges};
+use crate::operators::gaussian;
+use crate::optimizer::*;
+
+/// Trials drawn uniformly before the surrogate is used.
+const STARTUP_TRIALS: usize = 10;
+/// Fraction of trials (by fitness) that form the "good" density.
+const GAMMA: f64 = 0.25;
+/// Candidates drawn from the good density per proposed genome.
+const CANDIDATES: usize = 24;
+/// Smallest kernel bandwidth, in normalized units.
+const MIN_BANDWIDTH: f64 = 0.05;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trial {
+    pub x: Vec<f64>,
+    pub categories: [usize; 6],
+    pub fitness: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Tpe {
+    pub trials: Vec<Trial>,
+}
+
+/// Density model of one trial set.
+struct Parzen<'a> {
+    points: Vec<&'a Trial>,
+    bandwidth: Vec<f64>,
+    category_probs: Vec<Vec<f64>>,
+}
+
+impl<'a> Parzen<'a> {
+    fn fit(points: Vec<&'a Trial>) -> Self {
+        let k = points.len() as f64;
+        // Scott's rule per gene, floored so a collapsed set still explores
+        let bandwidth = (0..NUM_GENES)
+            .map(|d| {
+                let mean = points.iter().map(|t| t.x[d]).sum::<f64>() / k;
+                let var = points.iter().map(|t| (t.x[d] - mean).powi(2)).sum::<f64>() / k;
+                (1.06 * var.sqrt() * k.powf(-0.2)).max(MIN_BANDWIDTH)
+            })
+            .collect();
+        let category_probs = CATEGORY_SIZES
+            .iter()
+            .enumerate()
+            .map(|(c, &size)| {
+                let mut counts = vec![1.0; size];
+                for t in &points {
+                    counts[t.categories[c]] += 1.0;
+                }
+                let total: f64 = counts.iter().sum();
+                counts.into_iter().map(|n| n / total).collect()
+            })
+            .collect();
+        Self {
+            points,
+            bandwidth,
+            category_probs,
+        }
+    }
+
+    /// Log density of a candidate (numeric genes treated independently).
+    fn log_density(&self, x: &[f64], cats: &[usize; 6]) -> f64 {
+        let k = self.points.len() as f64;
+        let numeric: f64 = (0..NUM_GENES)
+            .map(|d| {
+                let h = self.bandwidth[d];
+                let kernels: f64 = self
+                    .points
+                    .iter()
+                    .map(|t| {
+                        let z = (x[d] - t.x[d]) / h;
+                        (-0.5 * z * z).exp() / (h * (2.0 * std::f64::consts::PI).sqrt())
+                    })
+                    .sum();
+                // Uniform prior on [0, 1] counts as one more point
+                ((kernels + 1.0) / (k + 1.0)).ln()
+            })
+            .sum();
+        let categorical: f64 = cats
+            .iter()
+            .enumerate()
+            .map(|(c, &i)| self.category_probs[c][i].ln())
+            .sum();
+        numeric + categorical
+    }
+
+    fn sample(&self, rng: &mut SimpleRng) -> (Vec<f64>, [usize; 6]) {
+        let center = self.points[(rng.next_u64() % self.points.len() as u64) as usize];
+        let x = (0..NUM_GENES)
+            .map(|d| (center.x[d] + self.bandwidth[d] * gaussian(rng)).clamp(0.0, 1.0))
+            .collect();
+        let cats = std::array::from_fn(|c| sample_index(&self.category_probs[c], rng));
+        (x, cats)
+    }
+}
+
+impl Tpe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn random_candidate(rng: &mut SimpleRng) -> (Vec<f64>, [usize; 6]) {
+        let x = (0..NUM_GENES).map(|_| rng.next_f64()).collect();
+        let cats = std::array::from_fn(|c| (rng.next_u64() % CATEGORY_SIZES[c] as u64) as usize);
+        (x, cats)
+    }
+}
+
+impl Optimizer for Tpe {
+    fn ask(&mut self, n: usize, rng: &mut SimpleRng, id_counter: &mut u64, generation: usize) -> Vec<Genome> {
+        let ranges = ParamRanges::default();
+        let mut sorted: Vec<&Trial> = self.trials.iter().collect();
+        sorted.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap_or(std::cmp::Ordering::Equal));
+        let n_good = ((sorted.len() as f64 * GAMMA).ceil() as usize).max(1);
+        let models = (sorted.len() >= STARTUP_TRIALS).then(|| {
+            let bad = sorted.split_off(n_good);
+            (Parzen::fit(sorted), Parzen::fit(bad))
+        });
+
+        (0..n)
+            .map(|_| {
+                let (x, cats) = match &models {
+                    None => Tpe::random_candidate(rng),
+                    Some((good, bad)) => (0..CANDIDATES)
+                        .map(|_| {
+                            let (x, cats) = good.sample(rng);
+                            let score = good.log_density(&x, &cats) - bad.log_density(&x, &cats);
+                            (score, x, cats)
+                        })
+                        .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+                        .map(|(_, x, cats)| (x, cats))
+                        .expect("CANDIDATES > 0"),
+                };
+                *id_counter += 1;
+                decode_genome(&x, &cats, &ranges, *id_counter, generation)
+            })
+            .collect()
+    }
+
+    fn tell(&mut self, evaluated: &[Genome]) {
+        let ranges = ParamRanges::default();
+        for genome in evaluated {
+            if let Some(fitness) = genome.fitness {
+                self.trials.push(Trial {
+                    x: encode_params(&genome.params, &ranges),
+                    categories: encode_categories(genome),
+                    fitness,
+                });
+            }
+        }
+    }
+
+    fn summary(&self) -> String {
+        let best = self.trials.iter().map(|t| t.fitness).fold(f64::NAN, f64::max);
+        format!("TPE: {} trials, best={:.4}", self.trials.len(), best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surrogate_beats_random_startup() {
+        // Maximize closeness of the yes_threshold gene to 0.9
+        let ranges = ParamRanges::default();
+        let score = |g: &Genome| -(encode_params(&g.params, &ranges)[6] - 0.9).abs();
+        let mut rng = SimpleRng::new(5);
+        let mut ids = 0;
+        let mut tpe = Tpe::new();
+        let mut last = Vec::new();
+        for gen in 0..12 {
+            let mut population = tpe.ask(8, &mut rng, &mut ids, gen);
+            for g in population.iter_mut() {
+                g.fitness = Some(score(g));
+            }
+            tpe.tell(&population);
+            last = population;
+        }
+        let startup: f64 = tpe.trials[..8].iter().map(|t| t.fitness).sum::<f64>() / 8.0;
+        let late: f64 = last.iter().map(|g| g.fitness.unwrap()).sum::<f64>() / last.len() as f64;
+        assert!(late > startup, "late {late} <= startup {startup}");
+        assert!(late > -0.1, "late mean distance {}", -late);
+    }
+}
@@ -272,6 +272,9 @@ fn finalize_grammar_section(
 ///
 /// [dictionary]
 /// word = "definition text"
+///
+/// [examples]            # optional
+/// word = ["example one", "example two"]
 /// ```
 pub fn parse_toml_dictionary(content: &str) -> Dictionary {
     let toml_value: toml::Value = toml::from_str(content)
@@ -282,13 +285,24 @@ pub fn parse_toml_dictionary(content: &str) -> Dictionary {
         .and_then(|v| v.as_table())
         .expect("TOML dictionary must have [dictionary] section");
 
+    let examples_table = toml_value.get("examples").and_then(|v| v.as_table());
+
     let mut entries: Vec<DictionaryEntry> = dict_table
         .iter()
         .map(|(word, definition)| {
+            let examples = examples_table
+                .and_then(|t| t.get(word))
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|e| e.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
             DictionaryEntry {
                 word: word.to_lowercase(),
                 definition: definition.as_str().unwrap_or("").to_string(),
-                examples: Vec::new(),
+                examples,
                 section: "default".to_string(),
                 is_entity: false,
             }
@@ -426,6 +440,25 @@ thing = "all that is"
         assert_eq!(dog.section, "default");
     }
 
+    #[test]
+    fn test_parse_toml_examples_table() {
+        let toml_content = r#"
+[dictionary]
+dog = "an animal"
+cat = "a small animal"
+
+[examples]
+dog = ["a dog can run", "the dog is big"]
+"#;
+
+        let dict = parse_toml_dictionary(toml_content);
+
+        let dog = dict.entries.iter().find(|e| e.word == "dog").unwrap();
+        assert_eq!(dog.examples, vec!["a dog can run", "the dog is big"]);
+        let cat = dict.entries.iter().find(|e| e.word == "cat").unwrap();
+        assert!(cat.examples.is_empty());
+    }
+
     #[test]
     fn test_parse_full_dict5_toml() {
         let content = std::fs::read_to_string("../../dictionaries/dict5.pkg.toml").unwrap();
@@ -1,11 +1,13 @@
 pub mod dictionary;
 pub mod questions;
+pub mod structured;
 
 pub use dictionary::parse_dictionary;
 pub use dictionary::parse_toml_dictionary;
 pub use dictionary::load_dictionary;
 pub use dictionary::parse_grammar_text;
-pub use questions::parse_test_questions;
+pub use questions::{parse_expected_answer, parse_test_questions};
+pub use structured::{load_test_suite, parse_test_suite_jsonl, parse_test_suite_toml};
 
 use std::collections::HashSet;
 
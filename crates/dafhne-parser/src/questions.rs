@@ -27,6 +27,7 @@ pub fn parse_test_questions(content: &str) -> TestSuite {
                     expected: answer,
                     chain: current_chain.clone(),
                     category: current_category.clone(),
+                    ..Default::default()
                 });
                 current_question.clear();
                 current_chain.clear();
@@ -44,6 +45,7 @@ pub fn parse_test_questions(content: &str) -> TestSuite {
                     expected: answer,
                     chain: current_chain.clone(),
                     category: current_category.clone(),
+                    ..Default::default()
                 });
                 current_question.clear();
                 current_chain.clear();
@@ -66,6 +68,7 @@ pub fn parse_test_questions(content: &str) -> TestSuite {
                     expected: answer,
                     chain: current_chain.clone(),
                     category: current_category.clone(),
+                    ..Default::default()
                 });
                 current_question.clear();
                 current_chain.clear();
@@ -102,13 +105,15 @@ pub fn parse_test_questions(content: &str) -> TestSuite {
             expected: answer,
             chain: current_chain,
             category: current_category,
+            ..Default::default()
         });
     }
 
     TestSuite { questions }
 }
 
-fn parse_expected_answer(text: &str) -> ExpectedAnswer {
+/// Parse an expected answer: Yes, No, I don't know, or an expected word.
+pub fn parse_expected_answer(text: &str) -> ExpectedAnswer {
     let lower = text.to_lowercase();
     match lower.as_str() {
         "yes" => ExpectedAnswer::Yes,
@@ -0,0 +1,183 @@
+use serde::Deserialize;
+
+use dafhne_core::{Evidence, TestQuestion, TestSuite};
+
+use crate::questions::{parse_expected_answer, parse_test_questions};
+
+/// One question in a structured (TOML or JSONL) test suite.
+///
+/// ```toml
+/// [[question]]
+/// id = "Q19"
+/// question = "What is a cat?"
+/// answers = ["a mammal", "an animal"]   # or: answer = "Yes"
+/// category = "PROPERTY QUERY"
+/// tags = ["what-is"]
+/// difficulty = 2
+/// evidence = { chain = true }
+/// ```
+#[derive(Debug, Deserialize)]
+struct StructuredQuestion {
+    id: String,
+    question: String,
+    #[serde(default)]
+    answer: Option<String>,
+    #[serde(default)]
+    answers: Vec<String>,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    chain: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    difficulty: Option<u32>,
+    #[serde(default)]
+    evidence: Option<Evidence>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StructuredSuite {
+    #[serde(default, rename = "question")]
+    questions: Vec<StructuredQuestion>,
+}
+
+impl StructuredQuestion {
+    /// First listed answer is the expected one; the rest are alternatives.
+    fn into_test_question(self) -> Result<TestQuestion, String> {
+        let mut answers: Vec<String> = self.answer.into_iter().collect();
+        answers.extend(self.answers);
+        if answers.is_empty() {
+            return Err(format!("question {} has no answer", self.id));
+        }
+        let mut parsed = answers.iter().map(|a| parse_expected_answer(a.trim()));
+        let expected = parsed.next().unwrap_or_default();
+        let alternatives = parsed.collect();
+
+        Ok(TestQuestion {
+            id: self.id,
+            question: self.question,
+            expected,
+            chain: self.chain,
+            category: self.category,
+            alternatives,
+            evidence: self.evidence,
+            tags: self.tags,
+            difficulty: self.difficulty,
+        })
+    }
+}
+
+/// Parse a TOML test suite made of `[[question]]` tables.
+pub fn parse_test_suite_toml(content: &str) -> Result<TestSuite, String> {
+    let suite: StructuredSuite =
+        toml::from_str(content).map_err(|e| format!("invalid TOML test suite: {}", e))?;
+    let questions = suite
+        .questions
+        .into_iter()
+        .map(StructuredQuestion::into_test_question)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(TestSuite { questions })
+}
+
+/// Parse a JSON Lines test suite: one question object per line.
+pub fn parse_test_suite_jsonl(content: &str) -> Result<TestSuite, String> {
+    let mut questions = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let q: StructuredQuestion = serde_json::from_str(line)
+            .map_err(|e| format!("line {}: invalid question: {}", i + 1, e))?;
+        questions.push(q.into_test_question()?);
+    }
+    Ok(TestSuite { questions })
+}
+
+/// Load a test suite from a file path, auto-detecting format by extension.
+/// - `.toml`  → parse_test_suite_toml()
+/// - `.jsonl` → parse_test_suite_jsonl()
+/// - everything else → parse_test_questions() (markdown)
+pub fn load_test_suite(path: impl AsRef<std::path::Path>) -> std::io::Result<TestSuite> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path)?;
+
+    let parsed = match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => parse_test_suite_toml(&content),
+        Some("jsonl") => parse_test_suite_jsonl(&content),
+        _ => Ok(parse_test_questions(&content)),
+    };
+
+    parsed.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dafhne_core::ExpectedAnswer;
+
+    #[test]
+    fn test_parse_toml_suite() {
+        let content = r#"
+[[question]]
+id = "Q01"
+question = "Is a dog a mammal?"
+answer = "Yes"
+category = "DIRECT LOOKUP"
+tags = ["is-a"]
+difficulty = 1
+evidence = { chain = true }
+
+[[question]]
+id = "Q19"
+question = "What is a cat?"
+answers = ["a mammal", "An Animal"]
+"#;
+        let suite = parse_test_suite_toml(content).unwrap();
+        assert_eq!(suite.questions.len(), 2);
+
+        let q1 = &suite.questions[0];
+        assert_eq!(q1.expected, ExpectedAnswer::Yes);
+        assert_eq!(q1.tags, vec!["is-a"]);
+        assert_eq!(q1.difficulty, Some(1));
+        assert!(q1.evidence.as_ref().unwrap().chain);
+
+        let q19 = &suite.questions[1];
+        assert_eq!(q19.expected, ExpectedAnswer::Word("a mammal".to_string()));
+        assert_eq!(q19.alternatives, vec![ExpectedAnswer::Word("an animal".to_string())]);
+        assert!(q19.evidence.is_none());
+    }
+
+    #[test]
+    fn test_parse_jsonl_suite() {
+        let content = concat!(
+            r#"{"id":"Q15","question":"What is the name of the sun?","answer":"I don't know"}"#,
+            "\n\n",
+            r#"{"id":"Q11","question":"Is a plant an animal?","answer":"No","evidence":{"connector":"is"}}"#,
+            "\n",
+        );
+        let suite = parse_test_suite_jsonl(content).unwrap();
+        assert_eq!(suite.questions[0].expected, ExpectedAnswer::IDontKnow);
+        assert_eq!(
+            suite.questions[1].evidence.as_ref().unwrap().connector.as_deref(),
+            Some("is")
+        );
+    }
+
+    #[test]
+    fn test_missing_answer_is_error() {
+        let err = parse_test_suite_toml("[[question]]\nid = \"Q1\"\nquestion = \"Is it?\"\n").unwrap_err();
+        assert!(err.contains("Q1"));
+    }
+
+    #[test]
+    fn test_load_structured_suite_file() {
+        let suite = load_test_suite("../../dictionaries/dict12_test.toml").unwrap();
+        let md = load_test_suite("../../dictionaries/dict12_test.md").unwrap();
+        assert_eq!(suite.questions.len(), md.questions.len());
+        for (s, m) in suite.questions.iter().zip(&md.questions) {
+            assert_eq!(s.id, m.id);
+            assert_eq!(s.expected, m.expected);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::service::{DafhneService, ReloadReport};
+
+// ─── Request / Response types ────────────────────────────────
+
+#[derive(Deserialize)]
+pub struct ReloadParams {
+    /// Retrain every model, changed or not.
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Serialize)]
+struct ReloadResponse {
+    #[serde(flatten)]
+    report: ReloadReport,
+    models: Vec<ModelVersion>,
+}
+
+#[derive(Serialize)]
+struct ModelVersion {
+    id: String,
+    version: u64,
+    digest: String,
+    loaded_at: String,
+}
+
+// ─── Routes ──────────────────────────────────────────────────
+
+pub fn routes() -> Router<Arc<DafhneService>> {
+    Router::new()
+        .route("/admin/reload", post(reload))
+}
+
+// ─── Handlers ────────────────────────────────────────────────
+
+/// Re-read the models config and retrain changed models (all with `?force=true`).
+async fn reload(
+    State(svc): State<Arc<DafhneService>>,
+    Query(params): Query<ReloadParams>,
+) -> impl IntoResponse {
+    let worker = svc.clone();
+    let result = tokio::task::spawn_blocking(move || worker.reload(params.force)).await;
+    match result {
+        Ok(Ok(report)) => {
+            log_report(&report);
+            let models = svc.models().iter()
+                .map(|m| ModelVersion {
+                    id: m.id.clone(),
+                    version: m.version,
+                    digest: m.digest.clone(),
+                    loaded_at: m.loaded_at.to_rfc3339(),
+                })
+                .collect();
+            Json(ReloadResponse { report, models }).into_response()
+        }
+        Ok(Err(e)) => (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e }))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": format!("reload failed: {}", e) })),
+        )
+            .into_response(),
+    }
+}
+
+// ─── Background Watcher ──────────────────────────────────────
+
+/// Poll the models config and every file the models read, retraining and
+/// swapping in changed models. Serving continues from the old models while
+/// the new ones train.
+pub fn spawn_watcher(svc: Arc<DafhneService>, every: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        interval.tick().await;
+        let mut last_error: Option<String> = None;
+        loop {
+            interval.tick().await;
+            let worker = svc.clone();
+            let error = match tokio::task::spawn_blocking(move || worker.reload(false)).await {
+                Ok(Ok(report)) => {
+                    log_report(&report);
+                    None
+                }
+                Ok(Err(e)) => Some(e),
+                Err(e) => Some(format!("reload failed: {}", e)),
+            };
+            // Report a broken config once, not on every tick
+            if error.is_some() && error != last_error {
+                tracing::warn!("Reload skipped: {}", error.as_deref().unwrap_or_default());
+            }
+            last_error = error;
+        }
+    });
+}
+
+fn log_report(report: &ReloadReport) {
+    if report.changed() {
+        tracing::info!(
+            "Reload: loaded {:?}, removed {:?}, failed {:?}",
+            report.loaded,
+            report.removed,
+            report.failed
+        );
+    }
+}
@@ -0,0 +1,509 @@
+//! Access control for the HTTP server: API keys with scopes, per-key rate
+//! limits, request body limits and CORS origins. Configured with
+//! `--auth-config` and/or the `--api-key`, `--anonymous`, `--cors-origin`,
+//! `--rate-limit` and `--max-body-bytes` flags (flags win over the file).
+//!
+//! ```toml
+//! cors_origins = ["https://chat.example.com"]   # "*": any origin
+//! rate_limit = 120                # requests per minute per key (0: unlimited)
+//! max_body_bytes = 2097152
+//! anonymous = ["read"]            # scopes of requests without a key (default: none)
+//!
+//! [[key]]
+//! name = "agent"
+//! key_env = "DAFHNE_AGENT_KEY"    # or key = "..."
+//! scopes = ["read", "teach"]      # read | teach | admin (default: all three)
+//! rate_limit = 600                # overrides the default above
+//! ```
+//!
+//! Clients send the key as `Authorization: Bearer <key>` or `X-API-Key: <key>`.
+//! Browsers cannot set headers on a WebSocket, so the upgrade at `WS_PATH`
+//! alone also accepts `?api_key=<key>` (URL-encoded); anywhere else a key in
+//! the query string would end up in access and proxy logs. `read` covers the
+//! chat, native, batch, WebSocket and MCP endpoints, `teach` the MCP
+//! `dafhne_define_word` tool and `admin` `/admin/*`. The chat page itself is
+//! public. With no key configured every request gets every scope, as before.
+//!
+//! Every request takes one token from the caller's rate-limit bucket. A batch
+//! takes one more per question after the first and a WebSocket one per
+//! `ask`, through the `Quota` that `guard` attaches to the request.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http_body_util::Limited;
+use serde::Deserialize;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::dafhne::ApiError;
+
+/// axum's own default body limit.
+const DEFAULT_MAX_BODY: usize = 2 * 1024 * 1024;
+
+/// Rate-limit buckets kept before idle ones are dropped.
+const MAX_BUCKETS: usize = 10_000;
+
+/// The one path that takes its key from the query string.
+const WS_PATH: &str = "/v1/dafhne/ws";
+
+// ─── Configuration ───────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Ask questions and read models.
+    Read,
+    /// Define words (MCP `dafhne_define_word`, WebSocket `teach`).
+    Teach,
+    /// Reload models (`/admin/*`).
+    Admin,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 3] = [Scope::Read, Scope::Teach, Scope::Admin];
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    #[serde(default)]
+    pub cors_origins: Vec<String>,
+    /// Requests per minute per key, or per address without a key (0: unlimited).
+    #[serde(default)]
+    pub rate_limit: Option<u32>,
+    #[serde(default)]
+    pub max_body_bytes: Option<usize>,
+    /// Scopes of requests without a key once keys are configured.
+    #[serde(default)]
+    pub anonymous: Option<Vec<Scope>>,
+    #[serde(default, rename = "key")]
+    pub keys: Vec<KeyConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyConfig {
+    pub name: String,
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Environment variable holding the key, to keep it out of the file.
+    #[serde(default)]
+    pub key_env: Option<String>,
+    #[serde(default = "all_scopes")]
+    pub scopes: Vec<Scope>,
+    #[serde(default)]
+    pub rate_limit: Option<u32>,
+    #[serde(default)]
+    pub max_body_bytes: Option<usize>,
+}
+
+fn all_scopes() -> Vec<Scope> {
+    Scope::ALL.to_vec()
+}
+
+impl AuthConfig {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| format!("cannot read auth config {:?}: {}", path, e))?;
+        toml::from_str(&content).map_err(|e| format!("invalid auth config {:?}: {}", path, e))
+    }
+}
+
+// ─── Access ──────────────────────────────────────────────────
+
+/// Who a request comes from and what it may do; added to every request's
+/// extensions by `guard`.
+#[derive(Debug, Clone)]
+pub struct Access {
+    /// The key's name; `None` for requests without a key.
+    pub key: Option<String>,
+    scopes: Vec<Scope>,
+    rate_limit: u32,
+    pub max_body: usize,
+}
+
+impl Access {
+    pub fn allows(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// The caller's rate-limit bucket, for handlers that answer more than one
+/// question per request; added to every request's extensions by `guard`.
+#[derive(Clone)]
+pub struct Quota {
+    auth: Arc<Auth>,
+    bucket: String,
+    per_minute: u32,
+}
+
+impl Quota {
+    /// Take a token; on failure, the rate-limit error to report.
+    pub fn take(&self) -> Result<(), RateLimited> {
+        self.auth
+            .take_token(&self.bucket, self.per_minute, Instant::now())
+            .map_err(|wait| RateLimited {
+                retry_after: wait.as_secs_f64().ceil().max(1.0) as u64,
+            })
+    }
+}
+
+#[cfg(test)]
+impl Quota {
+    /// A quota that never runs dry, for handler tests.
+    pub fn unlimited() -> Self {
+        Quota {
+            auth: Arc::new(Auth::new(AuthConfig::default()).unwrap()),
+            bucket: "test".to_string(),
+            per_minute: 0,
+        }
+    }
+}
+
+/// Out of tokens; `retry_after` seconds until the next one.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimited {
+    pub retry_after: u64,
+}
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "rate limit exceeded; retry in {} s", self.retry_after)
+    }
+}
+
+struct ApiKey {
+    secret: String,
+    access: Access,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Resolved access settings shared by every request.
+pub struct Auth {
+    keys: Vec<ApiKey>,
+    anonymous: Access,
+    default_max_body: usize,
+    cors_origins: Vec<String>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl Auth {
+    /// Resolve keys (reading `key_env` variables) and check the config.
+    pub fn new(config: AuthConfig) -> Result<Self, String> {
+        let rate_limit = config.rate_limit.unwrap_or(0);
+        let max_body = config.max_body_bytes.unwrap_or(DEFAULT_MAX_BODY);
+        let mut keys: Vec<ApiKey> = Vec::new();
+        for k in config.keys {
+            let secret = match (k.key, &k.key_env) {
+                (Some(key), None) => key,
+                (None, Some(var)) => {
+                    std::env::var(var).map_err(|_| format!("key '{}': environment variable {} is not set", k.name, var))?
+                }
+                _ => return Err(format!("key '{}' needs exactly one of key and key_env", k.name)),
+            };
+            if secret.trim().is_empty() {
+                return Err(format!("key '{}' is empty", k.name));
+            }
+            if keys.iter().any(|o| o.access.key.as_deref() == Some(k.name.as_str())) {
+                return Err(format!("key '{}' is declared twice", k.name));
+            }
+            if keys.iter().any(|o| o.secret == secret) {
+                return Err(format!("key '{}' reuses another key's secret", k.name));
+            }
+            keys.push(ApiKey {
+                secret,
+                access: Access {
+                    key: Some(k.name),
+                    scopes: k.scopes,
+                    rate_limit: k.rate_limit.unwrap_or(rate_limit),
+                    max_body: k.max_body_bytes.unwrap_or(max_body),
+                },
+            });
+        }
+        let anonymous = Access {
+            key: None,
+            // An open server (no keys) keeps working as it always has
+            scopes: match (config.anonymous, keys.is_empty()) {
+                (Some(scopes), _) => scopes,
+                (None, true) => all_scopes(),
+                (None, false) => Vec::new(),
+            },
+            rate_limit,
+            max_body,
+        };
+        Ok(Auth {
+            keys,
+            anonymous,
+            default_max_body: max_body,
+            cors_origins: config.cors_origins,
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// No key configured and anonymous requests may do everything.
+    pub fn is_open(&self) -> bool {
+        self.keys.is_empty() && Scope::ALL.iter().all(|s| self.anonymous.allows(*s))
+    }
+
+    pub fn key_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// The largest body any caller may send, for axum's `DefaultBodyLimit`;
+    /// `guard` holds each caller to its own limit.
+    pub fn body_limit(&self) -> usize {
+        self.keys.iter().map(|k| k.access.max_body).fold(self.default_max_body, usize::max)
+    }
+
+    /// CORS for the configured origins; `None` (same-origin only) without any.
+    pub fn cors_layer(&self) -> Result<Option<CorsLayer>, String> {
+        if self.cors_origins.is_empty() {
+            return Ok(None);
+        }
+        if self.cors_origins.iter().any(|o| o == "*") {
+            return Ok(Some(CorsLayer::permissive()));
+        }
+        let origins = self
+            .cors_origins
+            .iter()
+            .map(|o| HeaderValue::from_str(o).map_err(|_| format!("invalid CORS origin '{}'", o)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(
+            CorsLayer::new()
+                .allow_origin(AllowOrigin::list(origins))
+                .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+                .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, "x-api-key".parse().unwrap()]),
+        ))
+    }
+
+    /// The caller's access from its key: `Ok(None)` without one, `Err` for
+    /// an unknown key. `query` is only consulted for the WebSocket upgrade.
+    fn authenticate(&self, headers: &HeaderMap, path: &str, query: Option<&str>) -> Result<Option<&Access>, ()> {
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer ").or_else(|| v.strip_prefix("bearer ")))
+            .map(str::to_string);
+        let header_key = headers.get("x-api-key").and_then(|v| v.to_str().ok()).map(str::to_string);
+        let query_key = query.filter(|_| path == WS_PATH).and_then(|q| {
+            form_urlencoded::parse(q.as_bytes()).find_map(|(name, value)| (name == "api_key").then(|| value.into_owned()))
+        });
+        let Some(presented) = bearer.or(header_key).or(query_key) else {
+            return Ok(None);
+        };
+        let presented = presented.trim();
+        // Check every key so timing does not tell how many were tried
+        let mut found = None;
+        for key in &self.keys {
+            if same_key(&key.secret, presented) {
+                found = Some(&key.access);
+            }
+        }
+        found.map(Some).ok_or(())
+    }
+
+    /// Take a token from `bucket` (refilled at `per_minute`); on failure, how
+    /// long until the next one.
+    fn take_token(&self, bucket: &str, per_minute: u32, now: Instant) -> Result<(), Duration> {
+        if per_minute == 0 {
+            return Ok(());
+        }
+        let capacity = per_minute as f64;
+        let per_second = capacity / 60.0;
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_BUCKETS && !buckets.contains_key(bucket) {
+            // Idle for a minute means full again; nothing is lost by forgetting it
+            buckets.retain(|_, b| now.duration_since(b.updated) < Duration::from_secs(60));
+        }
+        let b = buckets.entry(bucket.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        b.tokens = (b.tokens + now.duration_since(b.updated).as_secs_f64() * per_second).min(capacity);
+        b.updated = now;
+        if b.tokens >= 1.0 {
+            b.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - b.tokens) / per_second))
+        }
+    }
+}
+
+/// Compare without stopping at the first differing byte.
+fn same_key(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// The scope a path needs; `None` for the public chat page.
+fn required_scope(path: &str) -> Option<Scope> {
+    match path {
+        "/" | "/chat" => None,
+        p if p == "/admin" || p.starts_with("/admin/") => Some(Scope::Admin),
+        _ => Some(Scope::Read),
+    }
+}
+
+// ─── Middleware ──────────────────────────────────────────────
+
+/// Authenticate, check the path's scope, the body size and the rate limit,
+/// then pass the request on with its `Access`.
+pub async fn guard(State(auth): State<Arc<Auth>>, req: Request, next: Next) -> Response {
+    let access = match auth.authenticate(req.headers(), req.uri().path(), req.uri().query()) {
+        Ok(Some(access)) => access.clone(),
+        Ok(None) => auth.anonymous.clone(),
+        Err(()) => return unauthorized("invalid API key"),
+    };
+
+    if let Some(scope) = required_scope(req.uri().path()) {
+        if !access.allows(scope) {
+            let message = format!("this endpoint needs the '{}' scope", scope_name(scope));
+            return match access.key {
+                None => unauthorized(&message),
+                Some(_) => ApiError::new(StatusCode::FORBIDDEN, message).into_response(),
+            };
+        }
+    }
+
+    let length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if length.is_some_and(|l| l > access.max_body) {
+        return ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("request body is larger than {} bytes", access.max_body),
+        )
+        .into_response();
+    }
+    // Content-Length is only a claim, and chunked bodies have none: cut the
+    // stream off at the limit too (extractors then answer 413)
+    let max_body = access.max_body;
+    let mut req = req.map(|body| Body::new(Limited::new(body, max_body)));
+
+    let bucket = match (&access.key, req.extensions().get::<ConnectInfo<SocketAddr>>()) {
+        (Some(name), _) => format!("key:{}", name),
+        (None, Some(ConnectInfo(addr))) => format!("ip:{}", addr.ip()),
+        (None, None) => "anonymous".to_string(),
+    };
+    let quota = Quota {
+        auth: auth.clone(),
+        bucket,
+        per_minute: access.rate_limit,
+    };
+    if let Err(limited) = quota.take() {
+        let mut response =
+            ApiError::new(StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded".to_string()).into_response();
+        response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(limited.retry_after));
+        return response;
+    }
+
+    req.extensions_mut().insert(access);
+    req.extensions_mut().insert(quota);
+    next.run(req).await
+}
+
+fn unauthorized(message: &str) -> Response {
+    let mut response = ApiError::new(StatusCode::UNAUTHORIZED, message.to_string()).into_response();
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    response
+}
+
+fn scope_name(scope: Scope) -> &'static str {
+    match scope {
+        Scope::Read => "read",
+        Scope::Teach => "teach",
+        Scope::Admin => "admin",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_scopes_and_rate_limits() {
+        let config: AuthConfig = toml::from_str(
+            r#"
+            rate_limit = 2
+            anonymous = ["read"]
+
+            [[key]]
+            name = "agent"
+            key = "s3cret"
+            scopes = ["read", "teach"]
+
+            [[key]]
+            name = "ops"
+            key = "0ps"
+            rate_limit = 0
+            "#,
+        )
+        .unwrap();
+        let auth = Auth::new(config).unwrap();
+        assert!(!auth.is_open());
+
+        let mut headers = HeaderMap::new();
+        assert!(auth.authenticate(&headers, "/v1/chat/completions", None).unwrap().is_none());
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer s3cret"));
+        let agent = auth.authenticate(&headers, "/v1/chat/completions", None).unwrap().unwrap();
+        assert_eq!(agent.key.as_deref(), Some("agent"));
+        assert!(agent.allows(Scope::Teach) && !agent.allows(Scope::Admin));
+        let ops = auth.authenticate(&HeaderMap::new(), WS_PATH, Some("x=1&api_key=0ps")).unwrap().unwrap();
+        assert!(Scope::ALL.iter().all(|s| ops.allows(*s)));
+        assert!(auth.authenticate(&HeaderMap::new(), WS_PATH, Some("api_key=nope")).is_err());
+        // Query keys only count on the WebSocket upgrade
+        assert!(auth.authenticate(&HeaderMap::new(), "/v1/dafhne/ask", Some("api_key=0ps")).unwrap().is_none());
+
+        assert_eq!(required_scope("/chat"), None);
+        assert_eq!(required_scope("/admin/reload"), Some(Scope::Admin));
+        assert_eq!(required_scope("/administrator"), Some(Scope::Read));
+
+        // Two per minute, then one more every 30 seconds
+        let start = Instant::now();
+        assert!(auth.take_token("key:agent", 2, start).is_ok());
+        assert!(auth.take_token("key:agent", 2, start).is_ok());
+        let wait = auth.take_token("key:agent", 2, start).unwrap_err();
+        assert_eq!(wait.as_secs(), 30);
+        assert!(auth.take_token("key:agent", 2, start + Duration::from_secs(30)).is_ok());
+        assert!(auth.take_token("key:ops", 0, start).is_ok());
+
+        // Handlers charge per question through the request's quota
+        let auth = Arc::new(auth);
+        let quota = Quota {
+            auth: auth.clone(),
+            bucket: "key:agent2".to_string(),
+            per_minute: 1,
+        };
+        assert!(quota.take().is_ok());
+        assert_eq!(quota.take().unwrap_err().retry_after, 60);
+        assert!(quota.clone().take().is_err());
+
+        // Query keys arrive URL-encoded (encodeURIComponent in the chat page)
+        let encoded: AuthConfig = toml::from_str("[[key]]\nname = \"b\"\nkey = \"a+b/c=%d\"\n").unwrap();
+        let encoded = Auth::new(encoded).unwrap();
+        let query = Some("api_key=a%2Bb%2Fc%3D%25d");
+        assert!(encoded.authenticate(&HeaderMap::new(), WS_PATH, query).unwrap().is_some());
+
+        // Without keys nothing changes for existing deployments
+        assert!(Auth::new(AuthConfig::default()).unwrap().is_open());
+        let twice: AuthConfig =
+            toml::from_str("[[key]]\nname = \"a\"\nkey = \"x\"\n[[key]]\nname = \"a\"\nkey = \"y\"\n").unwrap();
+        assert!(Auth::new(twice).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+//! Batch questions: `POST /v1/dafhne/batch` and `dafhne-server --batch`.
+//!
+//! Answers stream back as JSONL, one line per question in input order,
+//! followed by a summary line. Questions with an expected answer are scored
+//! with dafhne-eval's rules (answer match plus evidence requirements).
+
+use std::convert::Infallible;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::Extension;
+use axum::response::Response;
+use axum::routing::post;
+use axum::{Json, Router};
+use dafhne_core::{Breakdown, FitnessReport, TestQuestion};
+use dafhne_parser::{parse_expected_answer, parse_test_questions, parse_test_suite_jsonl, parse_test_suite_toml};
+use serde::{Deserialize, Serialize};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt as _;
+
+use crate::auth::Quota;
+use crate::dafhne::{default_model, find_model, ApiError};
+use crate::service::{DafhneModel, DafhneService, StructuredAnswer};
+
+// ─── Request / Response types ────────────────────────────────
+
+#[derive(Deserialize)]
+pub struct BatchRequest {
+    #[serde(default = "default_model")]
+    model: String,
+    #[serde(default)]
+    questions: Vec<BatchQuestion>,
+    /// A test suite in the markdown format (`**Q01**: ...` / `**A**: ...`).
+    #[serde(default)]
+    suite: Option<String>,
+    /// Score questions that have an expected answer.
+    #[serde(default = "default_score")]
+    score: bool,
+}
+
+fn default_score() -> bool {
+    true
+}
+
+/// A bare question, or a question with an id and an expected answer.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum BatchQuestion {
+    Text(String),
+    Item {
+        #[serde(default)]
+        id: Option<String>,
+        question: String,
+        #[serde(default)]
+        expected: Option<String>,
+    },
+}
+
+/// A question to answer; `scored` when it has an expected answer to check.
+pub struct BatchItem {
+    question: TestQuestion,
+    scored: bool,
+}
+
+#[derive(Serialize)]
+struct BatchLine<'a> {
+    index: usize,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    id: &'a str,
+    question: &'a str,
+    #[serde(flatten)]
+    answer: StructuredAnswer,
+    elapsed_us: u64,
+    #[serde(flatten)]
+    score: Option<LineScore>,
+}
+
+#[derive(Serialize)]
+struct LineScore {
+    expected: String,
+    correct: bool,
+    evidence_missing: bool,
+}
+
+/// Ends the stream early when the caller runs out of rate-limit tokens.
+#[derive(Serialize)]
+struct LimitLine {
+    index: usize,
+    error: String,
+    retry_after: u64,
+}
+
+#[derive(Serialize)]
+struct SummaryLine {
+    summary: BatchSummary,
+}
+
+#[derive(Serialize)]
+struct BatchSummary {
+    model: String,
+    questions: usize,
+    scored: usize,
+    elapsed_ms: u64,
+    #[serde(flatten)]
+    report: Option<ScoreSummary>,
+}
+
+/// `FitnessReport` without the per-question results, which the lines carry.
+#[derive(Serialize)]
+struct ScoreSummary {
+    total_correct: usize,
+    accuracy: f64,
+    honesty: f64,
+    fitness: f64,
+    breakdown: Breakdown,
+}
+
+impl From<FitnessReport> for ScoreSummary {
+    fn from(report: FitnessReport) -> Self {
+        ScoreSummary {
+            total_correct: report.total_correct,
+            accuracy: report.accuracy,
+            honesty: report.honesty,
+            fitness: report.fitness,
+            breakdown: report.breakdown,
+        }
+    }
+}
+
+impl BatchRequest {
+    fn into_items(self) -> Result<Vec<BatchItem>, String> {
+        let mut items = match self.suite {
+            Some(_) if !self.questions.is_empty() => {
+                return Err("give either questions or suite, not both".to_string())
+            }
+            Some(suite) => suite_items(parse_test_questions(&suite).questions),
+            None => self
+                .questions
+                .into_iter()
+                .enumerate()
+                .map(|(i, q)| match q {
+                    BatchQuestion::Text(question) => plain_item(i, question),
+                    BatchQuestion::Item { id, question, expected } => BatchItem {
+                        scored: expected.is_some(),
+                        question: TestQuestion {
+                            id: id.unwrap_or_else(|| (i + 1).to_string()),
+                            question,
+                            expected: expected.map(|e| parse_expected_answer(e.trim())).unwrap_or_default(),
+                            ..Default::default()
+                        },
+                    },
+                })
+                .collect(),
+        };
+        if items.is_empty() {
+            return Err("no questions given".to_string());
+        }
+        if !self.score {
+            items.iter_mut().for_each(|item| item.scored = false);
+        }
+        Ok(items)
+    }
+}
+
+fn suite_items(questions: Vec<TestQuestion>) -> Vec<BatchItem> {
+    questions
+        .into_iter()
+        .map(|question| BatchItem { question, scored: true })
+        .collect()
+}
+
+fn plain_item(index: usize, question: String) -> BatchItem {
+    BatchItem {
+        question: TestQuestion {
+            id: (index + 1).to_string(),
+            question,
+            ..Default::default()
+        },
+        scored: false,
+    }
+}
+
+/// Questions from a file's content: a `.toml` or `.jsonl` structured suite,
+/// a markdown suite, or otherwise one question per non-empty line (unscored).
+pub fn parse_items(content: &str, extension: Option<&str>) -> Result<Vec<BatchItem>, String> {
+    let suite = match extension {
+        Some("toml") => parse_test_suite_toml(content)?,
+        Some("jsonl") => parse_test_suite_jsonl(content)?,
+        _ => parse_test_questions(content),
+    };
+    if !suite.questions.is_empty() {
+        return Ok(suite_items(suite.questions));
+    }
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .enumerate()
+        .map(|(i, line)| plain_item(i, line.to_string()))
+        .collect())
+}
+
+// ─── Routes ──────────────────────────────────────────────────
+
+pub fn routes() -> Router<Arc<DafhneService>> {
+    Router::new().route("/v1/dafhne/batch", post(batch))
+}
+
+// ─── Handlers ────────────────────────────────────────────────
+
+/// Answer many questions, streaming one JSON line per answer.
+async fn batch(
+    State(svc): State<Arc<DafhneService>>,
+    Extension(quota): Extension<Quota>,
+    Json(req): Json<BatchRequest>,
+) -> Result<Response, ApiError> {
+    let model = find_model(&svc, &req.model)?;
+    let items = req.into_items().map_err(ApiError::bad_request)?;
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<String>(64);
+    // Stops early when the client disconnects and the receiver is dropped
+    tokio::task::spawn_blocking(move || run(&model, &items, Some(&quota), |line| tx.blocking_send(line).is_ok()));
+
+    let body = ReceiverStream::new(rx).map(|mut line| {
+        line.push('\n');
+        Ok::<_, Infallible>(line)
+    });
+    Ok(Response::builder()
+        .header("content-type", "application/x-ndjson")
+        .body(Body::from_stream(body))
+        .expect("Failed to build batch response"))
+}
+
+/// Answer `items` in order, handing each JSON line to `emit`, then the
+/// summary line. Stops as soon as `emit` returns false. With a `quota`, every
+/// question after the first (which the request itself paid for) takes a
+/// token; running out ends the stream with a `LimitLine` instead of the summary.
+pub fn run(model: &DafhneModel, items: &[BatchItem], quota: Option<&Quota>, mut emit: impl FnMut(String) -> bool) {
+    let start = Instant::now();
+    let mut results = Vec::new();
+
+    for (index, item) in items.iter().enumerate() {
+        if let Some(Err(limited)) = quota.filter(|_| index > 0).map(Quota::take) {
+            let line = LimitLine {
+                index,
+                error: limited.to_string(),
+                retry_after: limited.retry_after,
+            };
+            emit(serde_json::to_string(&line).expect("Failed to serialize batch line"));
+            return;
+        }
+        let q = &item.question;
+        let asked = Instant::now();
+        let answer = model.ask(&q.question, false);
+        let elapsed_us = asked.elapsed().as_micros() as u64;
+
+        let score = item.scored.then(|| {
+            let result = dafhne_eval::score_answer(
+                q,
+                answer.answer.clone(),
+                answer.distance,
+                answer.connector.clone(),
+                elapsed_us,
+                || model.chain_evidence(&q.question),
+            );
+            let score = LineScore {
+                expected: result.expected.to_string(),
+                correct: result.correct,
+                evidence_missing: result.evidence_missing,
+            };
+            results.push(result);
+            score
+        });
+
+        let line = BatchLine {
+            index,
+            id: &q.id,
+            question: &q.question,
+            answer,
+            elapsed_us,
+            score,
+        };
+        if !emit(serde_json::to_string(&line).expect("Failed to serialize batch line")) {
+            return;
+        }
+    }
+
+    let scored = results.len();
+    let summary = SummaryLine {
+        summary: BatchSummary {
+            model: model.id.clone(),
+            questions: items.len(),
+            scored,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+            report: (scored > 0).then(|| FitnessReport::from_results(results).into()),
+        },
+    };
+    emit(serde_json::to_string(&summary).expect("Failed to serialize batch summary"));
+}
+
+/// `--batch`: answer the questions in `path` (`-` for stdin) and write JSONL to stdout.
+pub fn run_cli(svc: &DafhneService, model_id: &str, path: &Path) -> Result<(), String> {
+    let model = svc
+        .get_model(model_id)
+        .ok_or_else(|| format!("model '{}' not found", model_id))?;
+    let content = if path == Path::new("-") {
+        std::io::read_to_string(std::io::stdin()).map_err(|e| format!("cannot read stdin: {}", e))?
+    } else {
+        std::fs::read_to_string(path).map_err(|e| format!("cannot read {:?}: {}", path, e))?
+    };
+    let items = parse_items(&content, path.extension().and_then(|e| e.to_str()))?;
+    if items.is_empty() {
+        return Err(format!("no questions in {:?}", path));
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    run(&model, &items, None, |line| writeln!(stdout, "{}", line).is_ok());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dafhne_core::ExpectedAnswer;
+
+    #[test]
+    fn batch_items_from_questions_and_text() {
+        let req: BatchRequest = serde_json::from_str(
+            r#"{"questions": ["Is a dog an animal?", {"id": "q2", "question": "What is a cat?", "expected": "an animal"}]}"#,
+        )
+        .unwrap();
+        let items = req.into_items().unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(!items[0].scored);
+        assert_eq!(items[0].question.id, "1");
+        assert!(items[1].scored);
+        assert_eq!(items[1].question.id, "q2");
+        assert_eq!(items[1].question.expected, ExpectedAnswer::Word("an animal".to_string()));
+
+        let both: BatchRequest = serde_json::from_str(r#"{"questions": ["x"], "suite": "y"}"#).unwrap();
+        assert!(both.into_items().is_err());
+
+        let suite = "## DIRECT\n\n**Q01**: Is a dog an animal?\n**A**: Yes\n";
+        let items = parse_items(suite, Some("md")).unwrap();
+        assert!(items[0].scored);
+        assert_eq!(items[0].question.category, "DIRECT");
+
+        let lines = parse_items("Is a dog an animal?\n\nWhat is a cat?\n", None).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(!lines[1].scored);
+    }
+}
@@ -0,0 +1,269 @@
+//! Load test for a running dafhne-server: fires chat completions at
+//! increasing concurrency and reports throughput and latency per level.
+//!
+//! ```bash
+//! cargo run --release -p dafhne-server --bin dafhne-loadtest -- \
+//!   --url http://127.0.0.1:3000 --model dafhne-50 --concurrency 1,2,4,8
+//! ```
+//!
+//! Throughput should grow with concurrency up to the server's core count;
+//! flat throughput means requests are serialising somewhere.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+#[derive(Parser)]
+#[command(name = "dafhne-loadtest", about = "Concurrent load test for dafhne-server")]
+struct Cli {
+    /// Server base URL: plain HTTP, optionally with a path prefix (no TLS)
+    #[arg(long, default_value = "http://127.0.0.1:3000")]
+    url: String,
+    /// Model to query
+    #[arg(long, default_value = "dafhne-50")]
+    model: String,
+    /// Requests per concurrency level
+    #[arg(long, default_value = "400")]
+    requests: usize,
+    /// Comma-separated concurrency levels
+    #[arg(long, default_value = "1,2,4,8", value_delimiter = ',')]
+    concurrency: Vec<usize>,
+    /// Test suite whose questions are asked in turn (default: a few built-in questions)
+    #[arg(long)]
+    questions: Option<PathBuf>,
+    /// API key, for servers that require one
+    #[arg(long)]
+    api_key: Option<String>,
+}
+
+const DEFAULT_QUESTIONS: [&str; 6] = [
+    "Is a dog an animal?",
+    "What is a cat?",
+    "What is two plus three?",
+    "Is three a noun?",
+    "Can you count?",
+    "Why is the sun hot?",
+];
+
+struct LevelResult {
+    concurrency: usize,
+    errors: usize,
+    elapsed: Duration,
+    latencies: Vec<Duration>,
+}
+
+impl LevelResult {
+    fn throughput(&self) -> f64 {
+        self.latencies.len() as f64 / self.elapsed.as_secs_f64()
+    }
+
+    fn percentile(&self, p: f64) -> f64 {
+        if self.latencies.is_empty() {
+            return 0.0;
+        }
+        let idx = ((self.latencies.len() - 1) as f64 * p).round() as usize;
+        self.latencies[idx].as_secs_f64() * 1000.0
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let (addr, prefix) = parse_base_url(&cli.url).unwrap_or_else(|e| panic!("Failed to use --url {:?}: {}", cli.url, e));
+    let path = format!("{}/v1/chat/completions", prefix);
+    let questions: Vec<String> = match &cli.questions {
+        Some(path) => dafhne_parser::load_test_suite(path)
+            .expect("Failed to read questions")
+            .questions
+            .into_iter()
+            .map(|q| q.question)
+            .collect(),
+        None => DEFAULT_QUESTIONS.iter().map(|q| q.to_string()).collect(),
+    };
+    assert!(!questions.is_empty(), "No questions to ask");
+    let bodies: Arc<Vec<String>> = Arc::new(
+        questions
+            .iter()
+            .map(|q| {
+                serde_json::json!({
+                    "model": cli.model,
+                    "messages": [{ "role": "user", "content": q }],
+                    "stream": false
+                })
+                .to_string()
+            })
+            .collect(),
+    );
+
+    println!(
+        "{} requests per level against {} ({}), {} distinct questions",
+        cli.requests,
+        cli.url,
+        cli.model,
+        bodies.len()
+    );
+    println!();
+    println!(
+        "{:>11} {:>8} {:>8} {:>10} {:>9} {:>9} {:>9} {:>8}",
+        "concurrency", "ok", "errors", "req/s", "p50 ms", "p95 ms", "p99 ms", "speedup"
+    );
+
+    let auth: Arc<String> = Arc::new(
+        cli.api_key
+            .as_ref()
+            .map(|key| format!("Authorization: Bearer {}\r\n", key))
+            .unwrap_or_default(),
+    );
+
+    let mut baseline: Option<f64> = None;
+    for &concurrency in &cli.concurrency {
+        let result = run_level(&addr, &path, &auth, bodies.clone(), cli.requests, concurrency.max(1)).await;
+        let throughput = result.throughput();
+        let speedup = match baseline {
+            Some(base) => format!("{:.2}x", throughput / base),
+            None => {
+                baseline = (throughput > 0.0).then_some(throughput);
+                "1.00x".to_string()
+            }
+        };
+        println!(
+            "{:>11} {:>8} {:>8} {:>10.1} {:>9.2} {:>9.2} {:>9.2} {:>8}",
+            result.concurrency,
+            result.latencies.len(),
+            result.errors,
+            throughput,
+            result.percentile(0.50),
+            result.percentile(0.95),
+            result.percentile(0.99),
+            speedup
+        );
+    }
+}
+
+/// `requests` requests shared by `concurrency` workers.
+async fn run_level(
+    addr: &str,
+    path: &str,
+    auth: &Arc<String>,
+    bodies: Arc<Vec<String>>,
+    requests: usize,
+    concurrency: usize,
+) -> LevelResult {
+    let next = Arc::new(AtomicUsize::new(0));
+    let start = Instant::now();
+    let workers: Vec<_> = (0..concurrency)
+        .map(|_| {
+            let (addr, path, auth, bodies, next) =
+                (addr.to_string(), path.to_string(), auth.clone(), bodies.clone(), next.clone());
+            tokio::spawn(async move {
+                let mut latencies = Vec::new();
+                let mut errors = 0;
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= requests {
+                        break;
+                    }
+                    let sent = Instant::now();
+                    match post(&addr, &path, &auth, &bodies[i % bodies.len()]).await {
+                        Ok(200) => latencies.push(sent.elapsed()),
+                        _ => errors += 1,
+                    }
+                }
+                (latencies, errors)
+            })
+        })
+        .collect();
+
+    let mut result = LevelResult {
+        concurrency,
+        errors: 0,
+        elapsed: Duration::ZERO,
+        latencies: Vec::new(),
+    };
+    for worker in workers {
+        let (latencies, errors) = worker.await.expect("Failed to join worker");
+        result.latencies.extend(latencies);
+        result.errors += errors;
+    }
+    result.elapsed = start.elapsed();
+    result.latencies.sort();
+    result
+}
+
+/// Split `http://host[:port][/prefix]` into the address to connect to
+/// (port 80 by default) and the path prefix without its trailing slash.
+/// Anything `post` cannot speak to (TLS, other schemes, query strings,
+/// credentials) is rejected rather than left to fail on every request.
+fn parse_base_url(url: &str) -> Result<(String, String), String> {
+    let rest = match url.split_once("://") {
+        Some(("http", rest)) => rest,
+        Some(("https", _)) => return Err("https is not supported; point it at the plain HTTP listener".to_string()),
+        Some((scheme, _)) => return Err(format!("unsupported scheme {:?}", scheme)),
+        None => return Err("expected http://host[:port][/prefix]".to_string()),
+    };
+    if rest.contains(['?', '#', '@']) {
+        return Err("query strings, fragments and credentials are not supported".to_string());
+    }
+    let (host, prefix) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    if host.is_empty() {
+        return Err("missing host".to_string());
+    }
+    let addr = match host.rsplit_once(':') {
+        Some((_, port)) if !host.ends_with(']') => {
+            port.parse::<u16>().map_err(|_| format!("invalid port {:?}", port))?;
+            host.to_string()
+        }
+        _ => format!("{}:80", host),
+    };
+    Ok((addr, prefix.trim_end_matches('/').to_string()))
+}
+
+/// Minimal HTTP/1.1 POST (one connection per request); returns the status code.
+/// `headers` are extra header lines, each ending in CRLF.
+async fn post(addr: &str, path: &str, headers: &str, body: &str) -> std::io::Result<u16> {
+    let mut stream = TcpStream::connect(addr).await?;
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\n{}Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        addr,
+        headers,
+        body.len(),
+        body
+    );
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await?;
+    let status = String::from_utf8_lossy(&response)
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .unwrap_or(0);
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_urls_the_harness_can_reach() {
+        let parse = parse_base_url;
+        assert_eq!(parse("http://127.0.0.1:3000"), Ok(("127.0.0.1:3000".into(), "".into())));
+        assert_eq!(parse("http://127.0.0.1:3000/"), Ok(("127.0.0.1:3000".into(), "".into())));
+        assert_eq!(parse("http://localhost/dafhne/"), Ok(("localhost:80".into(), "/dafhne".into())));
+        assert_eq!(parse("http://[::1]:3000/api"), Ok(("[::1]:3000".into(), "/api".into())));
+        assert_eq!(parse("http://[::1]"), Ok(("[::1]:80".into(), "".into())));
+
+        for url in ["https://example.com", "ftp://host", "127.0.0.1:3000", "http://", "http://host:x", "http://h/?a=1"] {
+            assert!(parse_base_url(url).is_err(), "{} should be rejected", url);
+        }
+    }
+}
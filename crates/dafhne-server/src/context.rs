@@ -0,0 +1,311 @@
+//! Conversation context for the chat endpoints: resolves pronouns and
+//! elliptical follow-ups in the last user message against earlier turns.
+//!
+//! - "it" (singular) and "they", "them" (plural) stand for the subject of the
+//!   previous question when the number agrees: "What is a dog?" then "Can it
+//!   make sound?" → "Can a dog make sound?"
+//! - "that", "this" in a pronoun position (first, last, or after a verb or
+//!   preposition) stand for the most recent mention from either side, the
+//!   assistant's word answers included: "What is a dog?", "an animal",
+//!   "What is that?" → "What is an animal?"
+//! - "And a cat?", "What about a cat?" repeat the previous question with a
+//!   new subject: "Is a dog an animal?" then "And a cat?" → "Is a cat an animal?"
+//!
+//! Replacements are spliced into the message as written; a message with
+//! nothing to resolve is passed through unchanged.
+
+use std::ops::Range;
+
+/// Words that are never a question's subject.
+const FUNCTION_WORDS: &[&str] = &[
+    "a", "an", "the", "what", "who", "whom", "whose", "which", "where", "when", "why", "how", "is", "are",
+    "was", "were", "am", "be", "can", "could", "do", "does", "did", "will", "would", "should", "has", "have",
+    "had", "not", "no", "yes", "and", "or", "but", "of", "to", "in", "on", "at", "with", "for", "about",
+    "you", "i", "me", "my", "your", "we", "us", "he", "she", "him", "her", "its", "their", "there", "these",
+    "those",
+];
+
+/// Verbs that make a follow-up a full question rather than an ellipsis.
+const VERBS: &[&str] = &[
+    "is", "are", "was", "were", "am", "be", "can", "could", "do", "does", "did", "will", "would", "has", "have",
+];
+
+const ARTICLES: &[&str] = &["a", "an", "the"];
+
+/// Pronouns standing for the previous question's subject.
+const SUBJECT_PRONOUNS: &[&str] = &["it", "they", "them"];
+
+/// Demonstratives standing for the most recent mention.
+const DEMONSTRATIVES: &[&str] = &["that", "this"];
+
+/// Prepositions after which "that"/"this" stands alone: "What about this?"
+const PREPOSITIONS: &[&str] = &["about", "of", "to", "in", "on", "at", "with", "for", "from", "like"];
+
+/// A question as lowercase words, each with its place in the original text,
+/// and the span of its subject phrase.
+#[derive(Debug, Clone)]
+struct Turn {
+    text: String,
+    words: Vec<String>,
+    /// Byte range of each word in `text`, punctuation excluded.
+    spans: Vec<Range<usize>>,
+    /// Determiner (if any) through the subject word.
+    subject: Option<Range<usize>>,
+}
+
+impl Turn {
+    fn parse(text: &str) -> Self {
+        let is_punct = |c: char| !c.is_alphanumeric() && c != '\'';
+        let mut words = Vec::new();
+        let mut spans = Vec::new();
+        for token in text.split_whitespace() {
+            let core = token.trim_matches(is_punct);
+            if core.is_empty() {
+                continue;
+            }
+            let start = token.as_ptr() as usize - text.as_ptr() as usize + token.len()
+                - token.trim_start_matches(is_punct).len();
+            words.push(core.to_lowercase());
+            spans.push(start..start + core.len());
+        }
+        let subject = words.iter().position(|w| is_content(w)).map(|i| phrase_start(&words, i)..i + 1);
+        Turn {
+            text: text.to_string(),
+            words,
+            spans,
+            subject,
+        }
+    }
+
+    /// Byte range in `text` covering the words in `words`.
+    fn byte_span(&self, words: Range<usize>) -> Range<usize> {
+        self.spans[words.start].start..self.spans[words.end - 1].end
+    }
+
+    /// The words in `words` as originally written.
+    fn phrase(&self, words: Range<usize>) -> String {
+        self.text[self.byte_span(words)].to_string()
+    }
+
+    fn subject_phrase(&self) -> Option<String> {
+        self.subject.clone().map(|span| self.phrase(span))
+    }
+
+    /// The last content word and its determiner.
+    fn last_mention(&self) -> Option<String> {
+        let i = self.words.iter().rposition(|w| is_content(w))?;
+        Some(self.phrase(phrase_start(&self.words, i)..i + 1))
+    }
+
+    /// Whether the demonstrative at `i` stands alone: first, last, or right
+    /// after a verb or preposition. Before a noun it is a determiner ("that
+    /// dog"); after one, a relative pronoun ("a thing that can move").
+    fn is_pronoun_slot(&self, i: usize) -> bool {
+        if self.words.get(i + 1).is_some_and(|w| is_content(w)) {
+            return false;
+        }
+        let Some(prev) = i.checked_sub(1).map(|p| self.words[p].as_str()) else {
+            return true;
+        };
+        VERBS.contains(&prev) || PREPOSITIONS.contains(&prev) || (i + 1 == self.words.len() && !is_content(prev))
+    }
+}
+
+fn is_content(word: &str) -> bool {
+    !FUNCTION_WORDS.contains(&word) && !SUBJECT_PRONOUNS.contains(&word) && !DEMONSTRATIVES.contains(&word)
+}
+
+/// Index of the phrase around the content word at `i`: its article, if any.
+fn phrase_start(words: &[String], i: usize) -> usize {
+    if i > 0 && ARTICLES.contains(&words[i - 1].as_str()) {
+        i - 1
+    } else {
+        i
+    }
+}
+
+fn capitalize(phrase: &str) -> String {
+    let mut chars = phrase.chars();
+    chars.next().map_or_else(String::new, |c| c.to_uppercase().chain(chars).collect())
+}
+
+/// Rough number agreement: "a dog" is singular, "cats" plural.
+fn is_plural(phrase: &str) -> bool {
+    let words: Vec<String> = phrase.split_whitespace().map(str::to_lowercase).collect();
+    match words.as_slice() {
+        [first, ..] if first == "a" || first == "an" => false,
+        [.., last] => last.ends_with('s') && !last.ends_with("ss"),
+        [] => false,
+    }
+}
+
+/// What earlier turns leave behind for the next one.
+#[derive(Debug, Default)]
+struct ConversationContext {
+    /// The last user question that had a subject, after resolution.
+    last_question: Option<Turn>,
+    /// Most recent mention from either side.
+    last_mention: Option<String>,
+}
+
+impl ConversationContext {
+    fn observe_user(&mut self, text: &str) -> String {
+        let resolved = self.resolve(text);
+        let turn = Turn::parse(&resolved);
+        if let Some(mention) = turn.last_mention() {
+            self.last_mention = Some(mention);
+        }
+        if turn.subject.is_some() {
+            self.last_question = Some(turn);
+        }
+        resolved
+    }
+
+    /// Word answers ("an animal", "five") become the most recent mention.
+    fn observe_assistant(&mut self, text: &str) {
+        let turn = Turn::parse(text);
+        let answer = turn.words.join(" ");
+        if turn.words.len() <= 4
+            && turn.words.iter().any(|w| is_content(w))
+            && !matches!(answer.as_str(), "yes" | "no" | "i don't know")
+        {
+            self.last_mention = Some(turn.phrase(0..turn.words.len()));
+        }
+    }
+
+    /// `text` with pronouns and ellipsis resolved; `text` itself when there
+    /// is nothing to resolve or nothing to resolve it against. Replacements
+    /// are spliced into `text`, so its casing and punctuation survive.
+    fn resolve(&self, text: &str) -> String {
+        let turn = Turn::parse(text);
+        if let Some(resolved) = self.resolve_ellipsis(&turn) {
+            return resolved;
+        }
+
+        let subject = self.last_question.as_ref().and_then(Turn::subject_phrase);
+        let mut replacements = Vec::new();
+        for (i, word) in turn.words.iter().enumerate() {
+            let referent = match word.as_str() {
+                // A pronoun only stands for a subject of the same number
+                "it" => subject.as_ref().filter(|s| !is_plural(s)),
+                "they" | "them" => subject.as_ref().filter(|s| is_plural(s)),
+                "that" | "this" if turn.is_pronoun_slot(i) => self.last_mention.as_ref(),
+                _ => None,
+            };
+            if let Some(phrase) = referent {
+                let span = turn.spans[i].clone();
+                let capitalized = text[span.clone()].starts_with(char::is_uppercase);
+                replacements.push((span, if capitalized { capitalize(phrase) } else { phrase.clone() }));
+            }
+        }
+
+        let mut resolved = text.to_string();
+        for (span, phrase) in replacements.into_iter().rev() {
+            resolved.replace_range(span, &phrase);
+        }
+        resolved
+    }
+
+    /// "And a cat?" / "What about a cat?": the previous question with the
+    /// new phrase as its subject. When the previous subject had a determiner
+    /// the new phrase needs one too: "What about cats?" after "What is a dog?"
+    /// has no grammatical reading in the question's frame, so it passes through.
+    fn resolve_ellipsis(&self, turn: &Turn) -> Option<String> {
+        let skip = match turn.words.as_slice() {
+            [first, ..] if first == "and" => 1,
+            [first, second, ..] if (first == "what" || first == "how") && second == "about" => 2,
+            _ => return None,
+        };
+        let rest = &turn.words[skip..];
+        if rest.is_empty() || rest.len() > 3 || rest.iter().any(|w| VERBS.contains(&w.as_str())) {
+            return None;
+        }
+        let previous = self.last_question.as_ref()?;
+        let span = previous.subject.clone()?;
+        let had_determiner = ARTICLES.contains(&previous.words[span.start].as_str());
+        if had_determiner && !ARTICLES.contains(&rest[0].as_str()) {
+            return None;
+        }
+        let mut resolved = previous.text.clone();
+        resolved.replace_range(previous.byte_span(span), &turn.phrase(skip..turn.words.len()));
+        Some(resolved)
+    }
+}
+
+/// The last user message of `messages` (role, content), resolved against
+/// the turns before it. Empty when there is no user message.
+pub fn resolve_conversation<'a>(messages: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+    let messages: Vec<(&str, &str)> = messages.into_iter().collect();
+    let Some(last) = messages.iter().rposition(|(role, _)| *role == "user") else {
+        return String::new();
+    };
+
+    let mut context = ConversationContext::default();
+    for (role, content) in &messages[..last] {
+        match *role {
+            "user" => {
+                context.observe_user(content);
+            }
+            "assistant" => context.observe_assistant(content),
+            _ => {}
+        }
+    }
+
+    let question = messages[last].1;
+    let resolved = context.resolve(question);
+    if resolved != question {
+        tracing::debug!("Resolved {:?} as {:?}", question, resolved);
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(turns: &[&str]) -> String {
+        let roles = ["user", "assistant"];
+        resolve_conversation(turns.iter().enumerate().map(|(i, t)| (roles[i % 2], *t)))
+    }
+
+    #[test]
+    fn resolves_pronouns_and_ellipsis() {
+        // Single-turn messages pass through untouched
+        assert_eq!(resolve(&["What is it?"]), "What is it?");
+        assert_eq!(resolve(&["What is a dog?", "an animal", "Is a cat a food?"]), "Is a cat a food?");
+
+        assert_eq!(resolve(&["What is a dog?", "an animal", "Can it make sound?"]), "Can a dog make sound?");
+        assert_eq!(resolve(&["Is a dog an animal?", "Yes", "And a cat?"]), "Is a cat an animal?");
+        assert_eq!(resolve(&["What is a dog?", "an animal", "What about an apple?"]), "What is an apple?");
+        // A bare noun cannot take the place of "a dog"
+        assert_eq!(resolve(&["What is a dog?", "an animal", "What about cats?"]), "What about cats?");
+        assert_eq!(resolve(&["What is a dog?", "an animal", "What is that?"]), "What is an animal?");
+        // "that" before a noun is a determiner
+        assert_eq!(resolve(&["What is a dog?", "an animal", "Is that dog big?"]), "Is that dog big?");
+        // Resolved questions carry the subject forward
+        assert_eq!(
+            resolve(&["What is a dog?", "an animal", "Can it make sound?", "Yes", "Is it big?"]),
+            "Is a dog big?"
+        );
+        assert_eq!(
+            resolve(&["What is two plus three?", "five", "And four?"]),
+            "What is four plus three?"
+        );
+    }
+
+    #[test]
+    fn keeps_relative_that_and_number_agreement() {
+        // "that" after a noun is a relative pronoun
+        assert_eq!(
+            resolve(&["What is a dog?", "an animal", "Is a dog a thing that can move?"]),
+            "Is a dog a thing that can move?"
+        );
+        assert_eq!(resolve(&["What is a dog?", "an animal", "What about this?"]), "What about an animal?");
+        assert_eq!(resolve(&["What is a dog?", "an animal", "Is that big?"]), "Is that big?");
+        assert_eq!(resolve(&["What is a dog?", "an animal", "That is big?"]), "An animal is big?");
+        // "they" cannot stand for "a dog", nor "it" for "cats"
+        assert_eq!(resolve(&["What is a dog?", "an animal", "Are they animals?"]), "Are they animals?");
+        assert_eq!(resolve(&["Are cats animals?", "Yes", "Can they move?"]), "Can cats move?");
+        assert_eq!(resolve(&["Are cats animals?", "Yes", "Can it move?"]), "Can it move?");
+    }
+}
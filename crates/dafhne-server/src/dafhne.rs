@@ -0,0 +1,749 @@
+//! Native DAFHNE API under `/v1/dafhne/`: structured answers, word
+//! geometry and routing, with an OpenAPI document at
+//! `/v1/dafhne/openapi.json`.
+
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use dafhne_engine::multispace::Resolution;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::service::{
+    blocking, DafhneModel, DafhneService, SpaceDistance, SpaceInfo, SpaceNeighbors, StructuredAnswer,
+};
+
+// ─── Request / Response types ────────────────────────────────
+
+#[derive(Deserialize)]
+pub struct AskRequest {
+    question: String,
+    #[serde(default = "default_model")]
+    model: String,
+    /// Include the routing trace (multi-space models).
+    #[serde(default)]
+    trace: bool,
+}
+
+#[derive(Serialize)]
+struct AskResponse {
+    model: String,
+    question: String,
+    #[serde(flatten)]
+    answer: StructuredAnswer,
+}
+
+#[derive(Deserialize)]
+pub struct WordQuery {
+    word: String,
+    #[serde(default = "default_model")]
+    model: String,
+}
+
+#[derive(Serialize)]
+struct DescribeResponse {
+    model: String,
+    word: String,
+    sentences: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct WordsQuery {
+    #[serde(default = "default_model")]
+    model: String,
+    space: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WordsResponse {
+    model: String,
+    space: Option<String>,
+    count: usize,
+    words: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct NeighborsQuery {
+    word: String,
+    #[serde(default = "default_k")]
+    k: usize,
+    #[serde(default = "default_model")]
+    model: String,
+    space: Option<String>,
+}
+
+fn default_k() -> usize {
+    10
+}
+
+#[derive(Serialize)]
+struct NeighborsResponse {
+    model: String,
+    word: String,
+    spaces: Vec<SpaceNeighbors>,
+}
+
+#[derive(Deserialize)]
+pub struct DistanceQuery {
+    a: String,
+    b: String,
+    #[serde(default = "default_model")]
+    model: String,
+    space: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DistanceResponse {
+    model: String,
+    a: String,
+    b: String,
+    spaces: Vec<SpaceDistance>,
+}
+
+#[derive(Deserialize)]
+pub struct ModelQuery {
+    #[serde(default = "default_model")]
+    model: String,
+}
+
+#[derive(Serialize)]
+struct SpacesResponse {
+    model: String,
+    spaces: Vec<SpaceInfo>,
+}
+
+#[derive(Deserialize)]
+pub struct RouteRequest {
+    question: String,
+    #[serde(default = "default_model")]
+    model: String,
+}
+
+pub(crate) fn default_model() -> String {
+    "dafhne-50".to_string()
+}
+
+#[derive(Serialize)]
+struct RouteResponse {
+    model: String,
+    #[serde(flatten)]
+    resolution: Resolution,
+}
+
+// ─── Routes ──────────────────────────────────────────────────
+
+pub fn routes() -> Router<Arc<DafhneService>> {
+    Router::new()
+        .route("/v1/dafhne/ask", post(ask))
+        .route("/v1/dafhne/describe", get(describe))
+        .route("/v1/dafhne/words", get(words))
+        .route("/v1/dafhne/neighbors", get(neighbors))
+        .route("/v1/dafhne/distance", get(distance))
+        .route("/v1/dafhne/spaces", get(spaces))
+        .route("/v1/dafhne/route", post(route))
+        .route("/v1/dafhne/openapi.json", get(openapi))
+}
+
+// ─── Handlers ────────────────────────────────────────────────
+
+/// Answer a question with its kind, distance, connector, routed spaces and confidence.
+async fn ask(
+    State(svc): State<Arc<DafhneService>>,
+    Json(req): Json<AskRequest>,
+) -> Result<Json<AskResponse>, ApiError> {
+    let model = find_model(&svc, &req.model)?;
+    let question = req.question.clone();
+    let answer = blocking(move || model.ask(&question, req.trace)).await;
+    Ok(Json(AskResponse {
+        model: req.model,
+        question: req.question,
+        answer,
+    }))
+}
+
+/// Describe a word from its definitions and position.
+async fn describe(
+    State(svc): State<Arc<DafhneService>>,
+    Query(q): Query<WordQuery>,
+) -> Result<Json<DescribeResponse>, ApiError> {
+    let model = find_model(&svc, &q.model)?;
+    let word = q.word.to_lowercase();
+    let (word, sentences) = blocking(move || {
+        let sentences = model.describe(&word);
+        (word, sentences)
+    })
+    .await;
+    if sentences.is_empty() {
+        return Err(ApiError::not_found(format!("'{}' is not in model '{}'", word, q.model)));
+    }
+    Ok(Json(DescribeResponse { model: q.model, word, sentences }))
+}
+
+/// The model's vocabulary, optionally for one space.
+async fn words(
+    State(svc): State<Arc<DafhneService>>,
+    Query(q): Query<WordsQuery>,
+) -> Result<Json<WordsResponse>, ApiError> {
+    let model = find_model(&svc, &q.model)?;
+    check_space(&model, q.space.as_deref())?;
+    let words = model.list_words(q.space.as_deref());
+    Ok(Json(WordsResponse {
+        model: q.model,
+        space: q.space,
+        count: words.len(),
+        words,
+    }))
+}
+
+/// Nearest words to a word, per space.
+async fn neighbors(
+    State(svc): State<Arc<DafhneService>>,
+    Query(q): Query<NeighborsQuery>,
+) -> Result<Json<NeighborsResponse>, ApiError> {
+    if q.k == 0 {
+        return Err(ApiError::bad_request("k must be at least 1".to_string()));
+    }
+    let model = find_model(&svc, &q.model)?;
+    check_space(&model, q.space.as_deref())?;
+    let word = q.word.to_lowercase();
+    let (word, spaces) = blocking(move || {
+        let spaces = model.neighbors(&word, q.k, q.space.as_deref());
+        (word, spaces)
+    })
+    .await;
+    if spaces.is_empty() {
+        return Err(ApiError::not_found(format!("'{}' is not in model '{}'", word, q.model)));
+    }
+    Ok(Json(NeighborsResponse { model: q.model, word, spaces }))
+}
+
+/// Distance between two words, per space.
+async fn distance(
+    State(svc): State<Arc<DafhneService>>,
+    Query(q): Query<DistanceQuery>,
+) -> Result<Json<DistanceResponse>, ApiError> {
+    let model = find_model(&svc, &q.model)?;
+    check_space(&model, q.space.as_deref())?;
+    let (a, b) = (q.a.to_lowercase(), q.b.to_lowercase());
+    let spaces = model.distance(&a, &b, q.space.as_deref());
+    if spaces.is_empty() {
+        return Err(ApiError::not_found(format!(
+            "no space of model '{}' knows both '{}' and '{}'",
+            q.model, a, b
+        )));
+    }
+    Ok(Json(DistanceResponse { model: q.model, a, b, spaces }))
+}
+
+/// Size, geometry, thresholds and bridges of each space.
+async fn spaces(
+    State(svc): State<Arc<DafhneService>>,
+    Query(q): Query<ModelQuery>,
+) -> Result<Json<SpacesResponse>, ApiError> {
+    let model = find_model(&svc, &q.model)?;
+    let spaces = blocking(move || model.space_info()).await;
+    Ok(Json(SpacesResponse { model: q.model, spaces }))
+}
+
+/// Answer a question on a multi-space model and report how it was routed.
+async fn route(
+    State(svc): State<Arc<DafhneService>>,
+    Json(req): Json<RouteRequest>,
+) -> Result<Json<RouteResponse>, ApiError> {
+    let model = find_model(&svc, &req.model)?;
+    let question = req.question;
+    match blocking(move || model.explain(&question)).await {
+        Some(resolution) => Ok(Json(RouteResponse { model: req.model, resolution })),
+        None => Err(ApiError::bad_request(format!(
+            "model '{}' is single-space and has no routing",
+            req.model
+        ))),
+    }
+}
+
+async fn openapi() -> Json<Value> {
+    Json(openapi_document())
+}
+
+pub(crate) fn find_model(svc: &DafhneService, id: &str) -> Result<Arc<DafhneModel>, ApiError> {
+    svc.get_model(id)
+        .ok_or_else(|| ApiError::not_found(format!("model '{}' not found", id)))
+}
+
+fn check_space(model: &DafhneModel, space: Option<&str>) -> Result<(), ApiError> {
+    match space {
+        Some(name) if !model.spaces().iter().any(|v| v.name == name) => Err(ApiError::not_found(format!(
+            "model '{}' has no space '{}'",
+            model.id, name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// `{"error": message}` with a status code.
+pub(crate) struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: String) -> Self {
+        ApiError { status, message }
+    }
+
+    pub fn not_found(message: String) -> Self {
+        ApiError {
+            status: StatusCode::NOT_FOUND,
+            message,
+        }
+    }
+
+    pub fn bad_request(message: String) -> Self {
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> axum::response::Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+// ─── OpenAPI ─────────────────────────────────────────────────
+
+/// OpenAPI 3.0 description of the `/v1/dafhne/` endpoints.
+pub fn openapi_document() -> Value {
+    let model = json!({
+        "name": "model", "in": "query", "required": false,
+        "schema": { "type": "string", "default": "dafhne-50" }
+    });
+    let space = json!({
+        "name": "space", "in": "query", "required": false,
+        "description": "Restrict to one space (\"default\" for single-space models)",
+        "schema": { "type": "string" }
+    });
+    let word = |name: &str| json!({ "name": name, "in": "query", "required": true, "schema": { "type": "string" } });
+    let ok = |schema: &str| json!({
+        "200": { "description": "OK", "content": { "application/json": { "schema": { "$ref": format!("#/components/schemas/{}", schema) } } } },
+        "404": { "$ref": "#/components/responses/NotFound" }
+    });
+    let with_error = |mut responses: Value| {
+        responses["400"] = json!({ "$ref": "#/components/responses/Error" });
+        responses
+    };
+    let body = |schema: &str| json!({
+        "required": true,
+        "content": { "application/json": { "schema": { "$ref": format!("#/components/schemas/{}", schema) } } }
+    });
+    let number = json!({ "type": "number" });
+    let nullable_number = json!({ "type": "number", "nullable": true });
+    let nullable_string = json!({ "type": "string", "nullable": true });
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "DAFHNE native API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Structured answers and word geometry from DAFHNE models. Servers with API keys configured answer 401, 403, 413 or 429 as described in docs/server.md."
+        },
+        // The empty alternative: servers without API keys need none
+        "security": [{ "bearer": [] }, { "apiKey": [] }, {}],
+        "paths": {
+            "/v1/dafhne/ask": { "post": {
+                "summary": "Answer a question",
+                "requestBody": body("AskRequest"),
+                "responses": ok("AskResponse")
+            }},
+            "/v1/dafhne/describe": { "get": {
+                "summary": "Describe a word",
+                "parameters": [word("word"), model],
+                "responses": ok("DescribeResponse")
+            }},
+            "/v1/dafhne/words": { "get": {
+                "summary": "List the vocabulary",
+                "parameters": [model, space],
+                "responses": ok("WordsResponse")
+            }},
+            "/v1/dafhne/neighbors": { "get": {
+                "summary": "Nearest words, per space",
+                "parameters": [
+                    word("word"),
+                    { "name": "k", "in": "query", "required": false, "schema": { "type": "integer", "default": 10, "minimum": 1 } },
+                    model, space
+                ],
+                "responses": with_error(ok("NeighborsResponse"))
+            }},
+            "/v1/dafhne/distance": { "get": {
+                "summary": "Distance between two words, per space",
+                "parameters": [word("a"), word("b"), model, space],
+                "responses": ok("DistanceResponse")
+            }},
+            "/v1/dafhne/spaces": { "get": {
+                "summary": "Geometry, thresholds and bridges of each space",
+                "parameters": [model],
+                "responses": ok("SpacesResponse")
+            }},
+            "/v1/dafhne/batch": { "post": {
+                "summary": "Answer many questions, streamed as JSONL",
+                "requestBody": body("BatchRequest"),
+                "responses": {
+                    "200": { "description": "One AskResponse-shaped line per question (with index, id, elapsed_us and, when scored, expected, correct and evidence_missing), then a {\"summary\": ...} line", "content": { "application/x-ndjson": { "schema": { "type": "string" } } } },
+                    "400": { "$ref": "#/components/responses/Error" },
+                    "404": { "$ref": "#/components/responses/NotFound" }
+                }
+            }},
+            "/v1/dafhne/ws": { "get": {
+                "summary": "WebSocket session: questions, answers with traces and live model events (see docs/server.md)",
+                "responses": { "101": { "description": "Switching to the WebSocket protocol" } }
+            }},
+            "/v1/dafhne/route": { "post": {
+                "summary": "Route a question across the spaces of a multi-space model",
+                "requestBody": body("RouteRequest"),
+                "responses": {
+                    "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "object" } } } },
+                    "400": { "$ref": "#/components/responses/Error" },
+                    "404": { "$ref": "#/components/responses/NotFound" }
+                }
+            }}
+        },
+        "components": {
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" },
+                "apiKey": { "type": "apiKey", "in": "header", "name": "X-API-Key" }
+            },
+            "responses": {
+                "NotFound": { "description": "Unknown model, space or word", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } } },
+                "Error": { "description": "Invalid request", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } } }
+            },
+            "schemas": {
+                "Error": { "type": "object", "required": ["error"], "properties": { "error": { "type": "string" } } },
+                "AskRequest": { "type": "object", "required": ["question"], "properties": {
+                    "question": { "type": "string" },
+                    "model": { "type": "string", "default": "dafhne-50" },
+                    "trace": { "type": "boolean", "default": false, "description": "Include the routing trace (multi-space models)" }
+                }},
+                "BatchRequest": { "type": "object", "properties": {
+                    "model": { "type": "string", "default": "dafhne-50" },
+                    "questions": { "type": "array", "items": { "oneOf": [
+                        { "type": "string" },
+                        { "type": "object", "required": ["question"], "properties": {
+                            "id": { "type": "string" },
+                            "question": { "type": "string" },
+                            "expected": { "type": "string", "description": "Yes, No, I don't know, or the expected word" }
+                        }}
+                    ]}},
+                    "suite": { "type": "string", "description": "Markdown test suite, instead of questions" },
+                    "score": { "type": "boolean", "default": true, "description": "Score questions that have an expected answer" }
+                }},
+                "RouteRequest": { "type": "object", "required": ["question"], "properties": {
+                    "question": { "type": "string" },
+                    "model": { "type": "string", "default": "dafhne-50" }
+                }},
+                "AskResponse": { "type": "object", "properties": {
+                    "model": { "type": "string" },
+                    "question": { "type": "string" },
+                    "kind": { "type": "string", "enum": ["Yes", "No", "IDontKnow", "Word"] },
+                    "word": nullable_string,
+                    "text": { "type": "string" },
+                    "distance": nullable_number,
+                    "connector": nullable_string,
+                    "confidence": {
+                        "type": "number", "nullable": true, "minimum": 0, "maximum": 1,
+                        "description": "How far the distance lies outside the I-don't-know band, as a fraction of the threshold it crossed; null for word answers"
+                    },
+                    "spaces": strings,
+                    "trace": { "type": "object", "description": "Routing trace, same shape as /v1/dafhne/route" }
+                }},
+                "DescribeResponse": { "type": "object", "properties": {
+                    "model": { "type": "string" }, "word": { "type": "string" }, "sentences": strings
+                }},
+                "WordsResponse": { "type": "object", "properties": {
+                    "model": { "type": "string" }, "space": nullable_string,
+                    "count": { "type": "integer" }, "words": strings
+                }},
+                "Neighbor": { "type": "object", "properties": {
+                    "word": { "type": "string" }, "distance": number,
+                    "normalized": { "type": "number", "description": "Distance over the space's mean pairwise distance" }
+                }},
+                "NeighborsResponse": { "type": "object", "properties": {
+                    "model": { "type": "string" }, "word": { "type": "string" },
+                    "spaces": { "type": "array", "items": { "type": "object", "properties": {
+                        "space": { "type": "string" },
+                        "neighbors": { "type": "array", "items": { "$ref": "#/components/schemas/Neighbor" } }
+                    }}}
+                }},
+                "DistanceResponse": { "type": "object", "properties": {
+                    "model": { "type": "string" }, "a": { "type": "string" }, "b": { "type": "string" },
+                    "spaces": { "type": "array", "items": { "type": "object", "properties": {
+                        "space": { "type": "string" }, "distance": number, "normalized": number
+                    }}}
+                }},
+                "SpaceInfo": { "type": "object", "properties": {
+                    "name": { "type": "string" },
+                    "words": { "type": "integer" },
+                    "dimensions": { "type": "integer" },
+                    "connectors": { "type": "integer" },
+                    "distance_mean": number,
+                    "distance_std_dev": number,
+                    "yes_threshold": number,
+                    "no_threshold": number,
+                    "bridges": { "type": "array", "items": { "type": "object", "properties": {
+                        "space": { "type": "string" }, "terms": { "type": "integer" }
+                    }}}
+                }},
+                "SpacesResponse": { "type": "object", "properties": {
+                    "model": { "type": "string" },
+                    "spaces": { "type": "array", "items": { "$ref": "#/components/schemas/SpaceInfo" } }
+                }}
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::{Neighbor, SpaceBridge};
+    use dafhne_core::{Answer, AnswerKind};
+
+    #[test]
+    fn openapi_covers_every_route() {
+        let doc = openapi_document();
+        let paths = doc["paths"].as_object().unwrap();
+        for path in ["ask", "describe", "words", "neighbors", "distance", "spaces", "batch", "ws", "route"] {
+            assert!(paths.contains_key(&format!("/v1/dafhne/{}", path)), "missing {}", path);
+        }
+        // Every $ref points at a defined component
+        let text = doc.to_string();
+        for reference in text.split("\"$ref\":\"#/components/").skip(1) {
+            let target = reference.split('"').next().unwrap();
+            let (kind, name) = target.split_once('/').unwrap();
+            assert!(doc["components"][kind].get(name).is_some(), "dangling $ref {}", target);
+        }
+    }
+
+    /// Check `value` against `schema`: types, nullability, enums, required
+    /// properties, and no property the schema leaves out.
+    fn check_schema(doc: &Value, schema: &Value, value: &Value, at: &str) {
+        if let Some(target) = schema["$ref"].as_str() {
+            let name = target.trim_start_matches("#/components/schemas/");
+            return check_schema(doc, &doc["components"]["schemas"][name], value, at);
+        }
+        if value.is_null() {
+            assert_eq!(schema["nullable"], true, "{} is null but not nullable", at);
+            return;
+        }
+        if let Some(options) = schema["enum"].as_array() {
+            assert!(options.contains(value), "{} = {} is not in {:?}", at, value, options);
+        }
+        let matches = match schema["type"].as_str() {
+            Some("string") => value.is_string(),
+            Some("number") => value.is_number(),
+            Some("integer") => value.is_u64() || value.is_i64(),
+            Some("boolean") => value.is_boolean(),
+            Some("array") => {
+                for (i, item) in value.as_array().into_iter().flatten().enumerate() {
+                    check_schema(doc, &schema["items"], item, &format!("{}[{}]", at, i));
+                }
+                value.is_array()
+            }
+            Some("object") => {
+                let object = value.as_object();
+                if let (Some(object), Some(properties)) = (object, schema["properties"].as_object()) {
+                    for (key, item) in object {
+                        let property = properties.get(key).unwrap_or_else(|| panic!("{}.{} is not documented", at, key));
+                        check_schema(doc, property, item, &format!("{}.{}", at, key));
+                    }
+                }
+                for key in schema["required"].as_array().into_iter().flatten() {
+                    assert!(object.is_some_and(|o| o.contains_key(key.as_str().unwrap())), "{} lacks {}", at, key);
+                }
+                object.is_some()
+            }
+            _ => true,
+        };
+        assert!(matches, "{} = {} is not a {}", at, value, schema["type"]);
+    }
+
+    /// Check a serialized response, and that it carries every documented
+    /// property except those in `optional`.
+    fn check_response(doc: &Value, name: &str, value: impl Serialize, optional: &[&str]) {
+        let value = serde_json::to_value(value).unwrap();
+        let schema = &doc["components"]["schemas"][name];
+        check_schema(doc, schema, &value, name);
+        for key in schema["properties"].as_object().unwrap().keys() {
+            assert!(
+                optional.contains(&key.as_str()) || value.get(key).is_some(),
+                "{}.{} is documented but never serialized",
+                name,
+                key
+            );
+        }
+    }
+
+    /// A request schema's required properties, filled in from `sample`,
+    /// deserialize into `T`; dropping any one of them fails.
+    fn check_request<T: serde::de::DeserializeOwned>(doc: &Value, name: &str, sample: &Value) {
+        let schema = &doc["components"]["schemas"][name];
+        let required: Vec<&str> =
+            schema["required"].as_array().into_iter().flatten().map(|k| k.as_str().unwrap()).collect();
+        let minimal: serde_json::Map<String, Value> =
+            required.iter().map(|k| (k.to_string(), sample[*k].clone())).collect();
+        check_schema(doc, schema, &Value::Object(minimal.clone()), name);
+        assert!(serde_json::from_value::<T>(Value::Object(minimal.clone())).is_ok(), "{} minimal body rejected", name);
+        for key in &required {
+            let mut partial = minimal.clone();
+            partial.remove(*key);
+            assert!(serde_json::from_value::<T>(Value::Object(partial)).is_err(), "{} accepted without {}", name, key);
+        }
+        check_schema(doc, schema, sample, name);
+        assert!(serde_json::from_value::<T>(sample.clone()).is_ok(), "{} full body rejected", name);
+    }
+
+    #[test]
+    fn openapi_schemas_match_serialized_types() {
+        let doc = openapi_document();
+
+        let answer = |answer: Answer, kind, word: Option<&str>, distance, confidence| StructuredAnswer {
+            text: answer.to_string(),
+            answer,
+            kind,
+            word: word.map(str::to_string),
+            distance,
+            connector: distance.map(|_| "is".to_string()),
+            confidence,
+            spaces: vec!["content".to_string()],
+            trace: None,
+        };
+        for answer in [
+            answer(Answer::Yes, AnswerKind::Yes, None, Some(0.4), Some(0.5)),
+            answer(Answer::Word("animal".into()), AnswerKind::Word, Some("animal"), None, None),
+        ] {
+            let response = AskResponse {
+                model: "dafhne-50".into(),
+                question: "Is a dog an animal?".into(),
+                answer,
+            };
+            check_response(&doc, "AskResponse", response, &["trace"]);
+        }
+        check_response(
+            &doc,
+            "DescribeResponse",
+            DescribeResponse {
+                model: "m".into(),
+                word: "dog".into(),
+                sentences: vec!["a dog is an animal".into()],
+            },
+            &[],
+        );
+        check_response(
+            &doc,
+            "WordsResponse",
+            WordsResponse {
+                model: "m".into(),
+                space: None,
+                count: 1,
+                words: vec!["dog".into()],
+            },
+            &[],
+        );
+        check_response(
+            &doc,
+            "NeighborsResponse",
+            NeighborsResponse {
+                model: "m".into(),
+                word: "dog".into(),
+                spaces: vec![SpaceNeighbors {
+                    space: "content".into(),
+                    neighbors: vec![Neighbor {
+                        word: "cat".into(),
+                        distance: 0.2,
+                        normalized: 0.5,
+                    }],
+                }],
+            },
+            &[],
+        );
+        check_response(
+            &doc,
+            "DistanceResponse",
+            DistanceResponse {
+                model: "m".into(),
+                a: "dog".into(),
+                b: "cat".into(),
+                spaces: vec![SpaceDistance {
+                    space: "content".into(),
+                    distance: 0.2,
+                    normalized: 0.5,
+                }],
+            },
+            &[],
+        );
+        check_response(
+            &doc,
+            "SpacesResponse",
+            SpacesResponse {
+                model: "m".into(),
+                spaces: vec![SpaceInfo {
+                    name: "content".into(),
+                    words: 50,
+                    dimensions: 8,
+                    connectors: 12,
+                    distance_mean: 1.0,
+                    distance_std_dev: 0.2,
+                    yes_threshold: 0.4,
+                    no_threshold: 1.2,
+                    bridges: vec![SpaceBridge {
+                        space: "math".into(),
+                        terms: 3,
+                    }],
+                }],
+            },
+            &[],
+        );
+
+        check_request::<AskRequest>(
+            &doc,
+            "AskRequest",
+            &json!({ "question": "Is a dog an animal?", "model": "dafhne-5", "trace": true }),
+        );
+        check_request::<RouteRequest>(&doc, "RouteRequest", &json!({ "question": "What is two plus three?", "model": "m" }));
+        check_request::<crate::batch::BatchRequest>(
+            &doc,
+            "BatchRequest",
+            &json!({
+                "model": "m",
+                "questions": ["Is a dog an animal?", { "id": "q2", "question": "What is a dog?", "expected": "animal" }],
+                "score": false
+            }),
+        );
+
+        // Documented defaults are the ones serde applies
+        let schema = &doc["components"]["schemas"]["AskRequest"]["properties"];
+        let ask: AskRequest = serde_json::from_value(json!({ "question": "q" })).unwrap();
+        assert_eq!(schema["model"]["default"], ask.model.as_str());
+        assert_eq!(schema["trace"]["default"], ask.trace);
+    }
+
+    #[test]
+    fn neighbors_k_starts_at_one() {
+        let doc = openapi_document();
+        let k = doc["paths"]["/v1/dafhne/neighbors"]["get"]["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"] == "k")
+            .unwrap();
+        assert_eq!(k["schema"]["minimum"], 1);
+        let q: NeighborsQuery = serde_json::from_value(json!({ "word": "dog" })).unwrap();
+        assert_eq!(k["schema"]["default"], q.k);
+    }
+}
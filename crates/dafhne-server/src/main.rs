@@ -2,16 +2,27 @@ mod service;
 mod ollama;
 mod openai;
 mod mcp;
+mod dafhne;
 mod chat;
+mod registry;
+mod admin;
+mod batch;
+mod context;
+mod ws;
+mod auth;
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::path::PathBuf;
+use std::time::Duration;
 
+use axum::extract::DefaultBodyLimit;
 use axum::Router;
 use clap::Parser;
-use tower_http::cors::CorsLayer;
 use tracing_subscriber::EnvFilter;
 
+use auth::{Auth, AuthConfig, KeyConfig, Scope};
+use registry::RegistrySource;
 use service::DafhneService;
 
 #[derive(Parser)]
@@ -23,18 +34,58 @@ struct Cli {
     /// Bind address
     #[arg(long, default_value = "0.0.0.0")]
     host: String,
-    /// Dictionary directory
+    /// Models config (TOML). Without it the built-in dafhne-5 / dafhne-12 /
+    /// dafhne-50 models are loaded from --data-dir.
+    #[arg(long)]
+    models: Option<PathBuf>,
+    /// Dictionary directory (built-in models only)
     #[arg(long, default_value = "./dictionaries")]
     data_dir: PathBuf,
-    /// Genome file (single-space, for dafhne-5 / dafhne-12)
+    /// Genome file (built-in models only; dafhne-5 / dafhne-12, and dafhne-50 without --multi-genome)
     #[arg(long)]
     genome: Option<PathBuf>,
-    /// Multi-space genome file (for dafhne-50)
+    /// Multi-space genome file (built-in dafhne-50 only)
     #[arg(long)]
     multi_genome: Option<PathBuf>,
+    /// Seconds between checks of the models config and its files for
+    /// changes; changed models are retrained and swapped in (0: off)
+    #[arg(long, default_value = "5")]
+    reload_interval: u64,
+    /// Let clients define words at runtime (MCP dafhne_define_word). Taught
+    /// entries live in memory and are lost on restart.
+    #[arg(long)]
+    allow_teaching: bool,
+    /// Access config (TOML): API keys and scopes, CORS origins, rate and
+    /// body limits. See docs/server.md.
+    #[arg(long)]
+    auth_config: Option<PathBuf>,
+    /// API key with every scope (repeatable). Once any key is configured,
+    /// requests without one only get the --anonymous scopes.
+    #[arg(long = "api-key")]
+    api_keys: Vec<String>,
+    /// Scopes of requests without a key when keys are configured (comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    anonymous: Option<Vec<Scope>>,
+    /// Origin allowed to call the API from a browser (repeatable; `*`: any)
+    #[arg(long = "cors-origin")]
+    cors_origins: Vec<String>,
+    /// Requests per minute per key, or per address without a key (0: unlimited)
+    #[arg(long)]
+    rate_limit: Option<u32>,
+    /// Largest request body in bytes
+    #[arg(long)]
+    max_body_bytes: Option<usize>,
     /// Run as MCP server on stdio (no HTTP)
     #[arg(long)]
     mcp_stdio: bool,
+    /// Answer the questions in this file (`-`: stdin) as JSONL on stdout
+    /// and exit (no HTTP). Markdown, .toml or .jsonl test suites are scored;
+    /// other files are read as one question per line.
+    #[arg(long)]
+    batch: Option<PathBuf>,
+    /// Model for --batch
+    #[arg(long, default_value = "dafhne-50")]
+    batch_model: String,
     /// Log level
     #[arg(long, default_value = "info")]
     log_level: String,
@@ -49,19 +100,60 @@ async fn main() {
             EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| EnvFilter::new(&cli.log_level)),
         )
+        .with_writer(std::io::stderr)
         .init();
 
+    let source = match &cli.models {
+        Some(path) => RegistrySource::File(path.clone()),
+        None => RegistrySource::Builtin {
+            data_dir: cli.data_dir.clone(),
+            genome: cli.genome.clone(),
+            multi_genome: cli.multi_genome.clone(),
+        },
+    };
+    let load = |source: RegistrySource| {
+        let mut svc = DafhneService::load(source).unwrap_or_else(|e| panic!("Failed to load models config: {}", e));
+        svc.allow_teaching(cli.allow_teaching);
+        let svc = Arc::new(svc);
+        if cli.reload_interval > 0 {
+            admin::spawn_watcher(svc.clone(), Duration::from_secs(cli.reload_interval));
+        }
+        svc
+    };
+
+    // Batch mode — answer a file of questions, no HTTP
+    if let Some(path) = &cli.batch {
+        tracing::info!("Loading models for batch mode...");
+        let svc = DafhneService::load(source).unwrap_or_else(|e| panic!("Failed to load models config: {}", e));
+        if let Err(e) = batch::run_cli(&svc, &cli.batch_model, path) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // MCP stdio mode — run JSON-RPC on stdin/stdout, no HTTP
     if cli.mcp_stdio {
         tracing::info!("Loading models for MCP stdio mode...");
-        let svc = Arc::new(DafhneService::load(&cli.data_dir, cli.genome.as_deref(), cli.multi_genome.as_deref()));
+        let svc = load(source);
         tracing::info!("Models loaded. Starting MCP stdio...");
         mcp::run_stdio(svc).await;
         return;
     }
 
-    tracing::info!("Loading models from {:?} ...", cli.data_dir);
-    let svc = Arc::new(DafhneService::load(&cli.data_dir, cli.genome.as_deref(), cli.multi_genome.as_deref()));
+    let auth = Arc::new(load_auth(&cli).unwrap_or_else(|e| panic!("Failed to load auth config: {}", e)));
+    if auth.is_open() {
+        tracing::warn!("No API keys configured: anyone who can reach {} has full access", cli.host);
+    } else {
+        tracing::info!("{} API key(s) configured", auth.key_count());
+    }
+    let cors = auth.cors_layer().unwrap_or_else(|e| panic!("Failed to load auth config: {}", e));
+
+    match &cli.models {
+        Some(path) => tracing::info!("Loading models from {:?} ...", path),
+        None => tracing::info!("Loading models from {:?} ...", cli.data_dir),
+    }
+    let svc = load(source);
     tracing::info!("Loaded {} model(s)", svc.model_count());
 
     let app = Router::new()
@@ -71,17 +163,60 @@ async fn main() {
         .merge(openai::routes())
         // MCP endpoint
         .merge(mcp::routes())
+        // Native DAFHNE API
+        .merge(dafhne::routes())
+        .merge(batch::routes())
+        .merge(ws::routes())
+        // Admin (model reload)
+        .merge(admin::routes())
         // Chat UI
         .merge(chat::routes())
         // Root redirect
         .route("/", axum::routing::get(|| async {
             axum::response::Redirect::temporary("/chat")
         }))
-        .layer(CorsLayer::permissive())
+        .layer(axum::middleware::from_fn_with_state(auth.clone(), auth::guard))
+        .layer(DefaultBodyLimit::max(auth.body_limit()))
         .with_state(svc);
+    // Outermost, so preflight requests are answered without a key
+    let app = match cors {
+        Some(cors) => app.layer(cors),
+        None => app,
+    };
 
     let addr = format!("{}:{}", cli.host, cli.port);
     tracing::info!("DAFHNE server listening on http://{}", addr);
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
+}
+
+/// `--auth-config` with the command-line flags applied on top.
+fn load_auth(cli: &Cli) -> Result<Auth, String> {
+    let mut config = match &cli.auth_config {
+        Some(path) => AuthConfig::load(path)?,
+        None => AuthConfig::default(),
+    };
+    for (i, key) in cli.api_keys.iter().enumerate() {
+        config.keys.push(KeyConfig {
+            name: format!("cli-{}", i + 1),
+            key: Some(key.clone()),
+            key_env: None,
+            scopes: Scope::ALL.to_vec(),
+            rate_limit: None,
+            max_body_bytes: None,
+        });
+    }
+    config.cors_origins.extend(cli.cors_origins.iter().cloned());
+    if cli.anonymous.is_some() {
+        config.anonymous = cli.anonymous.clone();
+    }
+    if cli.rate_limit.is_some() {
+        config.rate_limit = cli.rate_limit;
+    }
+    if cli.max_body_bytes.is_some() {
+        config.max_body_bytes = cli.max_body_bytes;
+    }
+    Auth::new(config)
 }
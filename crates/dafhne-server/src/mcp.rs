@@ -1,6 +1,16 @@
+//! MCP server (JSON-RPC over `POST /mcp` or `--mcp-stdio`).
+//!
+//! - Tools: ask, describe, list words, routing, distance, neighbors,
+//!   explain, and `dafhne_define_word` (only with `--allow-teaching`, and
+//!   over HTTP only for keys with the `teach` scope).
+//! - Resources: per model, the space statistics, each space's dictionary
+//!   (markdown, in the dictionary file format) and single entries through
+//!   the `dafhne://models/{model}/spaces/{space}/entries/{word}` template.
+//! - Prompts: templates for the question forms DAFHNE answers.
+
 use std::sync::Arc;
 
-use axum::extract::State;
+use axum::extract::{Extension, State};
 use axum::response::IntoResponse;
 use axum::routing::{get, post};
 use axum::{Json, Router};
@@ -10,7 +20,10 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
 use tokio_stream::StreamExt as _;
 
-use crate::service::DafhneService;
+use dafhne_core::DictionaryEntry;
+
+use crate::auth::{Access, Scope};
+use crate::service::{blocking, DafhneModel, DafhneService};
 
 // ─── JSON-RPC Types ──────────────────────────────────────────
 
@@ -34,7 +47,7 @@ struct JsonRpcResponse {
     error: Option<JsonRpcError>,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize)]
 struct JsonRpcError {
     code: i32,
     message: String,
@@ -48,8 +61,9 @@ const SERVER_VERSION: &str = "0.1.0";
 
 // ─── Tool Definitions ───────────────────────────────────────
 
-fn tool_definitions() -> Value {
-    serde_json::json!({
+/// The tools; `dafhne_define_word` only when `teaching` is allowed.
+fn tool_definitions(teaching: bool) -> Value {
+    let mut defs = serde_json::json!({
         "tools": [
             {
                 "name": "dafhne_ask",
@@ -86,24 +100,88 @@ fn tool_definitions() -> Value {
                     }
                 }
             },
+            {
+                "name": "dafhne_explain",
+                "description": "Ask DAFHNE a question and get the answer with its evidence: answer kind, distance, confidence, the spaces it was routed to and, for multi-space models, the full routing trace.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "question": { "type": "string", "description": "The question to ask" },
+                        "model": { "type": "string", "description": "Model ID (default: dafhne-50)", "default": "dafhne-50" }
+                    },
+                    "required": ["question"]
+                }
+            },
+            {
+                "name": "dafhne_distance",
+                "description": "Geometric distance between two words in every space that knows both. Normalized distance is relative to the space's mean distance; below the yes threshold means DAFHNE treats the words as closely related.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "a": { "type": "string", "description": "First word" },
+                        "b": { "type": "string", "description": "Second word" },
+                        "model": { "type": "string", "description": "Model ID (default: dafhne-50)", "default": "dafhne-50" },
+                        "space": { "type": "string", "description": "Only this space" }
+                    },
+                    "required": ["a", "b"]
+                }
+            },
+            {
+                "name": "dafhne_neighbors",
+                "description": "The nearest words to a word in DAFHNE's geometric space, per space.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "word": { "type": "string", "description": "The word" },
+                        "k": { "type": "integer", "description": "Neighbors per space (default: 10)", "default": 10, "minimum": 1 },
+                        "model": { "type": "string", "description": "Model ID (default: dafhne-50)", "default": "dafhne-50" },
+                        "space": { "type": "string", "description": "Only this space" }
+                    },
+                    "required": ["word"]
+                }
+            },
+            {
+                "name": "dafhne_define_word",
+                "description": "Teach DAFHNE a new word (or, with replace, redefine one) and retrain the model. Dictionaries are closed: every word of the definition and examples must already be known to the target space, otherwise the call fails and lists the unknown words.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "word": { "type": "string", "description": "A single lowercase word" },
+                        "definition": { "type": "string", "description": "Short definition using known words only" },
+                        "examples": { "type": "array", "items": { "type": "string" }, "description": "Example sentences using known words only" },
+                        "model": { "type": "string", "description": "Model ID (default: dafhne-50)", "default": "dafhne-50" },
+                        "space": { "type": "string", "description": "Space to define the word in (required for multi-space models)" },
+                        "replace": { "type": "boolean", "description": "Redefine a word that already exists", "default": false }
+                    },
+                    "required": ["word", "definition"]
+                }
+            },
             {
                 "name": "dafhne_which_space",
-                "description": "Ask DAFHNE which geometric space would handle a given question (content, math, grammar, task, or self).",
+                "description": "Show how DAFHNE routes a question: the activated spaces (content, math, grammar, task, self) and why, the words each space recognised, bridge terms, and whether an arithmetic or special-pattern detector takes over.",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "question": { "type": "string", "description": "The question to route" }
+                        "question": { "type": "string", "description": "The question to route" },
+                        "model": { "type": "string", "description": "Multi-space model ID (default: dafhne-50)", "default": "dafhne-50" }
                     },
                     "required": ["question"]
                 }
             }
         ]
-    })
+    });
+    if !teaching {
+        if let Some(tools) = defs["tools"].as_array_mut() {
+            tools.retain(|t| t["name"] != "dafhne_define_word");
+        }
+    }
+    defs
 }
 
 // ─── Handle a single JSON-RPC request ───────────────────────
 
-fn handle_request(req: &JsonRpcRequest, svc: &DafhneService) -> JsonRpcResponse {
+/// `teach`: whether the caller may use `dafhne_define_word`.
+fn handle_request(req: &JsonRpcRequest, svc: &DafhneService, teach: bool) -> JsonRpcResponse {
     let id = req.id.clone().unwrap_or(Value::Null);
 
     match req.method.as_str() {
@@ -114,7 +192,9 @@ fn handle_request(req: &JsonRpcRequest, svc: &DafhneService) -> JsonRpcResponse
                 result: Some(serde_json::json!({
                     "protocolVersion": MCP_PROTOCOL_VERSION,
                     "capabilities": {
-                        "tools": {}
+                        "tools": {},
+                        "resources": {},
+                        "prompts": {}
                     },
                     "serverInfo": {
                         "name": SERVER_NAME,
@@ -135,7 +215,7 @@ fn handle_request(req: &JsonRpcRequest, svc: &DafhneService) -> JsonRpcResponse
             }
         }
         "tools/list" => {
-            let defs = tool_definitions();
+            let defs = tool_definitions(teach && svc.teaching_allowed());
             JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
                 id,
@@ -154,7 +234,7 @@ fn handle_request(req: &JsonRpcRequest, svc: &DafhneService) -> JsonRpcResponse
                 .cloned()
                 .unwrap_or(Value::Object(serde_json::Map::new()));
 
-            let result = dispatch_tool(tool_name, &arguments, svc);
+            let result = dispatch_tool(tool_name, &arguments, svc, teach);
             JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
                 id,
@@ -162,6 +242,19 @@ fn handle_request(req: &JsonRpcRequest, svc: &DafhneService) -> JsonRpcResponse
                 error: None,
             }
         }
+        "resources/list" => respond(id, Ok(resource_list(svc))),
+        "resources/templates/list" => respond(id, Ok(resource_templates())),
+        "resources/read" => {
+            let uri = req.params.as_ref().and_then(|p| p.get("uri")).and_then(|v| v.as_str()).unwrap_or("");
+            respond(id, read_resource(uri, svc))
+        }
+        "prompts/list" => respond(id, Ok(prompt_list())),
+        "prompts/get" => {
+            let params = req.params.as_ref();
+            let name = params.and_then(|p| p.get("name")).and_then(|v| v.as_str()).unwrap_or("");
+            let arguments = params.and_then(|p| p.get("arguments")).cloned().unwrap_or(Value::Null);
+            respond(id, get_prompt(name, &arguments))
+        }
         _ => {
             JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
@@ -176,7 +269,24 @@ fn handle_request(req: &JsonRpcRequest, svc: &DafhneService) -> JsonRpcResponse
     }
 }
 
-fn dispatch_tool(name: &str, args: &Value, svc: &DafhneService) -> Value {
+fn respond(id: Value, result: Result<Value, JsonRpcError>) -> JsonRpcResponse {
+    let (result, error) = match result {
+        Ok(result) => (Some(result), None),
+        Err(error) => (None, Some(error)),
+    };
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        id,
+        result,
+        error,
+    }
+}
+
+fn invalid_params(message: String) -> JsonRpcError {
+    JsonRpcError { code: -32602, message }
+}
+
+fn dispatch_tool(name: &str, args: &Value, svc: &DafhneService, teach: bool) -> Value {
     match name {
         "dafhne_ask" => {
             let question = args.get("question").and_then(|v| v.as_str()).unwrap_or("");
@@ -241,22 +351,153 @@ fn dispatch_tool(name: &str, args: &Value, svc: &DafhneService) -> Value {
                 tool_error(&format!("Model '{}' not found", model_id))
             }
         }
-        "dafhne_which_space" => {
+        "dafhne_explain" => {
             let question = args.get("question").and_then(|v| v.as_str()).unwrap_or("");
-            // Use dafhne-50 for space routing
-            if let Some(model) = svc.get_model("dafhne-50") {
-                let (answer, _dist, conn) = model.answer(question);
-                // The connector or routing info tells us which space handled it
-                let space_info = conn.unwrap_or_else(|| "unknown".to_string());
+            let model_id = args.get("model").and_then(|v| v.as_str()).unwrap_or("dafhne-50");
+            if let Some(model) = svc.get_model(model_id) {
+                let answer = model.ask(question, true);
+                let mut text = format!("Answer: {}", answer.text);
+                if let Some(confidence) = answer.confidence {
+                    text.push_str(&format!(" (confidence {:.2})", confidence));
+                }
+                if let Some(distance) = answer.distance {
+                    text.push_str(&format!("\nDistance: {:.4}", distance));
+                }
+                if let Some(connector) = &answer.connector {
+                    text.push_str(&format!("\nConnector: {}", connector));
+                }
+                text.push_str(&format!("\nSpaces: {}", answer.spaces.join(", ")));
+                if let Some(trace) = &answer.trace {
+                    text.push_str(&format!("\n\n{}", trace));
+                }
                 serde_json::json!({
                     "content": [{
                         "type": "text",
-                        "text": format!("Space: {} (answer: {})", space_info, answer)
+                        "text": text
                     }],
-                    "isError": false
+                    "isError": false,
+                    "_answer": answer
                 })
             } else {
-                tool_error("Model 'dafhne-50' not loaded")
+                tool_error(&format!("Model '{}' not found", model_id))
+            }
+        }
+        "dafhne_distance" => {
+            let a = args.get("a").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+            let b = args.get("b").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+            let model_id = args.get("model").and_then(|v| v.as_str()).unwrap_or("dafhne-50");
+            let space = args.get("space").and_then(|v| v.as_str());
+            let Some(model) = svc.get_model(model_id) else {
+                return tool_error(&format!("Model '{}' not found", model_id));
+            };
+            if let Some(error) = check_space(&model, space) {
+                return error;
+            }
+            let distances = model.distance(&a, &b, space);
+            let text = if distances.is_empty() {
+                format!("No space knows both '{}' and '{}'.", a, b)
+            } else {
+                distances
+                    .iter()
+                    .map(|d| format!("{}: {:.4} (normalized {:.3})", d.space, d.distance, d.normalized))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            serde_json::json!({
+                "content": [{
+                    "type": "text",
+                    "text": text
+                }],
+                "isError": false,
+                "_distances": distances
+            })
+        }
+        "dafhne_neighbors" => {
+            let word = args.get("word").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+            let k = args.get("k").and_then(|v| v.as_u64()).unwrap_or(10).max(1) as usize;
+            let model_id = args.get("model").and_then(|v| v.as_str()).unwrap_or("dafhne-50");
+            let space = args.get("space").and_then(|v| v.as_str());
+            let Some(model) = svc.get_model(model_id) else {
+                return tool_error(&format!("Model '{}' not found", model_id));
+            };
+            if let Some(error) = check_space(&model, space) {
+                return error;
+            }
+            let neighbors = model.neighbors(&word, k, space);
+            let text = if neighbors.is_empty() {
+                format!("I don't know the word '{}'.", word)
+            } else {
+                neighbors
+                    .iter()
+                    .map(|n| {
+                        let words: Vec<String> =
+                            n.neighbors.iter().map(|w| format!("{} ({:.3})", w.word, w.normalized)).collect();
+                        format!("{}: {}", n.space, words.join(", "))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            serde_json::json!({
+                "content": [{
+                    "type": "text",
+                    "text": text
+                }],
+                "isError": false,
+                "_neighbors": neighbors
+            })
+        }
+        "dafhne_define_word" => {
+            if !teach {
+                return tool_error("This API key may not teach (it needs the 'teach' scope)");
+            }
+            let model_id = args.get("model").and_then(|v| v.as_str()).unwrap_or("dafhne-50");
+            let space = args.get("space").and_then(|v| v.as_str());
+            let replace = args.get("replace").and_then(|v| v.as_bool()).unwrap_or(false);
+            let entry = DictionaryEntry {
+                word: args.get("word").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                definition: args.get("definition").and_then(|v| v.as_str()).unwrap_or("").trim().to_string(),
+                examples: args
+                    .get("examples")
+                    .and_then(|v| v.as_array())
+                    .map(|a| a.iter().filter_map(|e| e.as_str()).map(str::to_string).collect())
+                    .unwrap_or_default(),
+                section: "TAUGHT".to_string(),
+                is_entity: false,
+            };
+            if entry.definition.is_empty() {
+                return tool_error("A definition is required");
+            }
+            match svc.teach(model_id, space, entry, replace) {
+                Ok(event) => serde_json::json!({
+                    "content": [{
+                        "type": "text",
+                        "text": format!(
+                            "Defined '{}'. {} is now at version {}.",
+                            event.word.as_deref().unwrap_or(""),
+                            event.model,
+                            event.version
+                        )
+                    }],
+                    "isError": false,
+                    "_event": event
+                }),
+                Err(e) => tool_error(&e),
+            }
+        }
+        "dafhne_which_space" => {
+            let question = args.get("question").and_then(|v| v.as_str()).unwrap_or("");
+            let model_id = args.get("model").and_then(|v| v.as_str()).unwrap_or("dafhne-50");
+            match svc.get_model(model_id).map(|m| m.explain(question)) {
+                Some(Some(resolution)) => serde_json::json!({
+                    "content": [{
+                        "type": "text",
+                        "text": resolution.to_string()
+                    }],
+                    "isError": false,
+                    "_route": resolution
+                }),
+                Some(None) => tool_error(&format!("Model '{}' is single-space and has no routing", model_id)),
+                None => tool_error(&format!("Model '{}' not found", model_id)),
             }
         }
         _ => {
@@ -265,6 +506,14 @@ fn dispatch_tool(name: &str, args: &Value, svc: &DafhneService) -> Value {
     }
 }
 
+fn check_space(model: &DafhneModel, space: Option<&str>) -> Option<Value> {
+    let space = space?;
+    if model.spaces().iter().any(|v| v.name == space) {
+        return None;
+    }
+    Some(tool_error(&format!("Model '{}' has no space '{}'", model.id, space)))
+}
+
 fn tool_error(msg: &str) -> Value {
     serde_json::json!({
         "content": [{
@@ -275,6 +524,163 @@ fn tool_error(msg: &str) -> Value {
     })
 }
 
+// ─── Resources ──────────────────────────────────────────────
+
+const URI_PREFIX: &str = "dafhne://models/";
+
+fn resource_list(svc: &DafhneService) -> Value {
+    let mut resources = Vec::new();
+    for model in svc.models() {
+        resources.push(serde_json::json!({
+            "uri": format!("{}{}/spaces", URI_PREFIX, model.id),
+            "name": format!("{} spaces", model.id),
+            "description": format!("Size, geometry, answer thresholds and bridges of each space of {}", model.name),
+            "mimeType": "application/json"
+        }));
+        for view in model.spaces() {
+            resources.push(serde_json::json!({
+                "uri": format!("{}{}/spaces/{}/dictionary", URI_PREFIX, model.id, view.name),
+                "name": format!("{} {} dictionary", model.id, view.name),
+                "description": format!("The {} entries DAFHNE was trained on in space '{}'", view.dictionary.entries.len(), view.name),
+                "mimeType": "text/markdown"
+            }));
+        }
+    }
+    serde_json::json!({ "resources": resources })
+}
+
+fn resource_templates() -> Value {
+    serde_json::json!({
+        "resourceTemplates": [{
+            "uriTemplate": format!("{}{{model}}/spaces/{{space}}/entries/{{word}}", URI_PREFIX),
+            "name": "Dictionary entry",
+            "description": "One word's definition and examples in a space",
+            "mimeType": "application/json"
+        }]
+    })
+}
+
+fn read_resource(uri: &str, svc: &DafhneService) -> Result<Value, JsonRpcError> {
+    let not_found = || invalid_params(format!("Resource not found: {}", uri));
+    let path = uri.strip_prefix(URI_PREFIX).ok_or_else(not_found)?;
+    let parts: Vec<&str> = path.split('/').collect();
+    let model = svc.get_model(parts[0]).ok_or_else(not_found)?;
+    let spaces = model.spaces();
+    let space = |name: &str| spaces.iter().find(|v| v.name == name).ok_or_else(not_found);
+
+    let (mime, text) = match parts[1..] {
+        ["spaces"] => (
+            "application/json",
+            serde_json::to_string_pretty(&model.space_info()).expect("Failed to serialize space info"),
+        ),
+        ["spaces", name, "dictionary"] => ("text/markdown", dictionary_markdown(&model, space(name)?.dictionary)),
+        ["spaces", name, "entries", word] => {
+            let entry = space(name)?
+                .dictionary
+                .entries
+                .iter()
+                .find(|e| e.word == word)
+                .ok_or_else(not_found)?;
+            (
+                "application/json",
+                serde_json::to_string_pretty(entry).expect("Failed to serialize dictionary entry"),
+            )
+        }
+        _ => return Err(not_found()),
+    };
+    Ok(serde_json::json!({
+        "contents": [{ "uri": uri, "mimeType": mime, "text": text }]
+    }))
+}
+
+/// A dictionary in the markdown format the dictionary files use.
+fn dictionary_markdown(model: &DafhneModel, dictionary: &dafhne_core::Dictionary) -> String {
+    let mut out = format!("# {} (v{})\n", model.name, model.version);
+    let mut section = None;
+    for entry in &dictionary.entries {
+        if section != Some(&entry.section) && !entry.section.is_empty() {
+            out.push_str(&format!("\n## {}\n", entry.section));
+        }
+        section = Some(&entry.section);
+        out.push_str(&format!("\n**{}** — {}\n", entry.word, entry.definition));
+        for example in &entry.examples {
+            out.push_str(&format!("- \"{}\"\n", example));
+        }
+    }
+    out
+}
+
+// ─── Prompts ────────────────────────────────────────────────
+
+/// (name, description, arguments, question template). `{a:x}` is argument
+/// `x` with an article unless it already has one.
+const PROMPTS: &[(&str, &str, &[&str], &str)] = &[
+    ("is_a", "Is something a kind of something else?", &["subject", "category"], "Is {a:subject} {a:category}?"),
+    ("what_is", "What is a word?", &["word"], "What is {a:word}?"),
+    ("why", "Why is something a kind of something else?", &["subject", "category"], "Why is {a:subject} {a:category}?"),
+    ("can", "Can something do something?", &["subject", "ability"], "Can {a:subject} {ability}?"),
+    ("arithmetic", "Small-number arithmetic in words", &["left", "operator", "right"], "What is {left} {operator} {right}?"),
+];
+
+fn prompt_list() -> Value {
+    let prompts: Vec<Value> = PROMPTS
+        .iter()
+        .map(|(name, description, arguments, _)| {
+            serde_json::json!({
+                "name": name,
+                "description": description,
+                "arguments": arguments
+                    .iter()
+                    .map(|a| serde_json::json!({ "name": a, "required": true }))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    serde_json::json!({ "prompts": prompts })
+}
+
+fn get_prompt(name: &str, args: &Value) -> Result<Value, JsonRpcError> {
+    let (_, description, arguments, template) = PROMPTS
+        .iter()
+        .find(|(n, ..)| *n == name)
+        .ok_or_else(|| invalid_params(format!("Unknown prompt: {}", name)))?;
+    let mut question = template.to_string();
+    for argument in *arguments {
+        let value = args
+            .get(argument)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.trim().is_empty())
+            .ok_or_else(|| invalid_params(format!("Missing argument: {}", argument)))?;
+        let value = value.trim();
+        question = question
+            .replace(&format!("{{a:{}}}", argument), &with_article(value))
+            .replace(&format!("{{{}}}", argument), value);
+    }
+    let text = format!(
+        "Use the dafhne_ask tool to ask DAFHNE: \"{}\"\n\n\
+         DAFHNE only knows the words in its dictionaries and answers \"I don't know\" \
+         for anything else; report its answer as given rather than filling gaps \
+         from your own knowledge. Use dafhne_explain to show the evidence.",
+        question
+    );
+    Ok(serde_json::json!({
+        "description": description,
+        "messages": [{
+            "role": "user",
+            "content": { "type": "text", "text": text }
+        }]
+    }))
+}
+
+fn with_article(noun: &str) -> String {
+    let lower = noun.to_lowercase();
+    if ["a ", "an ", "the "].iter().any(|a| lower.starts_with(a)) {
+        noun.to_string()
+    } else {
+        dafhne_eval::generate::with_article(noun)
+    }
+}
+
 // ─── HTTP Routes ─────────────────────────────────────────────
 
 pub fn routes() -> Router<Arc<DafhneService>> {
@@ -285,9 +691,11 @@ pub fn routes() -> Router<Arc<DafhneService>> {
 
 async fn mcp_post(
     State(svc): State<Arc<DafhneService>>,
+    Extension(access): Extension<Access>,
     Json(req): Json<JsonRpcRequest>,
 ) -> impl IntoResponse {
-    let resp = handle_request(&req, &svc);
+    let teach = access.allows(Scope::Teach);
+    let resp = blocking(move || handle_request(&req, &svc, teach)).await;
     let mut headers = axum::http::HeaderMap::new();
     headers.insert("content-type", "application/json".parse().unwrap());
     (headers, serde_json::to_string(&resp).unwrap())
@@ -327,7 +735,9 @@ pub async fn run_stdio(svc: Arc<DafhneService>) {
 
         match serde_json::from_str::<JsonRpcRequest>(&line) {
             Ok(req) => {
-                let resp = handle_request(&req, &svc);
+                let svc = svc.clone();
+                // Local process: the only gate is --allow-teaching
+                let resp = blocking(move || handle_request(&req, &svc, true)).await;
                 let mut out = serde_json::to_string(&resp).unwrap();
                 out.push('\n');
                 if stdout.write_all(out.as_bytes()).await.is_err() {
@@ -359,3 +769,34 @@ pub async fn run_stdio(svc: Arc<DafhneService>) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompts_fill_their_arguments_and_teaching_is_opt_in() {
+        let prompt = get_prompt("is_a", &serde_json::json!({"subject": "dog", "category": "animal"})).unwrap();
+        let text = prompt["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains("\"Is a dog an animal?\""));
+        let prompt = get_prompt("what_is", &serde_json::json!({"word": "the sun"})).unwrap();
+        assert!(prompt["messages"][0]["content"]["text"].as_str().unwrap().contains("\"What is the sun?\""));
+        let prompt = get_prompt("what_is", &serde_json::json!({"word": "user"})).unwrap();
+        assert!(prompt["messages"][0]["content"]["text"].as_str().unwrap().contains("\"What is a user?\""));
+        assert!(get_prompt("is_a", &serde_json::json!({"subject": "dog"})).is_err());
+        assert!(get_prompt("nope", &Value::Null).is_err());
+        assert_eq!(prompt_list()["prompts"].as_array().unwrap().len(), PROMPTS.len());
+
+        let names = |teaching| -> Vec<String> {
+            tool_definitions(teaching)["tools"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert!(!names(false).contains(&"dafhne_define_word".to_string()));
+        assert!(names(true).contains(&"dafhne_define_word".to_string()));
+        assert!(names(false).contains(&"dafhne_neighbors".to_string()));
+    }
+}
@@ -6,7 +6,8 @@ use axum::routing::{get, post};
 use axum::{Json, Router};
 use serde::{Deserialize, Serialize};
 
-use crate::service::DafhneService;
+use crate::context;
+use crate::service::{blocking, DafhneService};
 
 // ─── Request / Response types ────────────────────────────────
 
@@ -36,6 +37,8 @@ struct ModelTag {
     modified_at: String,
     size: u64,
     digest: String,
+    /// Increases each time the server retrains the model.
+    version: u64,
     details: ModelDetails,
 }
 
@@ -81,14 +84,14 @@ pub fn routes() -> Router<Arc<DafhneService>> {
 // ─── Handlers ────────────────────────────────────────────────
 
 async fn list_tags(State(svc): State<Arc<DafhneService>>) -> Json<TagsResponse> {
-    let models: Vec<ModelTag> = svc.model_order.iter()
-        .filter_map(|id| svc.models.get(id))
+    let models: Vec<ModelTag> = svc.models().iter()
         .map(|m| ModelTag {
             name: m.id.clone(),
             model: m.id.clone(),
-            modified_at: chrono::Utc::now().to_rfc3339(),
+            modified_at: m.loaded_at.to_rfc3339(),
             size: 0,
-            digest: m.id.replace('-', ""),
+            digest: m.digest.clone(),
+            version: m.version,
             details: ModelDetails {
                 parent_model: String::new(),
                 format: "geometric".to_string(),
@@ -131,15 +134,13 @@ async fn chat(
         }
     };
 
-    // Extract last user message
-    let question = req.messages.iter()
-        .rev()
-        .find(|m| m.role == "user")
-        .map(|m| m.content.clone())
-        .unwrap_or_default();
+    // Last user message, with pronouns and follow-ups resolved from earlier turns
+    let question = context::resolve_conversation(
+        req.messages.iter().map(|m| (m.role.as_str(), m.content.as_str())),
+    );
 
     let start = std::time::Instant::now();
-    let (answer, _dist, _conn) = model.answer(&question);
+    let (answer, _dist, _conn) = blocking(move || model.answer(&question)).await;
     let duration_ns = start.elapsed().as_nanos() as u64;
     let content = answer.to_string();
     let now = chrono::Utc::now().to_rfc3339();
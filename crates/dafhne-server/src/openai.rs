@@ -6,7 +6,8 @@ use axum::routing::{get, post};
 use axum::{Json, Router};
 use serde::{Deserialize, Serialize};
 
-use crate::service::DafhneService;
+use crate::context;
+use crate::service::{blocking, DafhneService};
 
 // ─── Request / Response types ────────────────────────────────
 
@@ -42,6 +43,8 @@ struct ModelObject {
     object: String,
     created: i64,
     owned_by: String,
+    /// Increases each time the server retrains the model.
+    version: u64,
 }
 
 #[derive(Serialize)]
@@ -95,14 +98,13 @@ pub fn routes() -> Router<Arc<DafhneService>> {
 // ─── Handlers ────────────────────────────────────────────────
 
 async fn list_models(State(svc): State<Arc<DafhneService>>) -> Json<ModelsResponse> {
-    let created = chrono::Utc::now().timestamp();
-    let data: Vec<ModelObject> = svc.model_order.iter()
-        .filter_map(|id| svc.models.get(id))
+    let data: Vec<ModelObject> = svc.models().iter()
         .map(|m| ModelObject {
             id: m.id.clone(),
             object: "model".to_string(),
-            created,
+            created: m.loaded_at.timestamp(),
             owned_by: "dafhne".to_string(),
+            version: m.version,
         })
         .collect();
 
@@ -139,14 +141,12 @@ async fn chat_completions(
         }
     };
 
-    // Extract last user message
-    let question = req.messages.iter()
-        .rev()
-        .find(|m| m.role == "user")
-        .and_then(|m| m.content.clone())
-        .unwrap_or_default();
+    // Last user message, with pronouns and follow-ups resolved from earlier turns
+    let question = context::resolve_conversation(
+        req.messages.iter().map(|m| (m.role.as_str(), m.content.as_deref().unwrap_or(""))),
+    );
 
-    let (answer, _dist, _conn) = model.answer(&question);
+    let (answer, _dist, _conn) = blocking(move || model.answer(&question)).await;
     let content = answer.to_string();
 
     // Count tokens (approximate: split on whitespace)
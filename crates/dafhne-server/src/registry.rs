@@ -0,0 +1,304 @@
+//! Model registry: which models `dafhne-server` serves (`--models`).
+//!
+//! ```toml
+//! [[model]]
+//! id = "dafhne-5"
+//! name = "DAFHNE 5-Word Spaces"
+//! description = "Core {words}-word vocabulary, single-space"
+//! dict = "dict5.md"                   # single-space: one dictionary
+//! grammar = "grammar5.md"             # optional, trained with the dictionary
+//! genome = "best_genome.json"         # optional dafhne-evolve genome
+//! build = "forcefield"                # forcefield (default) | equilibrium
+//! seed = 123                          # optional, overrides the genome's rng_seed
+//!
+//! [[model]]
+//! id = "dafhne-50"
+//! genome = "multi_genome.json"        # per-space genome, or a single genome for all spaces
+//! [[model.space]]                     # multi-space: one entry per space, in order
+//! name = "content"
+//! dict = "dict5.md"
+//! [[model.space]]
+//! name = "math"
+//! dict = "dict_math5.md"
+//! ```
+//!
+//! Relative paths resolve against the config file's directory. `{words}` and
+//! `{spaces}` in a description are filled in once the model is trained.
+//! Without `--models` the server uses `ModelRegistry::builtin`, the classic
+//! dafhne-5 / dafhne-12 / dafhne-50 line-up.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use dafhne_engine::BuildMode;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ModelRegistry {
+    #[serde(default, rename = "model")]
+    pub models: Vec<ModelSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct ModelSpec {
+    pub id: String,
+    /// Display name (default: the id).
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Single-space dictionary. Exactly one of `dict` and `space` is set.
+    #[serde(default)]
+    pub dict: Option<PathBuf>,
+    /// Grammar text trained with `dict` (single-space only).
+    #[serde(default)]
+    pub grammar: Option<PathBuf>,
+    #[serde(default, rename = "space")]
+    pub spaces: Vec<SpaceSpec>,
+    #[serde(default)]
+    pub genome: Option<PathBuf>,
+    /// "forcefield" (default) or "equilibrium".
+    #[serde(default)]
+    pub build: Option<String>,
+    /// RNG seed. For a per-space genome it is the base seed, mixed with
+    /// each space name.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct SpaceSpec {
+    pub name: String,
+    pub dict: PathBuf,
+}
+
+/// Where the registry comes from; re-read on every reload.
+#[derive(Debug, Clone)]
+pub enum RegistrySource {
+    File(PathBuf),
+    Builtin {
+        data_dir: PathBuf,
+        genome: Option<PathBuf>,
+        multi_genome: Option<PathBuf>,
+    },
+}
+
+impl RegistrySource {
+    pub fn read(&self) -> Result<ModelRegistry, String> {
+        match self {
+            RegistrySource::File(path) => ModelRegistry::load(path),
+            RegistrySource::Builtin { data_dir, genome, multi_genome } => Ok(ModelRegistry::builtin(
+                data_dir,
+                genome.as_deref(),
+                multi_genome.as_deref(),
+            )),
+        }
+    }
+}
+
+impl ModelSpec {
+    pub fn is_multi(&self) -> bool {
+        !self.spaces.is_empty()
+    }
+
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.id.clone())
+    }
+
+    pub fn build_mode(&self) -> BuildMode {
+        parse_build_mode(self.build.as_deref()).unwrap_or_default()
+    }
+
+    /// The description with `{words}` and `{spaces}` filled in.
+    pub fn describe(&self, words: usize, spaces: usize) -> String {
+        let template = match &self.description {
+            Some(d) => d.clone(),
+            None if self.is_multi() => "{words}-word vocabulary across {spaces} spaces".to_string(),
+            None => "{words}-word vocabulary, single-space".to_string(),
+        };
+        template
+            .replace("{words}", &words.to_string())
+            .replace("{spaces}", &spaces.to_string())
+    }
+}
+
+/// "forcefield" / "equilibrium" (same spellings as dafhne-eval's --mode).
+fn parse_build_mode(mode: Option<&str>) -> Option<BuildMode> {
+    match mode.map(|m| m.to_lowercase()).as_deref() {
+        None | Some("forcefield") | Some("force-field") | Some("ff") => Some(BuildMode::ForceField),
+        Some("equilibrium") | Some("eq") => Some(BuildMode::Equilibrium),
+        Some(_) => None,
+    }
+}
+
+impl ModelRegistry {
+    /// Read a registry, check it and resolve its paths against the file's directory.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read models config {:?}: {}", path, e))?;
+        let mut registry: ModelRegistry =
+            toml::from_str(&content).map_err(|e| format!("invalid models config {:?}: {}", path, e))?;
+        registry.validate()?;
+        registry.resolve_paths(path.parent().unwrap_or(Path::new(".")));
+        Ok(registry)
+    }
+
+    /// The classic line-up, read from `data_dir`: dafhne-5 (dict5), dafhne-12
+    /// (dict12) and the five-space dafhne-50. `genome` applies to every model,
+    /// `multi_genome` replaces it for dafhne-50.
+    pub fn builtin(data_dir: &Path, genome: Option<&Path>, multi_genome: Option<&Path>) -> Self {
+        let single = |id: &str, name: &str, description: &str, dict: &str| ModelSpec {
+            id: id.to_string(),
+            name: Some(name.to_string()),
+            description: Some(description.to_string()),
+            dict: Some(PathBuf::from(dict)),
+            grammar: None,
+            spaces: Vec::new(),
+            genome: None,
+            build: None,
+            seed: genome.map(|_| 123),
+        };
+        let spaces = [
+            ("content", "dict5.md"),
+            ("math", "dict_math5.md"),
+            ("grammar", "dict_grammar5.md"),
+            ("task", "dict_task5.md"),
+            ("self", "dict_self5.md"),
+        ];
+        let multi_genome = multi_genome.filter(|p| p.exists()).or(genome);
+        let mut registry = ModelRegistry {
+            models: vec![
+                single("dafhne-5", "DAFHNE 5-Word Spaces", "Core {words}-word vocabulary, single-space", "dict5.md"),
+                single("dafhne-12", "DAFHNE 12-Word Spaces", "Extended {words}-word vocabulary, single-space", "dict12.md"),
+                ModelSpec {
+                    id: "dafhne-50".to_string(),
+                    name: Some("DAFHNE 50 (5-space)".to_string()),
+                    description: Some(
+                        "Full {words}-word vocabulary across {spaces} spaces: content, math, grammar, task, self"
+                            .to_string(),
+                    ),
+                    dict: None,
+                    grammar: None,
+                    spaces: spaces
+                        .iter()
+                        .map(|(name, dict)| SpaceSpec {
+                            name: name.to_string(),
+                            dict: PathBuf::from(dict),
+                        })
+                        .collect(),
+                    genome: None,
+                    build: None,
+                    seed: multi_genome.map(|_| 123),
+                },
+            ],
+        };
+        registry.resolve_paths(data_dir);
+        // Genome paths come from the command line, relative to the working directory
+        for (spec, path) in registry.models.iter_mut().zip([genome, genome, multi_genome]) {
+            spec.genome = path.map(Path::to_path_buf);
+        }
+        registry
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let mut ids = HashSet::new();
+        for m in &self.models {
+            if !ids.insert(m.id.as_str()) {
+                return Err(format!("model '{}' is declared twice", m.id));
+            }
+            match (&m.dict, m.is_multi()) {
+                (Some(_), true) => return Err(format!("model '{}' has both dict and [[model.space]] entries", m.id)),
+                (None, false) => return Err(format!("model '{}' needs a dict or [[model.space]] entries", m.id)),
+                _ => {}
+            }
+            if m.grammar.is_some() && m.is_multi() {
+                return Err(format!("model '{}': grammar is only supported for single-space models", m.id));
+            }
+            let mut names = HashSet::new();
+            if let Some(dup) = m.spaces.iter().find(|s| !names.insert(s.name.as_str())) {
+                return Err(format!("model '{}' declares space '{}' twice", m.id, dup.name));
+            }
+            if parse_build_mode(m.build.as_deref()).is_none() {
+                return Err(format!("model '{}': unknown build mode '{}'", m.id, m.build.as_deref().unwrap_or("")));
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_paths(&mut self, base: &Path) {
+        let resolve = |p: &mut PathBuf| {
+            if p.is_relative() {
+                *p = base.join(&*p);
+            }
+        };
+        for m in &mut self.models {
+            for p in [&mut m.dict, &mut m.grammar, &mut m.genome].into_iter().flatten() {
+                resolve(p);
+            }
+            for s in &mut m.spaces {
+                resolve(&mut s.dict);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_validates_models() {
+        let registry: ModelRegistry = toml::from_str(
+            r#"
+            [[model]]
+            id = "kids"
+            dict = "kids.md"
+            build = "eq"
+
+            [[model]]
+            id = "multi"
+            description = "{words} words in {spaces} spaces"
+            [[model.space]]
+            name = "a"
+            dict = "a.md"
+            [[model.space]]
+            name = "b"
+            dict = "b.md"
+            "#,
+        )
+        .unwrap();
+        registry.validate().unwrap();
+        assert!(!registry.models[0].is_multi());
+        assert_eq!(registry.models[0].build_mode(), BuildMode::Equilibrium);
+        assert_eq!(registry.models[1].spaces.len(), 2);
+        assert_eq!(registry.models[1].describe(40, 2), "40 words in 2 spaces");
+
+        let both: ModelRegistry = toml::from_str(
+            r#"
+            [[model]]
+            id = "bad"
+            dict = "x.md"
+            [[model.space]]
+            name = "a"
+            dict = "a.md"
+            "#,
+        )
+        .unwrap();
+        assert!(both.validate().is_err());
+    }
+
+    #[test]
+    fn shipped_registry_loads() {
+        let registry = ModelRegistry::load(Path::new("../../dictionaries/server_models.toml")).unwrap();
+        let ids: Vec<&str> = registry.models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["dafhne-5", "dafhne-12", "dafhne-50"]);
+        assert_eq!(registry.models[2].spaces.len(), 5);
+
+        for model in &registry.models {
+            let paths = model.dict.iter().chain(&model.grammar).chain(model.spaces.iter().map(|s| &s.dict));
+            for path in paths {
+                assert!(path.exists(), "{} references missing {:?}", model.id, path);
+            }
+        }
+    }
+}
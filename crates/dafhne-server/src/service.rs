@@ -1,25 +1,31 @@
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
-use std::time::Instant;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Instant, SystemTime};
+
+use chrono::{DateTime, Utc};
 
 use dafhne_core::*;
-use dafhne_engine::multispace::{MultiSpace, SpaceConfig};
+use dafhne_engine::multispace::{MultiSpace, Resolution};
 use dafhne_engine::strategy::StrategyConfig;
-use dafhne_engine::{BuildMode, Engine};
-use dafhne_parser::load_dictionary;
-use serde::Deserialize;
+use dafhne_engine::Engine;
+use dafhne_parser::{load_dictionary, parse_grammar_text};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::registry::{ModelSpec, RegistrySource};
 
-/// Resolve a dictionary filename in a data directory.
-/// Tries the given name first (e.g. "dict5.md"), then falls back to ".pkg.toml".
-fn resolve_dict_path(data_dir: &Path, filename: &str) -> Option<PathBuf> {
-    let primary = data_dir.join(filename);
-    if primary.exists() {
-        return Some(primary);
+/// Resolve a dictionary path.
+/// Tries the given path first (e.g. "dict5.md"), then falls back to ".pkg.toml".
+fn resolve_dict_path(path: &Path) -> Option<PathBuf> {
+    if path.exists() {
+        return Some(path.to_path_buf());
     }
     // Fallback: replace .md extension with .pkg.toml
-    if let Some(stem) = filename.strip_suffix(".md") {
-        let fallback = data_dir.join(format!("{}.pkg.toml", stem));
+    if path.extension().is_some_and(|e| e == "md") {
+        let fallback = path.with_extension("pkg.toml");
         if fallback.exists() {
             return Some(fallback);
         }
@@ -41,22 +47,56 @@ struct SingleGenomeFile {
     use_connector_axis: bool,
 }
 
+/// Per-space genome; the space order comes from the model config.
 #[derive(Deserialize)]
-struct SpaceGenomeEntry {
-    params: EngineParams,
-    force_function: String,
-    connector_detection: String,
-    space_init: String,
-    multi_connector: String,
-    negation_model: String,
-    #[serde(default)]
-    use_connector_axis: bool,
+struct MultiGenomeFile {
+    spaces: HashMap<String, SingleGenomeFile>,
 }
 
-#[derive(Deserialize)]
-struct MultiGenomeFile {
-    spaces: HashMap<String, SpaceGenomeEntry>,
-    space_order: Vec<String>,
+enum Genome {
+    Single(EngineParams, StrategyConfig),
+    Multi(MultiGenomeFile),
+}
+
+impl SingleGenomeFile {
+    fn into_parts(self) -> (EngineParams, StrategyConfig) {
+        let strategy = parse_strategy_from_strings(
+            &self.force_function,
+            &self.connector_detection,
+            &self.space_init,
+            &self.multi_connector,
+            &self.negation_model,
+            self.use_connector_axis,
+        );
+        (self.params, strategy)
+    }
+}
+
+/// Read a single- or multi-space genome; `None` (with a warning) when it is
+/// missing or unreadable, so the model falls back to default parameters.
+fn load_genome(path: &Path) -> Option<Genome> {
+    if !path.exists() {
+        tracing::warn!("Genome file {:?} not found, using defaults", path);
+        return None;
+    }
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!("Failed to read genome {:?}: {}", path, e);
+            return None;
+        }
+    };
+    if let Ok(genome) = serde_json::from_str::<MultiGenomeFile>(&content) {
+        tracing::info!("Loaded multi-space genome from {:?}", path);
+        Some(Genome::Multi(genome))
+    } else if let Ok(genome) = serde_json::from_str::<SingleGenomeFile>(&content) {
+        tracing::info!("Loaded single-space genome from {:?}", path);
+        let (params, strategy) = genome.into_parts();
+        Some(Genome::Single(params, strategy))
+    } else {
+        tracing::warn!("Failed to parse genome {:?}, using defaults", path);
+        None
+    }
 }
 
 fn parse_strategy_from_strings(
@@ -103,6 +143,7 @@ fn parse_strategy_from_strings(
             _ => NegationModel::Inversion,
         },
         use_connector_axis,
+        ablation: Default::default(),
     }
 }
 
@@ -124,22 +165,34 @@ pub struct DafhneModel {
     pub description: String,
     pub word_count: usize,
     pub space_count: usize,
-    pub engine: Mutex<ModelEngine>,
+    /// Starts at 1 and increases each time the model is retrained.
+    pub version: u64,
+    /// See `model_digest`.
+    pub digest: String,
+    pub loaded_at: DateTime<Utc>,
+    /// Size and mtime of the files the model was trained from.
+    files: Vec<FileStamp>,
+    spec: ModelSpec,
+    /// Entries added at runtime by `DafhneService::teach`, kept on top of
+    /// the dictionaries across retrains until the server restarts.
+    taught: Vec<TaughtEntry>,
+    /// Trained state. Read-only: queries share it without locking, and a
+    /// changed model is a new `DafhneModel` swapped into the service.
+    pub engine: ModelEngine,
 }
 
 impl DafhneModel {
     /// Answer a question using this model's engine.
     pub fn answer(&self, question: &str) -> (Answer, Option<f64>, Option<String>) {
-        let engine = self.engine.lock().unwrap();
-        match &*engine {
-            ModelEngine::Single { engine, dictionary, params, strategy } => {
+        match &self.engine {
+            ModelEngine::Single { engine, dictionary, strategy, .. } => {
                 dafhne_engine::resolver::resolve_question(
                     question,
                     engine.space(),
                     dictionary,
                     engine.structural(),
                     engine.content(),
-                    params,
+                    &engine.effective_params(),
                     strategy,
                 )
             }
@@ -149,10 +202,49 @@ impl DafhneModel {
         }
     }
 
+    /// Answer a question and report how it was routed. `None` for
+    /// single-space models, which have no routing.
+    pub fn explain(&self, question: &str) -> Option<Resolution> {
+        match &self.engine {
+            ModelEngine::Single { .. } => None,
+            ModelEngine::Multi(multi) => Some(multi.explain(question)),
+        }
+    }
+
+    /// Whether the question's definition chain decides it (`None`: it does
+    /// not), for scoring questions that require chain evidence.
+    pub fn chain_evidence(&self, question: &str) -> Option<bool> {
+        match &self.engine {
+            ModelEngine::Single { engine, dictionary, .. } => dafhne_engine::resolver::chain_evidence(
+                question,
+                engine.space(),
+                dictionary,
+                engine.structural(),
+                engine.content(),
+                &engine.effective_params(),
+            ),
+            ModelEngine::Multi(multi) => dafhne_eval::multispace_chain_evidence(multi, question),
+        }
+    }
+
+    /// Words of `question` that no space of this model knows.
+    pub fn unknown_words(&self, question: &str) -> Vec<String> {
+        let spaces = self.spaces();
+        let mut unknown: Vec<String> = Vec::new();
+        for token in dafhne_parser::tokenize(question) {
+            let known = spaces
+                .iter()
+                .any(|v| dafhne_parser::stem_to_entry(&token, &v.dictionary.entry_set).is_some());
+            if !known && !unknown.contains(&token) {
+                unknown.push(token);
+            }
+        }
+        unknown
+    }
+
     /// Describe a word using this model.
     pub fn describe(&self, word: &str) -> Vec<String> {
-        let engine = self.engine.lock().unwrap();
-        match &*engine {
+        match &self.engine {
             ModelEngine::Single { engine, dictionary, params, strategy, .. } => {
                 dafhne_engine::resolver::describe(
                     word,
@@ -193,8 +285,7 @@ impl DafhneModel {
 
     /// List all words known by this model.
     pub fn list_words(&self, space_filter: Option<&str>) -> Vec<String> {
-        let engine = self.engine.lock().unwrap();
-        match &*engine {
+        match &self.engine {
             ModelEngine::Single { dictionary, .. } => {
                 dictionary.entry_words.clone()
             }
@@ -217,239 +308,917 @@ impl DafhneModel {
         }
     }
 
-    /// Return the space names for this model.
-    pub fn space_names(&self) -> Vec<String> {
-        let engine = self.engine.lock().unwrap();
-        match &*engine {
-            ModelEngine::Single { .. } => vec!["default".to_string()],
-            ModelEngine::Multi(multi) => multi.space_order.clone(),
+    /// This model's spaces, in order. A single-space model has one space
+    /// named "default".
+    pub fn spaces(&self) -> Vec<SpaceView<'_>> {
+        match &self.engine {
+            ModelEngine::Single { engine, dictionary, .. } => vec![SpaceView {
+                name: "default",
+                engine,
+                dictionary,
+            }],
+            ModelEngine::Multi(multi) => multi
+                .space_order
+                .iter()
+                .map(|name| {
+                    let space = &multi.spaces[name];
+                    SpaceView {
+                        name,
+                        engine: &space.engine,
+                        dictionary: &space.dictionary,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Answer a question with its answer kind, routed spaces and confidence;
+    /// `trace` adds the multi-space routing trace.
+    pub fn ask(&self, question: &str, trace: bool) -> StructuredAnswer {
+        let (answer, distance, connector, resolution) = match &self.engine {
+            ModelEngine::Single { .. } => {
+                let (answer, distance, connector) = self.answer(question);
+                (answer, distance, connector, None)
+            }
+            ModelEngine::Multi(multi) => {
+                let r = multi.explain(question);
+                (r.answer.clone(), r.distance, r.connector.clone(), Some(r))
+            }
+        };
+
+        let spaces = self.spaces();
+        let routed: Vec<String> = match &resolution {
+            Some(r) => r.route.activated.iter().map(|a| a.space.clone()).collect(),
+            None => vec![spaces[0].name.to_string()],
+        };
+        // Thresholds of the space whose answer won; the first routed space otherwise
+        let answering = resolution
+            .as_ref()
+            .and_then(|r| r.space_results.iter().find(|s| s.answer == answer && s.distance == distance))
+            .map(|s| s.space_name.as_str())
+            .or(routed.first().map(String::as_str));
+        let params = spaces
+            .iter()
+            .find(|v| Some(v.name) == answering)
+            .unwrap_or(&spaces[0])
+            .engine
+            .effective_params();
+
+        StructuredAnswer {
+            kind: AnswerKind::of_answer(&answer),
+            word: match &answer {
+                Answer::Word(w) => Some(w.clone()),
+                _ => None,
+            },
+            text: answer.to_string(),
+            confidence: confidence(&answer, distance, &params),
+            answer,
+            distance,
+            connector,
+            spaces: routed,
+            trace: resolution.filter(|_| trace),
+        }
+    }
+
+    /// The `k` nearest words to `word` in every space that knows it.
+    pub fn neighbors(&self, word: &str, k: usize, space: Option<&str>) -> Vec<SpaceNeighbors> {
+        self.spaces()
+            .into_iter()
+            .filter(|v| space.is_none_or(|s| s == v.name))
+            .filter_map(|v| {
+                let geo = v.engine.space();
+                let origin = geo.words.get(word)?;
+                let mean = geo.get_distance_stats().mean;
+                let mut neighbors: Vec<Neighbor> = geo
+                    .words
+                    .iter()
+                    .filter(|(w, _)| w.as_str() != word)
+                    .map(|(w, p)| {
+                        let distance = euclidean_distance(&origin.position, &p.position);
+                        Neighbor {
+                            word: w.clone(),
+                            distance,
+                            normalized: normalize(distance, mean),
+                        }
+                    })
+                    .collect();
+                neighbors.sort_by(|a, b| a.distance.total_cmp(&b.distance).then_with(|| a.word.cmp(&b.word)));
+                neighbors.truncate(k);
+                Some(SpaceNeighbors {
+                    space: v.name.to_string(),
+                    neighbors,
+                })
+            })
+            .collect()
+    }
+
+    /// Distance between two words in every space that knows both.
+    pub fn distance(&self, a: &str, b: &str, space: Option<&str>) -> Vec<SpaceDistance> {
+        self.spaces()
+            .into_iter()
+            .filter(|v| space.is_none_or(|s| s == v.name))
+            .filter_map(|v| {
+                let geo = v.engine.space();
+                let distance = euclidean_distance(&geo.words.get(a)?.position, &geo.words.get(b)?.position);
+                Some(SpaceDistance {
+                    space: v.name.to_string(),
+                    distance,
+                    normalized: normalize(distance, geo.get_distance_stats().mean),
+                })
+            })
+            .collect()
+    }
+
+    /// Size, geometry, thresholds and bridges of every space.
+    pub fn space_info(&self) -> Vec<SpaceInfo> {
+        let spaces = self.spaces();
+        spaces
+            .iter()
+            .map(|v| {
+                let geo = v.engine.space();
+                let stats = geo.get_distance_stats();
+                let params = v.engine.effective_params();
+                let bridges = match &self.engine {
+                    ModelEngine::Single { .. } => Vec::new(),
+                    ModelEngine::Multi(multi) => spaces
+                        .iter()
+                        .filter(|o| o.name != v.name)
+                        .filter_map(|o| {
+                            let key = if v.name < o.name {
+                                (v.name.to_string(), o.name.to_string())
+                            } else {
+                                (o.name.to_string(), v.name.to_string())
+                            };
+                            multi.bridges.get(&key).map(|terms| SpaceBridge {
+                                space: o.name.to_string(),
+                                terms: terms.len(),
+                            })
+                        })
+                        .collect(),
+                };
+                SpaceInfo {
+                    name: v.name.to_string(),
+                    words: v.dictionary.entries.len(),
+                    dimensions: geo.dimensions,
+                    connectors: geo.connectors.len(),
+                    distance_mean: stats.mean,
+                    distance_std_dev: stats.std_dev,
+                    yes_threshold: params.yes_threshold,
+                    no_threshold: params.no_threshold,
+                    bridges,
+                }
+            })
+            .collect()
+    }
+}
+
+/// One space of a model, borrowed.
+pub struct SpaceView<'a> {
+    pub name: &'a str,
+    pub engine: &'a Engine,
+    pub dictionary: &'a Dictionary,
+}
+
+// ─── Structured Results ──────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StructuredAnswer {
+    #[serde(skip)]
+    pub answer: Answer,
+    pub kind: AnswerKind,
+    /// Set when `kind` is `Word`.
+    pub word: Option<String>,
+    /// The answer as the chat APIs render it.
+    pub text: String,
+    pub distance: Option<f64>,
+    pub connector: Option<String>,
+    /// See `confidence`.
+    pub confidence: Option<f64>,
+    /// Spaces the question was routed to.
+    pub spaces: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Resolution>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Neighbor {
+    pub word: String,
+    pub distance: f64,
+    /// Distance over the space's mean pairwise distance (the scale the
+    /// yes/no thresholds use).
+    pub normalized: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpaceNeighbors {
+    pub space: String,
+    pub neighbors: Vec<Neighbor>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpaceDistance {
+    pub space: String,
+    pub distance: f64,
+    pub normalized: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpaceBridge {
+    pub space: String,
+    pub terms: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpaceInfo {
+    pub name: String,
+    pub words: usize,
+    pub dimensions: usize,
+    pub connectors: usize,
+    pub distance_mean: f64,
+    pub distance_std_dev: f64,
+    pub yes_threshold: f64,
+    pub no_threshold: f64,
+    /// Shared vocabulary with each other space (multi-space models).
+    pub bridges: Vec<SpaceBridge>,
+}
+
+fn normalize(distance: f64, mean: f64) -> f64 {
+    if mean > 1e-10 { distance / mean } else { distance }
+}
+
+/// How far a Yes/No answer's normalized distance lies outside the
+/// I-don't-know band (below `yes_threshold` or above `no_threshold`), as a
+/// fraction of the threshold it crossed, capped at 1. Negated questions
+/// swap which side means Yes, so only the margin counts, not the side.
+/// Yes/No answers without a distance come from definition chains and count
+/// as 1; I-don't-know is 0. `None` for word answers.
+fn confidence(answer: &Answer, distance: Option<f64>, params: &EngineParams) -> Option<f64> {
+    match (answer, distance) {
+        (Answer::Word(_), _) => None,
+        (Answer::IDontKnow, _) => Some(0.0),
+        (_, None) => Some(1.0),
+        (_, Some(d)) if d < params.yes_threshold && params.yes_threshold > 0.0 => {
+            Some(((params.yes_threshold - d) / params.yes_threshold).clamp(0.0, 1.0))
         }
+        (_, Some(d)) if d > params.no_threshold && params.no_threshold > 0.0 => {
+            Some(((d - params.no_threshold) / params.no_threshold).clamp(0.0, 1.0))
+        }
+        _ => Some(0.0),
     }
 }
 
+/// Run CPU-bound work (resolution, training) on the blocking thread pool so
+/// it does not stall the async runtime.
+pub async fn blocking<T, F>(f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.expect("Failed to join blocking task")
+}
+
 // ─── DafhneService ───────────────────────────────────────────
 
+/// The models currently served. Replaced wholesale on reload; requests keep
+/// the `Arc<DafhneModel>` they started with.
+#[derive(Clone, Default)]
+struct ModelSet {
+    models: HashMap<String, Arc<DafhneModel>>,
+    order: Vec<String>,
+}
+
+/// What `DafhneService::reload` did, per model id.
+#[derive(Debug, Default, Serialize)]
+pub struct ReloadReport {
+    pub loaded: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub removed: Vec<String>,
+    /// Models that could not be (re)trained; a previous version keeps serving.
+    pub failed: Vec<String>,
+}
+
+impl ReloadReport {
+    pub fn changed(&self) -> bool {
+        !self.loaded.is_empty() || !self.removed.is_empty() || !self.failed.is_empty()
+    }
+}
+
+/// A change to the served models, published by `reload`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelEvent {
+    pub event: ModelEventKind,
+    pub model: String,
+    /// Version now serving (for `removed`, the last one served).
+    pub version: u64,
+    /// Dictionary, grammar and genome files that changed since the previous
+    /// version; empty when only the config changed or the reload was forced.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed: Vec<PathBuf>,
+    /// The word defined, for `taught`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word: Option<String>,
+}
+
+impl ModelEvent {
+    fn new(event: ModelEventKind, model: &DafhneModel, changed: Vec<PathBuf>) -> Self {
+        ModelEvent {
+            event,
+            model: model.id.clone(),
+            version: model.version,
+            changed,
+            word: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelEventKind {
+    Added,
+    Retrained,
+    Removed,
+    /// Retraining failed; the previous version keeps serving.
+    Failed,
+    /// A word was defined at runtime (see `DafhneService::teach`).
+    Taught,
+}
+
+/// A dictionary entry added at runtime, and the space it went into.
+#[derive(Debug, Clone)]
+struct TaughtEntry {
+    space: String,
+    entry: DictionaryEntry,
+}
+
 pub struct DafhneService {
-    pub models: HashMap<String, DafhneModel>,
-    pub model_order: Vec<String>,
+    source: RegistrySource,
+    models: RwLock<Arc<ModelSet>>,
+    /// Serialises reloads and remembers the digest of every model last
+    /// attempted, so a model that failed is only retried once its files change.
+    attempted: Mutex<HashMap<String, String>>,
+    events: broadcast::Sender<ModelEvent>,
+    /// Whether `teach` may change models (`--allow-teaching`).
+    teaching: bool,
 }
 
 impl DafhneService {
+    /// Read the registry and train every model in it. Models whose
+    /// dictionaries are missing are skipped with a warning.
+    pub fn load(source: RegistrySource) -> Result<Self, String> {
+        let svc = DafhneService {
+            source,
+            models: RwLock::new(Arc::new(ModelSet::default())),
+            attempted: Mutex::new(HashMap::new()),
+            events: broadcast::channel(64).0,
+            teaching: false,
+        };
+        svc.reload(false)?;
+        Ok(svc)
+    }
+
     pub fn model_count(&self) -> usize {
-        self.models.len()
-    }
-
-    pub fn get_model(&self, id: &str) -> Option<&DafhneModel> {
-        self.models.get(id)
-    }
-
-    pub fn load(data_dir: &Path, genome_path: Option<&Path>, multi_genome_path: Option<&Path>) -> Self {
-        let mut models = HashMap::new();
-        let mut model_order = Vec::new();
-
-        // Load single-space genome if provided
-        let (single_params, single_strategy) = if let Some(gp) = genome_path {
-            if gp.exists() {
-                match std::fs::read_to_string(gp) {
-                    Ok(content) => {
-                        if let Ok(genome) = serde_json::from_str::<SingleGenomeFile>(&content) {
-                            let strategy = parse_strategy_from_strings(
-                                &genome.force_function,
-                                &genome.connector_detection,
-                                &genome.space_init,
-                                &genome.multi_connector,
-                                &genome.negation_model,
-                                genome.use_connector_axis,
-                            );
-                            let mut params = genome.params;
-                            params.rng_seed = 123;
-                            tracing::info!("Loaded single-space genome from {:?}", gp);
-                            (params, strategy)
-                        } else {
-                            tracing::warn!("Failed to parse genome {:?}, using defaults", gp);
-                            (EngineParams::default(), StrategyConfig::default())
-                        }
+        self.snapshot().models.len()
+    }
+
+    pub fn get_model(&self, id: &str) -> Option<Arc<DafhneModel>> {
+        self.snapshot().models.get(id).cloned()
+    }
+
+    /// All models, in registry order.
+    pub fn models(&self) -> Vec<Arc<DafhneModel>> {
+        let set = self.snapshot();
+        set.order.iter().filter_map(|id| set.models.get(id).cloned()).collect()
+    }
+
+    /// Let `teach` define words at runtime.
+    pub fn allow_teaching(&mut self, allow: bool) {
+        self.teaching = allow;
+    }
+
+    pub fn teaching_allowed(&self) -> bool {
+        self.teaching
+    }
+
+    /// Model changes from now on (see `ModelEvent`).
+    pub fn subscribe(&self) -> broadcast::Receiver<ModelEvent> {
+        self.events.subscribe()
+    }
+
+    fn snapshot(&self) -> Arc<ModelSet> {
+        self.models.read().unwrap().clone()
+    }
+
+    /// Re-read the registry and retrain the models whose config or files
+    /// changed (all of them when `force`), then swap the new set in. Blocks
+    /// while training; requests keep being served from the previous set.
+    pub fn reload(&self, force: bool) -> Result<ReloadReport, String> {
+        // A panic while training must not wedge later reloads
+        let mut attempted = self.attempted.lock().unwrap_or_else(|e| e.into_inner());
+        let registry = self.source.read()?;
+        let current = self.snapshot();
+        let mut report = ReloadReport::default();
+        let mut next = ModelSet::default();
+        // Published once `next` is in place, so subscribers see the new versions
+        let mut events = Vec::new();
+
+        for spec in &registry.models {
+            let digest = model_digest(spec);
+            let existing = current.models.get(&spec.id);
+            let up_to_date = existing.is_some_and(|m| m.digest == digest);
+            let retry = attempted.get(&spec.id) != Some(&digest);
+
+            let model = if up_to_date && !force {
+                report.unchanged.push(spec.id.clone());
+                existing.cloned()
+            } else if !retry && !force {
+                // Failed before with the same files; wait for them to change
+                existing.cloned()
+            } else {
+                attempted.insert(spec.id.clone(), digest.clone());
+                let version = existing.map_or(1, |m| m.version + 1);
+                let taught = existing.map(|m| m.taught.clone()).unwrap_or_default();
+                match train_model(spec, digest, version, taught) {
+                    Some(model) => {
+                        report.loaded.push(spec.id.clone());
+                        events.push(match existing {
+                            Some(old) => ModelEvent::new(ModelEventKind::Retrained, &model, changed_files(old, &model)),
+                            None => ModelEvent::new(ModelEventKind::Added, &model, Vec::new()),
+                        });
+                        Some(Arc::new(model))
                     }
-                    Err(e) => {
-                        tracing::warn!("Failed to read genome {:?}: {}", gp, e);
-                        (EngineParams::default(), StrategyConfig::default())
+                    None => {
+                        report.failed.push(spec.id.clone());
+                        if let Some(old) = existing {
+                            events.push(ModelEvent::new(ModelEventKind::Failed, old, Vec::new()));
+                        }
+                        existing.cloned()
                     }
                 }
-            } else {
-                tracing::warn!("Genome file {:?} not found, using defaults", gp);
-                (EngineParams::default(), StrategyConfig::default())
+            };
+            if let Some(model) = model {
+                next.order.push(spec.id.clone());
+                next.models.insert(spec.id.clone(), model);
             }
-        } else {
-            (EngineParams::default(), StrategyConfig::default())
+        }
+
+        for id in &current.order {
+            if !registry.models.iter().any(|m| &m.id == id) {
+                report.removed.push(id.clone());
+                events.push(ModelEvent::new(ModelEventKind::Removed, &current.models[id], Vec::new()));
+            }
+        }
+        attempted.retain(|id, _| registry.models.iter().any(|m| &m.id == id));
+
+        *self.models.write().unwrap() = Arc::new(next);
+        for event in events {
+            // No subscribers is fine
+            let _ = self.events.send(event);
+        }
+        Ok(report)
+    }
+
+    /// Define `word` in one space of a model and swap in the retrained model.
+    /// `space` may be omitted for single-space models. Every word of the
+    /// definition and examples must already be known to that space, and an
+    /// existing entry is only overwritten with `replace`.
+    pub fn teach(
+        &self,
+        model_id: &str,
+        space: Option<&str>,
+        entry: DictionaryEntry,
+        replace: bool,
+    ) -> Result<ModelEvent, String> {
+        if !self.teaching {
+            return Err("teaching is disabled on this server (start it with --allow-teaching)".to_string());
+        }
+        // Serialised with reloads, so neither loses the other's change
+        let _guard = self.attempted.lock().unwrap_or_else(|e| e.into_inner());
+        let model = self
+            .get_model(model_id)
+            .ok_or_else(|| format!("model '{}' not found", model_id))?;
+
+        let word = entry.word.trim().to_string();
+        if word.is_empty() || !word.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(format!("'{}' is not a single lowercase word", entry.word));
+        }
+        let views = model.spaces();
+        let view = match space {
+            Some(name) => views
+                .iter()
+                .find(|v| v.name == name)
+                .ok_or_else(|| format!("model '{}' has no space '{}'", model_id, name))?,
+            None if views.len() == 1 => &views[0],
+            None => return Err(format!("model '{}' has several spaces; say which one", model_id)),
         };
+        if view.dictionary.entry_set.contains(&word) && !replace {
+            return Err(format!("'{}' is already defined in space '{}'; set replace to redefine it", word, view.name));
+        }
+        let mut unknown: Vec<String> = Vec::new();
+        let text = std::iter::once(&entry.definition).chain(&entry.examples);
+        for token in text.flat_map(|t| dafhne_parser::tokenize(t)) {
+            let known = token == word || dafhne_parser::stem_to_entry(&token, &view.dictionary.entry_set).is_some();
+            if !known && !unknown.contains(&token) {
+                unknown.push(token);
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(format!(
+                "the definition uses words space '{}' does not know: {}",
+                view.name,
+                unknown.join(", ")
+            ));
+        }
 
-        // ── dafhne-5: single-space dict5 ──
-        if let Some(dict5_path) = resolve_dict_path(&data_dir, "dict5.md") {
-            let start = Instant::now();
-            let dictionary = load_dictionary(&dict5_path).unwrap();
-            let word_count = dictionary.entries.len();
-
-            let mut engine = Engine::with_strategy(single_params.clone(), single_strategy.clone());
-            engine.set_quiet(true);
-            engine.train(&dictionary);
-
-            let elapsed = start.elapsed();
-            tracing::info!("dafhne-5: {} words trained in {:?}", word_count, elapsed);
-
-            let model = DafhneModel {
-                id: "dafhne-5".to_string(),
-                name: "DAFHNE 5-Word Spaces".to_string(),
-                description: format!("Core {}-word vocabulary, single-space", word_count),
-                word_count,
-                space_count: 1,
-                engine: Mutex::new(ModelEngine::Single {
-                    engine,
-                    dictionary,
-                    params: single_params.clone(),
-                    strategy: single_strategy.clone(),
-                }),
-            };
-            model_order.push("dafhne-5".to_string());
-            models.insert("dafhne-5".to_string(), model);
+        let space = view.name.to_string();
+        let mut taught = model.taught.clone();
+        taught.retain(|t| !(t.space == space && t.entry.word == word));
+        taught.push(TaughtEntry {
+            space,
+            entry: DictionaryEntry { word: word.clone(), ..entry },
+        });
+        let retrained = train_model(&model.spec, model.digest.clone(), model.version + 1, taught)
+            .ok_or_else(|| format!("model '{}' could not be retrained", model_id))?;
+
+        let mut event = ModelEvent::new(ModelEventKind::Taught, &retrained, Vec::new());
+        event.word = Some(word);
+        {
+            let mut models = self.models.write().unwrap();
+            let mut next = ModelSet::clone(&models);
+            next.models.insert(model.id.clone(), Arc::new(retrained));
+            *models = Arc::new(next);
         }
+        let _ = self.events.send(event.clone());
+        Ok(event)
+    }
+}
 
-        // ── dafhne-12: single-space dict12 ──
-        if let Some(dict12_path) = resolve_dict_path(&data_dir, "dict12.md") {
-            let start = Instant::now();
-            let dictionary = load_dictionary(&dict12_path).unwrap();
-            let word_count = dictionary.entries.len();
-
-            let mut engine = Engine::with_strategy(single_params.clone(), single_strategy.clone());
-            engine.set_quiet(true);
-            engine.train(&dictionary);
-
-            let elapsed = start.elapsed();
-            tracing::info!("dafhne-12: {} words trained in {:?}", word_count, elapsed);
-
-            let model = DafhneModel {
-                id: "dafhne-12".to_string(),
-                name: "DAFHNE 12-Word Spaces".to_string(),
-                description: format!("Extended {}-word vocabulary, single-space", word_count),
-                word_count,
-                space_count: 1,
-                engine: Mutex::new(ModelEngine::Single {
-                    engine,
-                    dictionary,
-                    params: single_params.clone(),
-                    strategy: single_strategy.clone(),
-                }),
+/// A file a model reads, with its size and mtime (`None` if unreadable).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FileStamp {
+    path: PathBuf,
+    meta: Option<(u64, Option<SystemTime>)>,
+}
+
+fn model_files(spec: &ModelSpec) -> Vec<FileStamp> {
+    let dicts = spec.dict.iter().chain(spec.spaces.iter().map(|s| &s.dict));
+    dicts
+        .map(|d| resolve_dict_path(d).unwrap_or_else(|| d.clone()))
+        .chain(spec.grammar.iter().cloned())
+        .chain(spec.genome.iter().cloned())
+        .map(|path| FileStamp {
+            meta: std::fs::metadata(&path).ok().map(|m| (m.len(), m.modified().ok())),
+            path,
+        })
+        .collect()
+}
+
+/// Digest of a model's config and the size and mtime of every file it
+/// reads; a model is retrained when this changes.
+fn model_digest(spec: &ModelSpec) -> String {
+    let mut hasher = DefaultHasher::new();
+    spec.hash(&mut hasher);
+    model_files(spec).hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+fn changed_files(old: &DafhneModel, new: &DafhneModel) -> Vec<PathBuf> {
+    new.files
+        .iter()
+        .filter(|f| !old.files.contains(f))
+        .map(|f| f.path.clone())
+        .collect()
+}
+
+fn train_model(spec: &ModelSpec, digest: String, version: u64, taught: Vec<TaughtEntry>) -> Option<DafhneModel> {
+    let start = Instant::now();
+    let engine = if spec.is_multi() {
+        load_multi(spec, &taught)
+    } else {
+        load_single(spec, &taught)
+    }?;
+
+    let (word_count, space_count) = match &engine {
+        ModelEngine::Single { dictionary, .. } => (dictionary.entries.len(), 1),
+        ModelEngine::Multi(multi) => (
+            multi.spaces.values().map(|s| s.dictionary.entries.len()).sum(),
+            multi.spaces.len(),
+        ),
+    };
+    tracing::info!(
+        "{} v{}: {} words across {} space(s), trained in {:?}",
+        spec.id,
+        version,
+        word_count,
+        space_count,
+        start.elapsed()
+    );
+
+    Some(DafhneModel {
+        id: spec.id.clone(),
+        name: spec.display_name(),
+        description: spec.describe(word_count, space_count),
+        word_count,
+        space_count,
+        version,
+        digest,
+        loaded_at: Utc::now(),
+        files: model_files(spec),
+        spec: spec.clone(),
+        taught,
+        engine,
+    })
+}
+
+// ─── Model Loading ───────────────────────────────────────────
+
+/// `dictionary` with the entries taught to `space` added, or replacing
+/// the entries they redefine.
+fn with_taught(mut dictionary: Dictionary, space: &str, taught: &[TaughtEntry]) -> Dictionary {
+    let taught: Vec<&DictionaryEntry> = taught.iter().filter(|t| t.space == space).map(|t| &t.entry).collect();
+    if taught.is_empty() {
+        return dictionary;
+    }
+    for entry in taught {
+        match dictionary.entries.iter_mut().find(|e| e.word == entry.word) {
+            Some(existing) => *existing = entry.clone(),
+            None => dictionary.entries.push(entry.clone()),
+        }
+    }
+    dictionary.entry_words = dictionary.entries.iter().map(|e| e.word.clone()).collect();
+    dictionary.entry_set = dictionary.entry_words.iter().cloned().collect();
+    dictionary
+}
+
+fn load_single(spec: &ModelSpec, taught: &[TaughtEntry]) -> Option<ModelEngine> {
+    let dict = spec.dict.as_deref()?;
+    let Some(dict_path) = resolve_dict_path(dict) else {
+        tracing::warn!("{}: dictionary {:?} not found, skipping", spec.id, dict);
+        return None;
+    };
+    let dictionary = match load_dictionary(&dict_path) {
+        Ok(dictionary) => with_taught(dictionary, "default", taught),
+        Err(e) => {
+            tracing::warn!("{}: failed to read dictionary {:?}: {}", spec.id, dict_path, e);
+            return None;
+        }
+    };
+
+    let (mut params, strategy) = match spec.genome.as_deref().and_then(load_genome) {
+        Some(Genome::Single(params, strategy)) => (params, strategy),
+        Some(Genome::Multi(_)) => {
+            tracing::warn!("{}: per-space genome on a single-space model, using defaults", spec.id);
+            (EngineParams::default(), StrategyConfig::default())
+        }
+        None => (EngineParams::default(), StrategyConfig::default()),
+    };
+    if let Some(seed) = spec.seed {
+        params.rng_seed = seed;
+    }
+
+    let mut engine = Engine::with_strategy(params.clone(), strategy.clone());
+    engine.set_quiet(true);
+    engine.set_mode(spec.build_mode());
+    match &spec.grammar {
+        Some(path) => {
+            let content = match std::fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) => {
+                    tracing::warn!("{}: failed to read grammar {:?}: {}", spec.id, path, e);
+                    return None;
+                }
             };
-            model_order.push("dafhne-12".to_string());
-            models.insert("dafhne-12".to_string(), model);
+            engine.train_with_grammar(&dictionary, &parse_grammar_text(&content));
+        }
+        None => engine.train(&dictionary),
+    }
+
+    Some(ModelEngine::Single {
+        engine,
+        dictionary,
+        params,
+        strategy,
+    })
+}
+
+fn load_multi(spec: &ModelSpec, taught: &[TaughtEntry]) -> Option<ModelEngine> {
+    let mut dictionaries = Vec::new();
+    for space in &spec.spaces {
+        let Some(path) = resolve_dict_path(&space.dict) else {
+            tracing::warn!("{}: dictionary {:?} for space '{}' not found, skipping", spec.id, space.dict, space.name);
+            return None;
+        };
+        match load_dictionary(&path) {
+            Ok(dictionary) => dictionaries.push((space.name.clone(), with_taught(dictionary, &space.name, taught))),
+            Err(e) => {
+                tracing::warn!("{}: failed to read dictionary {:?}: {}", spec.id, path, e);
+                return None;
+            }
         }
+    }
 
-        // ── dafhne-50: multi-space (5 spaces) ──
-        let space_dicts = [
-            ("content", "dict5.md"),
-            ("math", "dict_math5.md"),
-            ("grammar", "dict_grammar5.md"),
-            ("task", "dict_task5.md"),
-            ("self", "dict_self5.md"),
-        ];
-        // Resolve each dictionary with .pkg.toml fallback
-        let resolved_space_dicts: Vec<(&str, PathBuf)> = space_dicts.iter()
-            .filter_map(|(name, file)| {
-                resolve_dict_path(&data_dir, file).map(|p| (*name, p))
-            })
-            .collect();
-        let all_exist = resolved_space_dicts.len() == space_dicts.len();
-        if all_exist {
-            let start = Instant::now();
-
-            // Try to load multi-space genome for per-space params
-            let multi_genome = multi_genome_path
-                .and_then(|p| {
-                    if p.exists() {
-                        std::fs::read_to_string(p).ok()
-                    } else {
-                        None
-                    }
-                })
-                .and_then(|content| serde_json::from_str::<MultiGenomeFile>(&content).ok());
-
-            let multi = if let Some(ref mg) = multi_genome {
-                tracing::info!("Loading dafhne-50 with per-space genome");
-                let mut space_params: HashMap<String, (EngineParams, StrategyConfig)> = HashMap::new();
-                for (name, sg) in &mg.spaces {
-                    let strategy = parse_strategy_from_strings(
-                        &sg.force_function,
-                        &sg.connector_detection,
-                        &sg.space_init,
-                        &sg.multi_connector,
-                        &sg.negation_model,
-                        sg.use_connector_axis,
-                    );
-                    let mut params = sg.params.clone();
+    // Per-space params from a per-space genome; `uniform` for every other space
+    let mut space_params: HashMap<String, (EngineParams, StrategyConfig)> = HashMap::new();
+    let uniform = match spec.genome.as_deref().and_then(load_genome) {
+        Some(Genome::Multi(genome)) => {
+            for (name, sg) in genome.spaces {
+                let (mut params, strategy) = sg.into_parts();
+                if let Some(seed) = spec.seed {
                     // Unique seed per space
                     let space_hash = name.bytes().fold(0u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64));
-                    params.rng_seed = 123u64.wrapping_mul(6364136223846793005).wrapping_add(space_hash);
-                    space_params.insert(name.clone(), (params, strategy));
+                    params.rng_seed = seed.wrapping_mul(6364136223846793005).wrapping_add(space_hash);
                 }
+                space_params.insert(name, (params, strategy));
+            }
+            (EngineParams::default(), StrategyConfig::default())
+        }
+        genome => {
+            let (mut params, strategy) = match genome {
+                Some(Genome::Single(params, strategy)) => (params, strategy),
+                _ => (EngineParams::default(), StrategyConfig::default()),
+            };
+            if let Some(seed) = spec.seed {
+                params.rng_seed = seed;
+            }
+            (params, strategy)
+        }
+    };
 
-                let configs: Vec<SpaceConfig> = mg.space_order.iter()
-                    .map(|name| {
-                        let path = resolved_space_dicts.iter()
-                            .find(|(n, _)| *n == name.as_str())
-                            .map(|(_, p)| p.to_string_lossy().to_string())
-                            .unwrap_or_else(|| data_dir.join("dict5.md").to_string_lossy().to_string());
-                        SpaceConfig {
-                            name: name.clone(),
-                            dict_path: path,
-                        }
-                    })
-                    .collect();
+    let spaces = dictionaries
+        .into_iter()
+        .map(|(name, dictionary)| {
+            let (params, strategy) = space_params.remove(&name).unwrap_or_else(|| uniform.clone());
+            (name, dictionary, params, strategy)
+        })
+        .collect();
+    Some(ModelEngine::Multi(MultiSpace::from_dictionaries(spaces, spec.build_mode())))
+}
 
-                MultiSpace::new_per_space(
-                    configs,
-                    &space_params,
-                    &EngineParams::default(),
-                    &StrategyConfig::default(),
-                    BuildMode::ForceField,
-                )
-            } else {
-                // Fall back to uniform params
-                let configs: Vec<SpaceConfig> = resolved_space_dicts.iter()
-                    .map(|(name, path)| SpaceConfig {
-                        name: name.to_string(),
-                        dict_path: path.to_string_lossy().to_string(),
-                    })
-                    .collect();
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
 
-                MultiSpace::new(
-                    configs,
-                    &single_params,
-                    &single_strategy,
-                    BuildMode::ForceField,
-                )
-            };
+    const DICT: &str = "\
+**thing** — all that is.
+- \"a dog is a thing\"
 
-            let total_words: usize = multi.spaces.values()
-                .map(|s| s.dictionary.entries.len())
-                .sum();
-            let space_count = multi.spaces.len();
-
-            let elapsed = start.elapsed();
-            tracing::info!("dafhne-50: {} total words across {} spaces, trained in {:?}",
-                total_words, space_count, elapsed);
-
-            let model = DafhneModel {
-                id: "dafhne-50".to_string(),
-                name: "DAFHNE 50 (5-space)".to_string(),
-                description: format!("Full {}-word vocabulary across {} spaces: content, math, grammar, task, self",
-                    total_words, space_count),
-                word_count: total_words,
-                space_count,
-                engine: Mutex::new(ModelEngine::Multi(multi)),
-            };
-            model_order.push("dafhne-50".to_string());
-            models.insert("dafhne-50".to_string(), model);
+**a** — one thing.
+- \"a dog\"
+
+**is** — tells what a thing is.
+- \"a dog is an animal\"
+
+**an** — one thing.
+- \"an animal\"
+
+**animal** — a thing that can move.
+- \"a dog is an animal\"
+
+**dog** — an animal.
+- \"a dog is an animal\"
+";
+
+    /// A registry with one single-space model over `DICT`, in a fresh directory.
+    pub(crate) fn registry_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dafhne_reload_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("dict.md"), DICT).unwrap();
+        std::fs::write(dir.join("models.toml"), "[[model]]\nid = \"tiny\"\ndict = \"dict.md\"\n").unwrap();
+        dir
+    }
+
+    fn word_count(svc: &DafhneService) -> usize {
+        svc.get_model("tiny").unwrap().word_count
+    }
+
+    fn knows(svc: &DafhneService, word: &str) -> bool {
+        let model = svc.get_model("tiny").unwrap();
+        let known = model.spaces()[0].dictionary.entry_set.contains(word);
+        known
+    }
+
+    #[test]
+    fn reload_tracks_digests_versions_and_failures() {
+        let dir = registry_dir("digest");
+        let svc = DafhneService::load(RegistrySource::File(dir.join("models.toml"))).unwrap();
+        let mut events = svc.subscribe();
+        assert_eq!(svc.get_model("tiny").unwrap().version, 1);
+
+        // Nothing changed: nothing retrained
+        let report = svc.reload(false).unwrap();
+        assert_eq!(report.unchanged, vec!["tiny"]);
+        assert!(!report.changed());
+
+        // A modified dictionary changes the digest and bumps the version
+        std::fs::write(dir.join("dict.md"), format!("{}\n**cat** — an animal.\n- \"a cat is an animal\"\n", DICT)).unwrap();
+        let report = svc.reload(false).unwrap();
+        assert_eq!(report.loaded, vec!["tiny"]);
+        assert_eq!(svc.get_model("tiny").unwrap().version, 2);
+        assert_eq!(word_count(&svc), 7);
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.event, ModelEventKind::Retrained);
+        assert_eq!(event.changed, vec![dir.join("dict.md")]);
+
+        // A failed retrain keeps the previous version serving...
+        std::fs::remove_file(dir.join("dict.md")).unwrap();
+        let report = svc.reload(false).unwrap();
+        assert_eq!(report.failed, vec!["tiny"]);
+        assert_eq!(svc.get_model("tiny").unwrap().version, 2);
+        assert_eq!(events.try_recv().unwrap().event, ModelEventKind::Failed);
+        // ...is not retried while its files stay the same...
+        let report = svc.reload(false).unwrap();
+        assert!(!report.changed());
+        assert!(report.unchanged.is_empty());
+        // ...and is retried once they change
+        std::fs::write(dir.join("dict.md"), DICT).unwrap();
+        let report = svc.reload(false).unwrap();
+        assert_eq!(report.loaded, vec!["tiny"]);
+        assert_eq!(svc.get_model("tiny").unwrap().version, 3);
+        assert_eq!(word_count(&svc), 6);
+
+        // Dropping the model from the registry removes it
+        std::fs::write(dir.join("models.toml"), "").unwrap();
+        let _ = events.try_recv();
+        let report = svc.reload(false).unwrap();
+        assert_eq!(report.removed, vec!["tiny"]);
+        assert!(svc.get_model("tiny").is_none());
+        let event = events.try_recv().unwrap();
+        assert_eq!((event.event, event.version), (ModelEventKind::Removed, 3));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn taught_words_survive_concurrent_reloads() {
+        let dir = registry_dir("teach");
+        let mut svc = DafhneService::load(RegistrySource::File(dir.join("models.toml"))).unwrap();
+        svc.allow_teaching(true);
+        let svc = Arc::new(svc);
+        let pup = DictionaryEntry {
+            word: "pup".to_string(),
+            definition: "a dog.".to_string(),
+            examples: vec!["a pup is a dog".to_string()],
+            section: String::new(),
+            is_entity: false,
+        };
+
+        let reloader = {
+            let svc = svc.clone();
+            std::thread::spawn(move || svc.reload(true).unwrap())
+        };
+        let event = svc.teach("tiny", None, pup.clone(), false).unwrap();
+        assert_eq!(event.word.as_deref(), Some("pup"));
+        reloader.join().unwrap();
+
+        // Whichever ran first, the other built on it
+        assert_eq!(svc.get_model("tiny").unwrap().version, 3);
+        assert!(knows(&svc, "pup"));
+        // A file change retrains with the taught entry carried forward
+        std::fs::write(dir.join("dict.md"), format!("{}\n", DICT)).unwrap();
+        assert_eq!(svc.reload(false).unwrap().loaded, vec!["tiny"]);
+        assert!(knows(&svc, "pup"));
+        assert!(svc.teach("tiny", None, pup, false).unwrap_err().contains("already defined"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn one_model_answers_from_many_threads() {
+        let dir = registry_dir("threads");
+        let svc = DafhneService::load(RegistrySource::File(dir.join("models.toml"))).unwrap();
+        let model: Arc<DafhneModel> = svc.get_model("tiny").unwrap();
+        let questions = ["Is a dog an animal?", "Is a dog a thing?", "What is a dog?", "Is an animal a dog?"];
+        let expected: Vec<_> = questions.iter().map(|q| model.answer(q)).collect();
+
+        // No lock around the model: every thread reads the same space at once
+        let threads: Vec<_> = (0..8)
+            .map(|t| {
+                let model = model.clone();
+                std::thread::spawn(move || {
+                    (0..50)
+                        .map(|i| {
+                            let q = (t + i) % questions.len();
+                            (q, model.answer(questions[q]))
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        for thread in threads {
+            for (q, answer) in thread.join().unwrap() {
+                assert_eq!(answer, expected[q], "{}", questions[q]);
+            }
         }
+        std::fs::remove_dir_all(&dir).ok();
+    }
 
-        DafhneService { models, model_order }
+    #[test]
+    fn confidence_grows_past_the_thresholds() {
+        let params = EngineParams::default(); // yes < 0.7, no > 1.0
+        let yes = |d| confidence(&Answer::Yes, Some(d), &params).unwrap();
+        assert!((yes(0.35) - 0.5).abs() < 1e-9);
+        assert!(yes(0.1) > yes(0.6));
+        assert_eq!(confidence(&Answer::No, Some(3.0), &params), Some(1.0));
+        assert_eq!(confidence(&Answer::IDontKnow, Some(0.8), &params), Some(0.0));
+        assert_eq!(confidence(&Answer::Yes, None, &params), Some(1.0));
+        assert_eq!(confidence(&Answer::Word("dog".into()), Some(0.2), &params), None);
     }
 }
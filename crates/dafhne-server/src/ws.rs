@@ -0,0 +1,471 @@
+//! WebSocket sessions at `/v1/dafhne/ws`.
+//!
+//! Each connection is a session with its own model, conversation history
+//! (follow-ups resolve as in the chat endpoints, see `context`) and the
+//! words its questions used that the model does not know yet: the open
+//! topics of a teaching dialogue. Model changes are pushed as they happen.
+//!
+//! Client → server, one JSON object per text frame:
+//!
+//! ```json
+//! {"type": "ask", "question": "Can it make sound?", "id": 7, "trace": true}
+//! {"type": "session", "model": "dafhne-5", "trace": false}
+//! {"type": "teach", "id": 8, "word": "pup", "definition": "a young dog.", "space": "content"}
+//! {"type": "reset"}
+//! ```
+//!
+//! `teach` defines a word in the session's model the way MCP
+//! `dafhne_define_word` does, for keys with the `teach` scope.
+//!
+//! Server → client: `hello` on connect, `answer` for each `ask`, `taught`
+//! for each successful `teach`, `session` after `session` / `reset` and
+//! whenever the open topics change, `model` events (see `ModelEvent`) and
+//! `error`.
+
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Extension, State};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::auth::{Access, Quota, Scope};
+use crate::context;
+use crate::dafhne::default_model;
+use dafhne_core::DictionaryEntry;
+
+use crate::service::{blocking, DafhneService, ModelEvent, ModelEventKind, StructuredAnswer};
+
+/// Messages of history kept per session (user and assistant turns).
+const MAX_HISTORY: usize = 40;
+
+/// Open topics (unknown words) kept per session; the oldest are dropped first.
+const MAX_UNKNOWN: usize = 40;
+
+// ─── Request / Response types ────────────────────────────────
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Ask {
+        /// Echoed back on the answer.
+        #[serde(default)]
+        id: Option<Value>,
+        question: String,
+        /// Overrides the session's trace setting for this question.
+        #[serde(default)]
+        trace: Option<bool>,
+    },
+    Session {
+        #[serde(default)]
+        model: Option<String>,
+        #[serde(default)]
+        trace: Option<bool>,
+    },
+    /// Define a word in the session's model, as MCP `dafhne_define_word`.
+    Teach {
+        /// Echoed back on the reply.
+        #[serde(default)]
+        id: Option<Value>,
+        word: String,
+        definition: String,
+        #[serde(default)]
+        examples: Vec<String>,
+        /// Required for multi-space models.
+        #[serde(default)]
+        space: Option<String>,
+        /// Redefine a word that already exists.
+        #[serde(default)]
+        replace: bool,
+    },
+    Reset,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Hello {
+        session: String,
+        model: String,
+        models: Vec<ModelVersion>,
+    },
+    Answer {
+        id: Option<Value>,
+        model: String,
+        version: u64,
+        question: String,
+        /// The question after resolving pronouns and follow-ups, when it changed.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        resolved: Option<String>,
+        #[serde(flatten)]
+        answer: Box<StructuredAnswer>,
+        /// Words in this question the model does not know.
+        unknown_words: Vec<String>,
+    },
+    Taught {
+        id: Option<Value>,
+        model: String,
+        version: u64,
+        word: String,
+    },
+    Session {
+        session: String,
+        model: String,
+        trace: bool,
+        turns: usize,
+        /// Unknown words from this session's questions, oldest first.
+        unknown: Vec<String>,
+    },
+    Model(ModelEvent),
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<Value>,
+        error: String,
+    },
+}
+
+#[derive(Serialize)]
+struct ModelVersion {
+    id: String,
+    version: u64,
+}
+
+/// Per-connection state.
+struct Session {
+    id: String,
+    model: String,
+    trace: bool,
+    /// The key has the `teach` scope.
+    teach: bool,
+    /// (role, content) of earlier turns, questions as asked.
+    history: Vec<(String, String)>,
+    unknown: Vec<String>,
+}
+
+impl Session {
+    fn message(&self) -> ServerMessage {
+        ServerMessage::Session {
+            session: self.id.clone(),
+            model: self.model.clone(),
+            trace: self.trace,
+            turns: self.history.len() / 2,
+            unknown: self.unknown.clone(),
+        }
+    }
+
+    /// Add new unknown words, evicting the oldest past `MAX_UNKNOWN`.
+    /// Returns whether the list changed.
+    fn note_unknown(&mut self, words: &[String]) -> bool {
+        let mut changed = false;
+        for word in words {
+            if !self.unknown.contains(word) {
+                self.unknown.push(word.clone());
+                changed = true;
+            }
+        }
+        let excess = self.unknown.len().saturating_sub(MAX_UNKNOWN);
+        self.unknown.drain(..excess);
+        changed
+    }
+}
+
+// ─── Routes ──────────────────────────────────────────────────
+
+pub fn routes() -> Router<Arc<DafhneService>> {
+    Router::new()
+        .route("/v1/dafhne/ws", get(upgrade))
+}
+
+// ─── Handlers ────────────────────────────────────────────────
+
+async fn upgrade(
+    State(svc): State<Arc<DafhneService>>,
+    Extension(access): Extension<Access>,
+    Extension(quota): Extension<Quota>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.max_message_size(access.max_body)
+        .on_upgrade(move |socket| session(socket, svc, access, quota))
+}
+
+/// One connection. Every `ask` and `teach` takes a token from the caller's `quota`.
+async fn session(mut socket: WebSocket, svc: Arc<DafhneService>, access: Access, quota: Quota) {
+    let mut events = svc.subscribe();
+    let mut session = Session {
+        id: uuid::Uuid::new_v4().to_string(),
+        model: default_model(),
+        trace: false,
+        teach: access.allows(Scope::Teach),
+        history: Vec::new(),
+        unknown: Vec::new(),
+    };
+    let hello = ServerMessage::Hello {
+        session: session.id.clone(),
+        model: session.model.clone(),
+        models: svc
+            .models()
+            .iter()
+            .map(|m| ModelVersion { id: m.id.clone(), version: m.version })
+            .collect(),
+    };
+    if send(&mut socket, &hello).await.is_err() {
+        return;
+    }
+
+    loop {
+        let replies = tokio::select! {
+            frame = socket.recv() => match frame {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(msg) => handle(msg, &mut session, &svc, &quota).await,
+                    Err(e) => vec![error(None, format!("invalid message: {}", e))],
+                },
+                Some(Ok(Message::Binary(_))) => vec![error(None, "expected a JSON text frame".to_string())],
+                Some(Ok(_)) => continue,
+                Some(Err(_)) | None => break,
+            },
+            event = events.recv() => match event {
+                Ok(event) => model_event(event, &mut session, &svc).await,
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("WebSocket session {} missed {} model event(s)", session.id, missed);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            },
+        };
+        for reply in &replies {
+            if send(&mut socket, reply).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+async fn handle(msg: ClientMessage, session: &mut Session, svc: &Arc<DafhneService>, quota: &Quota) -> Vec<ServerMessage> {
+    match msg {
+        ClientMessage::Ask { id, question, trace } => {
+            let Some(model) = svc.get_model(&session.model) else {
+                return vec![error(id, format!("model '{}' not found", session.model))];
+            };
+            if let Err(limited) = quota.take() {
+                return vec![error(id, limited.to_string())];
+            }
+            let resolved = context::resolve_conversation(
+                session
+                    .history
+                    .iter()
+                    .map(|(role, content)| (role.as_str(), content.as_str()))
+                    .chain(std::iter::once(("user", question.as_str()))),
+            );
+            let trace = trace.unwrap_or(session.trace);
+            let (model, resolved, answer, unknown_words) = blocking(move || {
+                let answer = model.ask(&resolved, trace);
+                let unknown = model.unknown_words(&resolved);
+                (model, resolved, answer, unknown)
+            })
+            .await;
+
+            session.history.push(("user".to_string(), question.clone()));
+            session.history.push(("assistant".to_string(), answer.text.clone()));
+            let excess = session.history.len().saturating_sub(MAX_HISTORY);
+            session.history.drain(..excess);
+
+            let mut replies = Vec::new();
+            let unknown_changed = session.note_unknown(&unknown_words);
+            replies.push(ServerMessage::Answer {
+                id,
+                model: model.id.clone(),
+                version: model.version,
+                resolved: (resolved != question).then_some(resolved),
+                question,
+                answer: Box::new(answer),
+                unknown_words,
+            });
+            if unknown_changed {
+                replies.push(session.message());
+            }
+            replies
+        }
+        ClientMessage::Session { model, trace } => {
+            if let Some(model) = model {
+                if svc.get_model(&model).is_none() {
+                    return vec![error(None, format!("model '{}' not found", model))];
+                }
+                if model != session.model {
+                    session.model = model;
+                    session.unknown.clear();
+                }
+            }
+            if let Some(trace) = trace {
+                session.trace = trace;
+            }
+            vec![session.message()]
+        }
+        ClientMessage::Teach { id, word, definition, examples, space, replace } => {
+            if !session.teach {
+                return vec![error(id, "This API key may not teach (it needs the 'teach' scope)".to_string())];
+            }
+            if definition.trim().is_empty() {
+                return vec![error(id, "A definition is required".to_string())];
+            }
+            if let Err(limited) = quota.take() {
+                return vec![error(id, limited.to_string())];
+            }
+            let entry = DictionaryEntry {
+                word,
+                definition: definition.trim().to_string(),
+                examples,
+                section: "TAUGHT".to_string(),
+                is_entity: false,
+            };
+            let (svc, model) = (svc.clone(), session.model.clone());
+            match blocking(move || svc.teach(&model, space.as_deref(), entry, replace)).await {
+                Ok(event) => {
+                    let word = event.word.clone().unwrap_or_default();
+                    let before = session.unknown.len();
+                    session.unknown.retain(|w| *w != word);
+                    let mut replies = vec![ServerMessage::Taught {
+                        id,
+                        model: event.model,
+                        version: event.version,
+                        word,
+                    }];
+                    if session.unknown.len() != before {
+                        replies.push(session.message());
+                    }
+                    replies
+                }
+                Err(e) => vec![error(id, e)],
+            }
+        }
+        ClientMessage::Reset => {
+            session.history.clear();
+            session.unknown.clear();
+            vec![session.message()]
+        }
+    }
+}
+
+/// Forward a model event; when the session's model was retrained or
+/// taught, drop the open topics it now knows.
+async fn model_event(event: ModelEvent, session: &mut Session, svc: &DafhneService) -> Vec<ServerMessage> {
+    let mut replies = Vec::new();
+    let retrained = event.model == session.model
+        && matches!(event.event, ModelEventKind::Retrained | ModelEventKind::Taught);
+    replies.push(ServerMessage::Model(event));
+    if retrained && !session.unknown.is_empty() {
+        if let Some(model) = svc.get_model(&session.model) {
+            let words = session.unknown.join(" ");
+            let still_unknown = blocking(move || model.unknown_words(&words)).await;
+            if still_unknown.len() != session.unknown.len() {
+                session.unknown = still_unknown;
+                replies.push(session.message());
+            }
+        }
+    }
+    replies
+}
+
+fn error(id: Option<Value>, error: String) -> ServerMessage {
+    ServerMessage::Error { id, error }
+}
+
+async fn send(socket: &mut WebSocket, msg: &ServerMessage) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(msg).expect("Failed to serialize WebSocket message");
+    socket.send(Message::Text(text.into())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn messages_are_tagged_by_type() {
+        let ask: ClientMessage =
+            serde_json::from_str(r#"{"type": "ask", "id": "q1", "question": "Is it big?"}"#).unwrap();
+        assert!(matches!(ask, ClientMessage::Ask { id: Some(_), trace: None, .. }));
+        assert!(matches!(serde_json::from_str(r#"{"type": "reset"}"#).unwrap(), ClientMessage::Reset));
+        let teach: ClientMessage =
+            serde_json::from_str(r#"{"type": "teach", "word": "pup", "definition": "a dog."}"#).unwrap();
+        assert!(matches!(teach, ClientMessage::Teach { id: None, space: None, replace: false, .. }));
+
+        let event = ServerMessage::Model(ModelEvent {
+            event: ModelEventKind::Retrained,
+            model: "dafhne-5".to_string(),
+            version: 2,
+            changed: vec![PathBuf::from("dict5.md")],
+            word: None,
+        });
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({"type": "model", "event": "retrained", "model": "dafhne-5", "version": 2, "changed": ["dict5.md"]})
+        );
+    }
+
+    #[test]
+    fn unknown_words_are_capped_oldest_first() {
+        let mut session = Session {
+            id: "s".to_string(),
+            model: "dafhne-5".to_string(),
+            trace: false,
+            teach: false,
+            history: Vec::new(),
+            unknown: Vec::new(),
+        };
+        let words: Vec<String> = (0..MAX_UNKNOWN + 5).map(|i| format!("w{}", i)).collect();
+        assert!(session.note_unknown(&words));
+        assert_eq!(session.unknown.len(), MAX_UNKNOWN);
+        assert_eq!(session.unknown[0], "w5");
+        assert!(!session.note_unknown(&words[MAX_UNKNOWN..]));
+        assert!(session.note_unknown(&["fresh".to_string()]));
+        assert_eq!(session.unknown.len(), MAX_UNKNOWN);
+        assert_eq!(session.unknown.last().map(String::as_str), Some("fresh"));
+    }
+
+    #[tokio::test]
+    async fn teach_needs_the_scope_and_closes_the_topic() {
+        let dir = crate::service::tests::registry_dir("ws_teach");
+        let mut svc = DafhneService::load(crate::registry::RegistrySource::File(dir.join("models.toml"))).unwrap();
+        svc.allow_teaching(true);
+        let svc = Arc::new(svc);
+        let quota = Quota::unlimited();
+        let mut session = Session {
+            id: "s".to_string(),
+            model: "tiny".to_string(),
+            trace: false,
+            teach: false,
+            history: Vec::new(),
+            unknown: vec!["pup".to_string(), "kit".to_string()],
+        };
+        let teach = || ClientMessage::Teach {
+            id: Some(Value::from(8)),
+            word: "pup".to_string(),
+            definition: "a dog.".to_string(),
+            examples: vec!["a pup is a dog".to_string()],
+            space: None,
+            replace: false,
+        };
+
+        let replies = handle(teach(), &mut session, &svc, &quota).await;
+        assert!(matches!(&replies[..], [ServerMessage::Error { error, .. }] if error.contains("'teach' scope")));
+        assert_eq!(svc.get_model("tiny").unwrap().version, 1);
+
+        session.teach = true;
+        let replies = handle(teach(), &mut session, &svc, &quota).await;
+        assert!(matches!(
+            &replies[..],
+            [ServerMessage::Taught { word, version: 2, .. }, ServerMessage::Session { unknown, .. }]
+                if word == "pup" && unknown == &["kit"]
+        ));
+        assert!(svc.get_model("tiny").unwrap().unknown_words("pup").is_empty());
+
+        // Same path as MCP: redefining needs replace
+        let replies = handle(teach(), &mut session, &svc, &quota).await;
+        assert!(matches!(&replies[..], [ServerMessage::Error { id: Some(_), error }] if error.contains("already defined")));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}